@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Feeds arbitrary `EnrollStatus`/`VerifyStatus` bodies through the argument
+//! parsing, which must turn anything it can't read into `None` without
+//! panicking.
+
+#![no_main]
+
+use fprint_client::fprint::parse_status_args;
+use libfuzzer_sys::fuzz_target;
+use zbus::zvariant::serialized::{Context, Data};
+use zbus::zvariant::{BE, LE, Signature};
+
+/// Signatures a status signal could arrive with: the real one, and those of a
+/// broken or hostile sender.
+const SIGNATURES: &[&str] = &["sb", "s", "b", "bs", "sbs", "(sb)", "v", "as", "a{sv}", ""];
+
+fuzz_target!(|input: &[u8]| {
+    // The first byte picks the byte order and signature, the rest is the body
+    let Some((&selector, body)) = input.split_first() else {
+        return;
+    };
+    let endian = if selector & 0x80 == 0 { LE } else { BE };
+    let signature = SIGNATURES[usize::from(selector & 0x7f) % SIGNATURES.len()];
+    let signature = Signature::try_from(signature).unwrap();
+
+    let data = Data::new(body, Context::new_dbus(endian, 0));
+    if let Some((result, _done)) = parse_status_args(&data, &signature) {
+        assert!(result.len() <= body.len());
+    }
+});
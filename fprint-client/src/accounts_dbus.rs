@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! AccountsService, which lists the users whose prints can be managed.
+
+use zbus::proxy;
+
+/// The list of accounts on the system.
+#[proxy(
+    interface = "org.freedesktop.Accounts",
+    default_service = "org.freedesktop.Accounts",
+    default_path = "/org/freedesktop/Accounts"
+)]
+pub trait Accounts {
+    fn list_cached_users(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+    fn find_user_by_name(&self, name: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    #[zbus(signal)]
+    fn user_added(&self, user: zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn user_deleted(&self, user: zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+}
+
+/// One account, at a path listed by [`AccountsProxy`].
+#[proxy(
+    interface = "org.freedesktop.Accounts.User",
+    default_service = "org.freedesktop.Accounts"
+)]
+pub trait User {
+    #[zbus(property)]
+    fn user_name(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn real_name(&self) -> zbus::Result<String>;
+
+    /// Path of the account's picture, which may not exist.
+    #[zbus(property)]
+    fn icon_file(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn system_account(&self) -> zbus::Result<bool>;
+
+    /// 0 for a standard account, 1 for an administrator.
+    #[zbus(property)]
+    fn account_type(&self) -> zbus::Result<i32>;
+}
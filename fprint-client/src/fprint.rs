@@ -0,0 +1,965 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Async helpers for the operations of fprintd.
+
+use crate::fprint_dbus::{DeviceProxy, ManagerProxy};
+use crate::polkit_dbus::{ALLOW_USER_INTERACTION, AuthorityProxy, AuthorizationResult};
+use crate::session::DeviceSession;
+use futures_util::sink::Sink;
+use futures_util::stream::{self, Stream};
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use zbus::zvariant::{OwnedObjectPath, Signature, Value, serialized};
+
+const LIST_CONCURRENCY: usize = 10;
+
+/// polkit action fprintd checks before managing the prints of another user.
+const SET_USERNAME_ACTION: &str = "net.reactivated.fprint.device.setusername";
+
+/// DBus calls taking longer than this are logged as slow.
+const SLOW_CALL_THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Bus name fprintd owns while it runs.
+const FPRINT_BUS_NAME: &str = "net.reactivated.Fprint";
+
+/// Where fprintd exports its readers, under an ObjectManager.
+const DEVICES_PATH: &str = "/net/reactivated/Fprint/Device";
+
+/// Interface of each reader fprintd exports.
+const DEVICE_INTERFACE: &str = "net.reactivated.Fprint.Device";
+
+/// systemd unit of the fprintd service.
+const FPRINTD_UNIT: &str = "fprintd.service";
+
+/// Progress of a running operation, as reported by fprintd.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// An enrollment started, with the number of scans it takes if known.
+    EnrollStart(Option<u32>),
+    /// An `EnrollStatus` signal: the result and whether it was the last one.
+    EnrollStatus(String, bool),
+    /// A `VerifyStatus` signal: the result and whether it was the last one.
+    VerifyStatus(String, bool),
+    /// A `VerifyFingerSelected` signal: the finger the scan is compared against.
+    VerifyFingerSelected(String),
+    /// Every print of the user was removed during a clear.
+    UserCleared(String),
+    /// A signal that could not be read, which ends the operation.
+    InvalidSignal,
+}
+
+/// A reader known to fprintd, with what it can do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Object path of the reader, for [`DeviceProxy`].
+    pub path: OwnedObjectPath,
+    /// Name reported by the driver, empty if unreadable.
+    pub name: String,
+    /// How a finger is presented to the reader.
+    pub scan_type: ScanType,
+    /// Scans an enrollment takes, if the reader reports it.
+    pub stages: Option<u32>,
+    /// What the reader supports beyond the basics.
+    pub caps: DeviceCaps,
+}
+
+/// Awaits a DBus call, warning when it takes longer than two seconds.
+pub async fn timed<F: Future>(name: &str, call: F) -> F::Output {
+    let start = Instant::now();
+    let output = call.await;
+    let elapsed = start.elapsed();
+    if elapsed > SLOW_CALL_THRESHOLD {
+        tracing::warn!(?elapsed, "Slow DBus call {name}");
+    } else {
+        tracing::debug!(?elapsed, "DBus call {name}");
+    }
+    output
+}
+
+/// Lists every reader known to fprintd, with the default one first.
+pub async fn find_devices(connection: &zbus::Connection) -> zbus::Result<Vec<DeviceInfo>> {
+    let manager = ManagerProxy::new(connection).await?;
+    let default = timed("GetDefaultDevice", manager.get_default_device())
+        .await
+        .ok();
+
+    let mut devices = Vec::new();
+    for path in timed("GetDevices", manager.get_devices()).await? {
+        let proxy = DeviceProxy::builder(connection)
+            .path(path.clone())?
+            .build()
+            .await?;
+        let name = proxy.name().await.unwrap_or_else(|e| {
+            tracing::warn!(%e, "Failed to read name of device {path}");
+            String::new()
+        });
+        let scan_type = proxy
+            .scan_type()
+            .await
+            .map(|scan_type| ScanType::from_property(&scan_type))
+            .unwrap_or_default();
+        let stages = match proxy.num_enroll_stages().await {
+            Ok(n) if n > 0 => Some(n as u32),
+            _ => None,
+        };
+        let caps = DeviceCaps::from_device_name(&name);
+        devices.push(DeviceInfo {
+            path,
+            name,
+            scan_type,
+            stages,
+            caps,
+        });
+    }
+
+    devices.sort_by_key(|device| Some(&device.path) != default.as_ref());
+    Ok(devices)
+}
+
+/// How a finger is presented to the reader.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScanType {
+    /// The finger rests on the sensor.
+    #[default]
+    Press,
+    /// The finger is swiped across a narrow sensor.
+    Swipe,
+}
+
+impl ScanType {
+    /// Parses the `scan-type` property of a device.
+    pub fn from_property(scan_type: &str) -> Self {
+        match scan_type {
+            "swipe" => ScanType::Swipe,
+            _ => ScanType::Press,
+        }
+    }
+}
+
+/// Where a reader keeps its enrolled templates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TemplateStorage {
+    /// Templates are stored on disk by fprintd.
+    #[default]
+    Host,
+    /// Templates are stored inside the sensor (match-on-chip).
+    Device,
+}
+
+impl TemplateStorage {
+    /// Guesses the storage from the device name reported by fprintd.
+    ///
+    /// libfprint names its match-on-chip drivers "... MOC ..." or "Match-On-Chip",
+    /// with Synaptics sensors being the exception.
+    pub fn from_device_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        let is_moc = name.contains("match-on-chip")
+            || name.contains("synaptics")
+            || name
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word == "moc");
+
+        if is_moc {
+            TemplateStorage::Device
+        } else {
+            TemplateStorage::Host
+        }
+    }
+}
+
+/// What a reader supports that changes how it should be handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeviceCaps {
+    /// Where the reader keeps its prints.
+    pub storage: TemplateStorage,
+    /// Prints outlive a reinstall and are seen by other systems on the machine.
+    pub shared_with_other_systems: bool,
+    /// The reader refuses a finger already enrolled for another user.
+    pub rejects_duplicates: bool,
+}
+
+impl DeviceCaps {
+    /// Capabilities of a reader, guessed from the name reported by fprintd.
+    pub fn from_device_name(name: &str) -> Self {
+        Self::for_storage(TemplateStorage::from_device_name(name))
+    }
+
+    /// Capabilities that follow from where a reader keeps its prints.
+    pub fn for_storage(storage: TemplateStorage) -> Self {
+        let on_chip = storage == TemplateStorage::Device;
+        Self {
+            storage,
+            shared_with_other_systems: on_chip,
+            rejects_duplicates: on_chip,
+        }
+    }
+}
+
+/// What the running fprintd offers, which differs between its versions.
+///
+/// Everything is assumed to be there until fprintd was asked, so a failed
+/// probe leaves the errors to the calls themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `DeleteEnrolledFinger`, deleting a single finger, since fprintd 1.92.
+    pub delete_finger: bool,
+    /// `DeleteEnrolledFingers2`, deleting every print of the user that
+    /// claimed the reader, since fprintd 1.90.
+    pub delete_all_fingers: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            delete_finger: true,
+            delete_all_fingers: true,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Reads the methods a reader has from its introspection data.
+    pub fn from_introspection(xml: &str) -> Self {
+        let has_method = |name: &str| xml.contains(&format!("<method name=\"{name}\""));
+        Self {
+            delete_finger: has_method("DeleteEnrolledFinger"),
+            delete_all_fingers: has_method("DeleteEnrolledFingers2"),
+        }
+    }
+}
+
+/// Asks fprintd what the reader at `path` offers.
+pub async fn probe_capabilities(
+    connection: &zbus::Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<Capabilities> {
+    let proxy = zbus::fdo::IntrospectableProxy::builder(connection)
+        .destination(FPRINT_BUS_NAME)?
+        .path(path)?
+        .build()
+        .await?;
+    let xml = timed("Introspect", proxy.introspect()).await?;
+    Ok(Capabilities::from_introspection(&xml))
+}
+
+/// Reports whether fprintd is on the bus each time it starts or goes away.
+///
+/// The stream ends when the connection to the bus is lost.
+pub async fn watch_fprintd(
+    connection: &zbus::Connection,
+) -> zbus::Result<impl Stream<Item = bool> + use<>> {
+    let dbus = zbus::fdo::DBusProxy::new(connection).await?;
+    let changes = dbus
+        .receive_name_owner_changed_with_args(&[(0, FPRINT_BUS_NAME)])
+        .await?;
+    Ok(changes.filter_map(|signal| async move {
+        signal.args().ok().map(|args| args.new_owner().is_some())
+    }))
+}
+
+/// Reports each time fprintd adds or removes a reader, as one is plugged in
+/// or out while fprintd runs.
+///
+/// fprintd's Manager has no signals for this, but the ObjectManager it
+/// exports the readers with sends `InterfacesAdded` and `InterfacesRemoved`.
+/// The stream ends when the connection to the bus is lost.
+pub async fn watch_devices(
+    connection: &zbus::Connection,
+) -> zbus::Result<impl Stream<Item = ()> + use<>> {
+    let objects = zbus::fdo::ObjectManagerProxy::builder(connection)
+        .destination(FPRINT_BUS_NAME)?
+        .path(DEVICES_PATH)?
+        .build()
+        .await?;
+    let added = objects.receive_interfaces_added().await?.filter(|signal| {
+        let device = signal.args().is_ok_and(|args| {
+            args.interfaces_and_properties()
+                .keys()
+                .any(|name| name.as_str() == DEVICE_INTERFACE)
+        });
+        std::future::ready(device)
+    });
+    let removed = objects
+        .receive_interfaces_removed()
+        .await?
+        .filter(|signal| {
+            let device = signal.args().is_ok_and(|args| {
+                args.interfaces()
+                    .iter()
+                    .any(|name| name.as_str() == DEVICE_INTERFACE)
+            });
+            std::future::ready(device)
+        });
+
+    Ok(stream::select(
+        added.map(|_| tracing::debug!("fprintd added a reader")),
+        removed.map(|_| tracing::debug!("fprintd removed a reader")),
+    ))
+}
+
+/// Whether fprintd is there to answer calls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceState {
+    /// fprintd owns its name on the bus.
+    Running,
+    /// fprintd is installed, and DBus starts it on the first call.
+    Activatable,
+    /// Nothing on the bus provides fprintd.
+    NotInstalled,
+}
+
+/// Looks up fprintd on the bus without starting it.
+pub async fn service_state(connection: &zbus::Connection) -> zbus::Result<ServiceState> {
+    let dbus = zbus::fdo::DBusProxy::new(connection).await?;
+    let name = zbus::names::WellKnownName::try_from(FPRINT_BUS_NAME)?;
+
+    if dbus.name_has_owner(name.clone().into()).await? {
+        return Ok(ServiceState::Running);
+    }
+    let activatable = dbus.list_activatable_names().await?;
+    if activatable.iter().any(|n| n.as_str() == FPRINT_BUS_NAME) {
+        Ok(ServiceState::Activatable)
+    } else {
+        Ok(ServiceState::NotInstalled)
+    }
+}
+
+/// Starts fprintd through DBus activation, which needs no password.
+pub async fn start_fprintd(connection: &zbus::Connection) -> zbus::Result<()> {
+    let dbus = zbus::fdo::DBusProxy::new(connection).await?;
+    let name = zbus::names::WellKnownName::try_from(FPRINT_BUS_NAME)?;
+    timed("StartServiceByName", dbus.start_service_by_name(name, 0)).await?;
+    Ok(())
+}
+
+/// Restarts fprintd through `pkexec`, which asks for an administrator password.
+///
+/// A restart also starts the service when it isn't running at all.
+pub async fn restart_fprintd() -> Result<(), String> {
+    let status = tokio::process::Command::new("pkexec")
+        .args(["systemctl", "restart", FPRINTD_UNIT])
+        .status()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("systemctl exited with {status}"))
+    }
+}
+
+/// Asks polkit whether this app may manage the prints of other users,
+/// prompting for a password if needed.
+///
+/// fprintd checks the same action for our bus name, so a kept authorization
+/// covers the following calls.
+pub async fn authorize_other_users(connection: &zbus::Connection) -> zbus::Result<bool> {
+    let result = check_other_users(connection, ALLOW_USER_INTERACTION).await?;
+    Ok(result.is_authorized)
+}
+
+/// Asks polkit, without prompting, whether this app could manage the prints
+/// of other users, either right away or after authenticating.
+///
+/// A `false` means polkit would refuse even an administrator password, so
+/// fprintd would answer with `PermissionDenied`.
+pub async fn may_manage_other_users(connection: &zbus::Connection) -> zbus::Result<bool> {
+    let result = check_other_users(connection, 0).await?;
+    Ok(result.is_authorized || result.is_challenge)
+}
+
+/// Checks the action fprintd requires for other users against our bus name.
+async fn check_other_users(
+    connection: &zbus::Connection,
+    flags: u32,
+) -> zbus::Result<AuthorizationResult> {
+    let name = connection
+        .unique_name()
+        .ok_or_else(|| zbus::Error::Failure("Connection has no unique name".to_string()))?;
+    let subject = (
+        "system-bus-name",
+        HashMap::from([("name", Value::from(name.as_str()))]),
+    );
+
+    let authority = AuthorityProxy::new(connection).await?;
+    authority
+        .check_authorization(&subject, SET_USERNAME_ACTION, HashMap::new(), flags, "")
+        .await
+}
+
+/// Lists the fingers `username` has enrolled on `device`, by fprintd id.
+pub async fn list_enrolled_fingers_dbus(
+    device: &DeviceProxy<'static>,
+    username: String,
+) -> zbus::Result<Vec<String>> {
+    validate_username(&username)?;
+    timed("ListEnrolledFingers", device.list_enrolled_fingers(&username)).await
+}
+
+/// Returns which of `usernames` already have `finger` enrolled on the device.
+///
+/// Users whose prints can't be listed are skipped.
+pub async fn find_users_with_finger(
+    device: &DeviceProxy<'static>,
+    usernames: Vec<String>,
+    finger: &str,
+) -> Vec<String> {
+    stream::iter(usernames)
+        .map(|username| async move {
+            match list_enrolled_fingers_dbus(device, username.clone()).await {
+                Ok(fingers) if fingers.iter().any(|f| f == finger) => Some(username),
+                Ok(_) => None,
+                Err(e) => {
+                    tracing::debug!(%e, "Failed to list fingers of {username}");
+                    None
+                }
+            }
+        })
+        .buffer_unordered(LIST_CONCURRENCY)
+        .filter_map(|res| async move { res })
+        .collect()
+        .await
+}
+
+/// Lists the enrolled fingers of each of `usernames`, in the given order.
+///
+/// Users without prints, or whose prints can't be listed, are skipped.
+pub async fn list_fingers_by_user(
+    device: &DeviceProxy<'static>,
+    usernames: Vec<String>,
+) -> Vec<(String, Vec<String>)> {
+    let mut fingers: Vec<(usize, String, Vec<String>)> =
+        stream::iter(usernames.into_iter().enumerate())
+            .map(|(i, username)| async move {
+                match list_enrolled_fingers_dbus(device, username.clone()).await {
+                    Ok(fingers) if !fingers.is_empty() => Some((i, username, fingers)),
+                    Ok(_) => None,
+                    Err(e) => {
+                        tracing::debug!(%e, "Failed to list fingers of {username}");
+                        None
+                    }
+                }
+            })
+            .buffer_unordered(LIST_CONCURRENCY)
+            .filter_map(|res| async move { res })
+            .collect()
+            .await;
+
+    fingers.sort_by_key(|(i, ..)| *i);
+    fingers
+        .into_iter()
+        .map(|(_, username, fingers)| (username, fingers))
+        .collect()
+}
+
+/// Claims and releases the reader to find out whether another program still holds it.
+pub async fn probe_claim(device: &DeviceProxy<'static>, username: String) -> zbus::Result<()> {
+    validate_username(&username)?;
+    DeviceSession::claim(device, &username).await?.release().await
+}
+
+/// Deletes one enrolled `finger` of `username`, claiming the reader for it.
+pub async fn delete_fingerprint_dbus(
+    connection: &zbus::Connection,
+    path: OwnedObjectPath,
+    finger: String,
+    username: String,
+) -> zbus::Result<()> {
+    validate_username(&username)?;
+    let device = DeviceProxy::builder(connection).path(path)?.build().await?;
+
+    let session = DeviceSession::claim(&device, &username).await?;
+    let res = timed("DeleteEnrolledFinger", device.delete_enrolled_finger(&finger)).await;
+    let rel_res = session.release().await;
+    res.and(rel_res)
+}
+
+/// Deletes every print of `username`, claiming the reader for it.
+pub async fn delete_fingers(
+    connection: &zbus::Connection,
+    path: OwnedObjectPath,
+    username: String,
+) -> zbus::Result<()> {
+    validate_username(&username)?;
+    let device = DeviceProxy::builder(connection).path(path)?.build().await?;
+
+    let session = DeviceSession::claim(&device, &username).await?;
+    let _ = timed("DeleteEnrolledFingers2", device.delete_enrolled_fingers2()).await;
+    session.release().await
+}
+
+/// Who a clear of the device got to, and how it went for each of them.
+#[derive(Clone, Debug)]
+pub struct ClearReport<E = zbus::Error> {
+    /// Users left without prints, in the order they were cleared.
+    pub cleared: Vec<String>,
+    /// Users whose prints could not be cleared, with the reason.
+    pub failed: Vec<(String, E)>,
+}
+
+impl<E> Default for ClearReport<E> {
+    fn default() -> Self {
+        Self {
+            cleared: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<E> ClearReport<E> {
+    /// Converts the errors, e.g. into ones that can be cloned.
+    pub fn map_err<F>(self, mut f: impl FnMut(E) -> F) -> ClearReport<F> {
+        ClearReport {
+            cleared: self.cleared,
+            failed: self
+                .failed
+                .into_iter()
+                .map(|(username, e)| (username, f(e)))
+                .collect(),
+        }
+    }
+}
+
+/// Clears the prints of each of `usernames` in turn, sending
+/// [`Event::UserCleared`] once a user has no prints left.
+///
+/// A user that fails is reported and the next one is cleared all the same.
+/// Stops before the next user once `cancel` is set, so the remaining users
+/// can be cleared later.
+pub async fn clear_all_fingers_dbus<S>(
+    connection: &zbus::Connection,
+    path: OwnedObjectPath,
+    usernames: Vec<String>,
+    cancel: &AtomicBool,
+    output: &mut S,
+) -> zbus::Result<ClearReport>
+where
+    S: Sink<Event> + Unpin + Send,
+    S::Error: std::fmt::Debug + Send,
+{
+    let device = DeviceProxy::builder(connection).path(path)?.build().await?;
+
+    let mut report = ClearReport::default();
+    for username in usernames {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        match clear_user(&device, &username).await {
+            Ok(()) => {
+                let _ = output.send(Event::UserCleared(username.clone())).await;
+                report.cleared.push(username);
+            }
+            Err(e) => {
+                tracing::debug!(%e, "Failed to clear the prints of {username}");
+                report.failed.push((username, e));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Claims the reader for `username` and deletes all of their prints.
+async fn clear_user(device: &DeviceProxy<'static>, username: &str) -> zbus::Result<()> {
+    validate_username(username)?;
+
+    let session = DeviceSession::claim(device, username).await?;
+    let res = clear_user_fingers(device, username).await;
+    let rel_res = session.release().await;
+    res.and(rel_res)
+}
+
+/// Deletes every print of the user the device is claimed for.
+async fn clear_user_fingers(device: &DeviceProxy<'static>, username: &str) -> zbus::Result<()> {
+    let fingers = match timed("ListEnrolledFingers", device.list_enrolled_fingers(username)).await
+    {
+        Ok(fingers) => fingers,
+        // Nothing to clear
+        Err(zbus::Error::MethodError(name, _, _))
+            if name.as_str() == "net.reactivated.Fprint.Error.NoEnrolledPrints" =>
+        {
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    for finger in fingers {
+        timed("DeleteEnrolledFinger", device.delete_enrolled_finger(&finger)).await?;
+    }
+    Ok(())
+}
+
+/// Enrolls `finger_name` for `username`, sending [`Event::EnrollStart`] and
+/// then each [`Event::EnrollStatus`] to `output` until the last one.
+///
+/// The reader is claimed for the enrollment and released once it ends.
+pub async fn enroll_fingerprint_process<S>(
+    connection: zbus::Connection,
+    path: &OwnedObjectPath,
+    finger_name: &str,
+    username: &str,
+    output: &mut S,
+) -> zbus::Result<()>
+where
+    S: Sink<Event> + Unpin + Send,
+    S::Error: std::fmt::Debug + Send,
+{
+    validate_username(username)?;
+    let device = DeviceProxy::builder(&connection)
+        .path(path)?
+        .build()
+        .await?;
+
+    // Claim device
+    let session = DeviceSession::claim(&device, username).await?;
+
+    let total_stages = match device.num_enroll_stages().await {
+        Ok(n) if n > 0 => Some(n as u32),
+        _ => None,
+    };
+    let _ = output.send(Event::EnrollStart(total_stages)).await;
+
+    // Start enrollment
+    if let Err(e) = device.enroll_start(finger_name).await {
+        let _ = session.release().await;
+        return Err(e);
+    }
+
+    // Listen for signals
+    let mut stream = match device.receive_enroll_status().await {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = session.release().await;
+            return Err(e);
+        }
+    };
+
+    while let Some(signal) = stream.next().await {
+        match status_args(signal.message()) {
+            Some((result, done)) => {
+                let _ = output.send(Event::EnrollStatus(result, done)).await;
+
+                if done {
+                    break;
+                }
+            }
+            None => {
+                let _ = output.send(Event::InvalidSignal).await;
+                break;
+            }
+        }
+    }
+
+    // Release device
+    let _ = session.release().await;
+
+    Ok(())
+}
+
+/// Verifies `finger_name` of `username`, or any of their fingers for `any`,
+/// sending each [`Event::VerifyStatus`] to `output` until the last one.
+///
+/// The reader is claimed for the verification and released once it ends.
+pub async fn verify_fingerprint_process<S>(
+    connection: zbus::Connection,
+    path: &OwnedObjectPath,
+    finger_name: &str,
+    username: &str,
+    output: &mut S,
+) -> zbus::Result<()>
+where
+    S: Sink<Event> + Unpin + Send,
+    S::Error: std::fmt::Debug + Send,
+{
+    validate_username(username)?;
+    let device = DeviceProxy::builder(&connection)
+        .path(path)?
+        .build()
+        .await?;
+
+    let session = DeviceSession::claim(&device, username).await?;
+
+    // Subscribe before starting so the first result can't be missed
+    let signals = async {
+        let selected = device.receive_verify_finger_selected().await?;
+        let status = device.receive_verify_status().await?;
+        Ok::<_, zbus::Error>((selected, status))
+    };
+    let (selected, status) = match signals.await {
+        Ok(streams) => streams,
+        Err(e) => {
+            let _ = session.release().await;
+            return Err(e);
+        }
+    };
+
+    if let Err(e) = device.verify_start(finger_name).await {
+        let _ = session.release().await;
+        return Err(e);
+    }
+
+    let selected = selected.map(|signal| {
+        signal
+            .args()
+            .map(|args| Event::VerifyFingerSelected(args.finger_name))
+            .map_err(drop)
+    });
+    let status = status.map(|signal| {
+        status_args(signal.message())
+            .map(|(result, done)| Event::VerifyStatus(result, done))
+            .ok_or(())
+    });
+    let mut events = stream::select(selected, status);
+
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(event) => {
+                let done = matches!(event, Event::VerifyStatus(_, true));
+                let _ = output.send(event).await;
+
+                if done {
+                    break;
+                }
+            }
+            Err(_) => {
+                let _ = output.send(Event::InvalidSignal).await;
+                break;
+            }
+        }
+    }
+
+    let _ = device.verify_stop().await;
+    let _ = session.release().await;
+
+    Ok(())
+}
+
+/// Reads the `(result, done)` arguments of an `EnrollStatus` or `VerifyStatus`
+/// signal, `None` if they are malformed.
+fn status_args(message: &zbus::Message) -> Option<(String, bool)> {
+    let body = message.body();
+    let args = parse_status_args(body.data(), body.signature());
+    let member = message.header().member().map(|member| member.to_string());
+    match &args {
+        Some((result, done)) => tracing::debug!(?member, result, done, "Status signal"),
+        None => {
+            let signature = body.signature().to_string();
+            tracing::warn!(?member, signature, "Malformed status signal");
+        }
+    }
+    args
+}
+
+/// Reads `(result, done)` status arguments from a serialized signal body with
+/// the given `signature`, `None` if the body doesn't hold them.
+///
+/// This is the parsing behind [`Event::EnrollStatus`] and [`Event::VerifyStatus`],
+/// public so it can be fuzzed.
+pub fn parse_status_args(
+    data: &serialized::Data<'_, '_>,
+    signature: &Signature,
+) -> Option<(String, bool)> {
+    data.deserialize_for_dynamic_signature(signature.clone())
+        .ok()
+        .map(|(args, _)| args)
+}
+
+fn validate_username(username: &str) -> zbus::Result<()> {
+    if username.is_empty() {
+        return Err(zbus::Error::Failure("Username cannot be empty".to_string()));
+    }
+    if username.len() > 255 {
+        return Err(zbus::Error::Failure("Username is too long".to_string()));
+    }
+    if !username.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.') {
+        return Err(zbus::Error::Failure(format!(
+            "Invalid characters in username: {}",
+            username
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_username() {
+        // Valid usernames
+        assert!(validate_username("user").is_ok());
+        assert!(validate_username("user1").is_ok());
+        assert!(validate_username("user_name").is_ok());
+        assert!(validate_username("user-name").is_ok());
+        assert!(validate_username("user.name").is_ok());
+        assert!(validate_username("u").is_ok());
+        assert!(validate_username("123").is_ok());
+        assert!(validate_username("User").is_ok()); // Uppercase is allowed by our validation
+
+        // Invalid usernames
+        assert!(validate_username("").is_err());
+        assert!(validate_username("user name").is_err()); // space
+        assert!(validate_username("user/name").is_err()); // slash
+        assert!(validate_username("user@name").is_err()); // @
+        assert!(validate_username("user!name").is_err()); // !
+        assert!(validate_username("user?name").is_err()); // ?
+
+        let long_name = "a".repeat(256);
+        assert!(validate_username(&long_name).is_err());
+
+        let max_len_name = "a".repeat(255);
+        assert!(validate_username(&max_len_name).is_ok());
+    }
+
+    #[test]
+    fn test_scan_type_from_property() {
+        assert_eq!(ScanType::from_property("swipe"), ScanType::Swipe);
+        assert_eq!(ScanType::from_property("press"), ScanType::Press);
+        assert_eq!(ScanType::from_property(""), ScanType::Press);
+    }
+
+    #[test]
+    fn test_template_storage_from_device_name() {
+        let on_chip = [
+            "Goodix MOC Fingerprint Sensor",
+            "Elan MOC Sensors",
+            "FPC MOC Fingerprint Sensor",
+            "Egis Technology (LighTuning) Match-On-Chip",
+            "Synaptics Sensors",
+        ];
+        for name in on_chip {
+            assert_eq!(TemplateStorage::from_device_name(name), TemplateStorage::Device, "{name}");
+        }
+
+        let on_host = [
+            "Validity VFS0050",
+            "ElanTech Fingerprint Sensor",
+            "AuthenTec AES2501",
+            "Mock device",
+            "",
+        ];
+        for name in on_host {
+            assert_eq!(TemplateStorage::from_device_name(name), TemplateStorage::Host, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_device_caps_from_device_name() {
+        let caps = DeviceCaps::from_device_name("Goodix MOC Fingerprint Sensor");
+        assert_eq!(caps.storage, TemplateStorage::Device);
+        assert!(caps.shared_with_other_systems);
+        assert!(caps.rejects_duplicates);
+
+        assert_eq!(DeviceCaps::from_device_name("Validity VFS0050"), DeviceCaps::default());
+    }
+
+    #[test]
+    fn test_capabilities_from_introspection() {
+        // As fprintd 1.90 describes a reader, before deleting single fingers
+        let xml = r#"<node>
+  <interface name="net.reactivated.Fprint.Device">
+    <method name="ListEnrolledFingers">
+      <arg type="s" name="username" direction="in"/>
+      <arg type="as" name="enrolled_fingers" direction="out"/>
+    </method>
+    <method name="DeleteEnrolledFingers2"/>
+    <method name="Claim">
+      <arg type="s" name="username" direction="in"/>
+    </method>
+  </interface>
+</node>"#;
+        let caps = Capabilities::from_introspection(xml);
+        assert!(!caps.delete_finger);
+        assert!(caps.delete_all_fingers);
+
+        let xml = xml.replace(
+            "<method name=\"Claim\">",
+            "<method name=\"DeleteEnrolledFinger\">",
+        );
+        assert_eq!(Capabilities::from_introspection(&xml), Capabilities::default());
+    }
+
+    #[test]
+    fn test_clear_report_map_err() {
+        let report = ClearReport {
+            cleared: vec!["alice".to_string()],
+            failed: vec![("bob".to_string(), zbus::Error::InvalidReply)],
+        };
+        let report = report.map_err(|e| e.to_string());
+        assert_eq!(report.cleared, ["alice"]);
+        assert_eq!(report.failed[0].0, "bob");
+        assert_eq!(report.failed[0].1, zbus::Error::InvalidReply.to_string());
+    }
+
+    #[test]
+    fn test_parse_status_args() {
+        use zbus::zvariant::{LE, serialized::Context};
+
+        let context = Context::new_dbus(LE, 0);
+        let status = zbus::zvariant::to_bytes(context, &("enroll-completed", true)).unwrap();
+        let signature = Signature::try_from("sb").unwrap();
+        assert_eq!(
+            parse_status_args(&status, &signature),
+            Some(("enroll-completed".to_string(), true))
+        );
+
+        // Cut short, with the wrong signature, or not a status at all
+        let short = serialized::Data::new(&status[..status.len() - 2], context);
+        assert_eq!(parse_status_args(&short, &signature), None);
+        let swapped = Signature::try_from("bs").unwrap();
+        assert_eq!(parse_status_args(&status, &swapped), None);
+        let garbage = serialized::Data::new(&[0xff; 16][..], context);
+        assert_eq!(parse_status_args(&garbage, &signature), None);
+        assert_eq!(parse_status_args(&garbage, &Signature::try_from("s").unwrap()), None);
+    }
+
+    struct FakeDevice;
+
+    #[zbus::interface(name = "net.reactivated.Fprint.Device")]
+    impl FakeDevice {
+        #[zbus(property)]
+        fn name(&self) -> String {
+            "Fake Reader".to_string()
+        }
+    }
+
+    struct OtherInterface;
+
+    #[zbus::interface(name = "org.example.Other")]
+    impl OtherInterface {}
+
+    #[tokio::test]
+    async fn test_watch_devices() {
+        let (server_stream, client_stream) = tokio::net::UnixStream::pair().unwrap();
+        let server = zbus::connection::Builder::unix_stream(server_stream)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(DEVICES_PATH, zbus::fdo::ObjectManager)
+            .unwrap()
+            .build();
+        let client = zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build();
+        let (server, client) = futures_util::try_join!(server, client).unwrap();
+        let mut changes = std::pin::pin!(watch_devices(&client).await.unwrap());
+
+        let objects = server.object_server();
+        let reader = format!("{DEVICES_PATH}/0");
+        // Other objects below the manager don't count
+        objects.at(format!("{DEVICES_PATH}/other"), OtherInterface).await.unwrap();
+        objects.at(reader.as_str(), FakeDevice).await.unwrap();
+        objects.remove::<FakeDevice, _>(reader.as_str()).await.unwrap();
+
+        let wait = Duration::from_secs(5);
+        for _ in 0..2 {
+            let change = tokio::time::timeout(wait, changes.next()).await;
+            assert_eq!(change.unwrap(), Some(()));
+        }
+        let wait = Duration::from_millis(200);
+        assert!(tokio::time::timeout(wait, changes.next()).await.is_err());
+    }
+}
@@ -1,16 +1,24 @@
 // SPDX-License-Identifier: MPL-2.0
 
+//! fprintd's own interfaces, as described in its DBus API documentation.
+
 use zbus::proxy;
 
+/// Finds the readers fprintd knows about.
 #[proxy(
     interface = "net.reactivated.Fprint.Manager",
     default_service = "net.reactivated.Fprint",
     default_path = "/net/reactivated/Fprint/Manager"
 )]
 pub trait Manager {
+    fn get_devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
     fn get_default_device(&self) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
 }
 
+/// A single reader, at a path returned by [`ManagerProxy`].
+///
+/// Every call but the properties needs the reader to be claimed first, see
+/// [`crate::session::DeviceSession`].
 #[proxy(
     interface = "net.reactivated.Fprint.Device",
     default_service = "net.reactivated.Fprint"
@@ -23,10 +31,26 @@ pub trait Device {
     fn delete_enrolled_fingers2(&self) -> zbus::Result<()>;
     fn enroll_start(&self, finger_name: &str) -> zbus::Result<()>;
     fn enroll_stop(&self) -> zbus::Result<()>;
+    fn verify_start(&self, finger_name: &str) -> zbus::Result<()>;
+    fn verify_stop(&self) -> zbus::Result<()>;
 
     #[zbus(signal)]
     fn enroll_status(&self, result: String, done: bool) -> zbus::Result<()>;
 
+    #[zbus(signal)]
+    fn verify_status(&self, result: String, done: bool) -> zbus::Result<()>;
+
+    /// The finger a verification compares against, `any` when the reader
+    /// identifies among all of them.
+    #[zbus(signal)]
+    fn verify_finger_selected(&self, finger_name: String) -> zbus::Result<()>;
+
     #[zbus(property, name = "num-enroll-stages")]
     fn num_enroll_stages(&self) -> zbus::Result<i32>;
+
+    #[zbus(property, name = "name")]
+    fn name(&self) -> zbus::Result<String>;
+
+    #[zbus(property, name = "scan-type")]
+    fn scan_type(&self) -> zbus::Result<String>;
 }
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Client for fprintd and the system services around it.
+//!
+//! Shared by the cosmic-ext-fprint window and command line mode, and usable by
+//! other COSMIC components that manage fingerprints.
+//!
+//! Everything is async and runs on tokio. Operations that report progress
+//! send [`fprint::Event`]s to a sink, so a caller can forward them to its own
+//! UI as they come:
+//!
+//! ```no_run
+//! use fprint_client::fprint::{Event, enroll_fingerprint_process, find_devices};
+//!
+//! # async fn enroll() -> zbus::Result<()> {
+//! let connection = zbus::Connection::system().await?;
+//! let devices = find_devices(&connection).await?;
+//! let Some(device) = devices.first() else {
+//!     return Ok(());
+//! };
+//!
+//! let mut events = std::pin::pin!(futures_util::sink::unfold((), |(), event| async move {
+//!     if let Event::EnrollStatus(result, done) = event {
+//!         println!("{result} {done}");
+//!     }
+//!     Ok::<_, std::convert::Infallible>(())
+//! }));
+//! let path = device.path.clone();
+//! enroll_fingerprint_process(connection, &path, "right-index-finger", "alice", &mut events)
+//!     .await
+//! # }
+//! ```
+//!
+//! The `*_dbus` modules hold the raw proxies for fprintd and the services the
+//! helpers rely on, for callers that need something the helpers don't cover.
+
+#![warn(missing_docs)]
+
+// The proxies zbus generates have items no doc comment reaches
+#[allow(missing_docs)]
+pub mod accounts_dbus;
+pub mod fprint;
+#[allow(missing_docs)]
+pub mod fprint_dbus;
+#[allow(missing_docs)]
+pub mod logind_dbus;
+#[allow(missing_docs)]
+pub mod notifications_dbus;
+#[allow(missing_docs)]
+pub mod polkit_dbus;
+#[allow(missing_docs)]
+pub mod power_profiles_dbus;
+pub mod session;
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! logind, which tells whether the caller's session is at the machine.
+
+use zbus::proxy;
+use zbus::zvariant::OwnedObjectPath;
+
+/// The logind session of the calling process, whichever it is.
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+pub trait Session {
+    #[zbus(property)]
+    fn remote(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn remote_host(&self) -> zbus::Result<String>;
+
+    /// `x11`, `wayland`, `mir`, `tty` or `unspecified`.
+    #[zbus(property, name = "Type")]
+    fn session_type(&self) -> zbus::Result<String>;
+
+    /// Id and object path of the seat, an empty id when the session has none.
+    #[zbus(property)]
+    fn seat(&self) -> zbus::Result<(String, OwnedObjectPath)>;
+}
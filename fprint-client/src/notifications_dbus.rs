@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The desktop's notification server, on the session bus.
+
+// The signature of `Notify` is fixed by the specification
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::HashMap;
+use zbus::proxy;
+use zbus::zvariant::Value;
+
+/// Shows notifications, per the freedesktop Desktop Notifications specification.
+#[proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+pub trait Notifications {
+    /// Shows a notification, or replaces the one `replaces_id` names when it
+    /// isn't 0, and returns its id. An `expire_timeout` of -1 leaves how long
+    /// it stays up to the server.
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+}
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! polkit, which asks for the password an action on other users needs.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use zbus::proxy;
+use zbus::zvariant::{Type, Value};
+
+/// Lets polkit prompt the user for a password.
+pub const ALLOW_USER_INTERACTION: u32 = 1;
+
+/// The authority deciding whether a subject may perform an action.
+#[proxy(
+    interface = "org.freedesktop.PolicyKit1.Authority",
+    default_service = "org.freedesktop.PolicyKit1",
+    default_path = "/org/freedesktop/PolicyKit1/Authority"
+)]
+pub trait Authority {
+    #[zbus(allow_interactive_auth)]
+    fn check_authorization(
+        &self,
+        subject: &(&str, HashMap<&str, Value<'_>>),
+        action_id: &str,
+        details: HashMap<&str, &str>,
+        flags: u32,
+        cancellation_id: &str,
+    ) -> zbus::Result<AuthorizationResult>;
+}
+
+/// Answer to [`AuthorityProxy::check_authorization`].
+#[derive(Debug, Deserialize, Type)]
+pub struct AuthorizationResult {
+    /// The subject may go ahead.
+    pub is_authorized: bool,
+    /// The subject could go ahead after authenticating, when interaction wasn't allowed.
+    pub is_challenge: bool,
+    /// Extra information from polkit, such as the dismissed dialog.
+    pub details: HashMap<String, String>,
+}
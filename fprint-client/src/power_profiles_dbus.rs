@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! power-profiles-daemon, followed to slow animations on battery saver.
+
+use zbus::proxy;
+
+/// Profile power-profiles-daemon reports while battery saver is on.
+pub const POWER_SAVER_PROFILE: &str = "power-saver";
+
+/// The active power profile.
+#[proxy(
+    interface = "net.hadess.PowerProfiles",
+    default_service = "net.hadess.PowerProfiles",
+    default_path = "/net/hadess/PowerProfiles"
+)]
+pub trait PowerProfiles {
+    #[zbus(property)]
+    fn active_profile(&self) -> zbus::Result<String>;
+}
@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Claims held by this process on the fingerprint reader.
+
+use crate::fprint::timed;
+use crate::fprint_dbus::DeviceProxy;
+use nix::errno::Errno;
+use nix::fcntl::{Flock, FlockArg};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tokio::sync::watch;
+use zbus::zvariant::OwnedObjectPath;
+
+/// Reported when another instance of the app holds the reader's lock file.
+pub const LOCKED_BY_OTHER_INSTANCE: &str = "The reader is in use by another instance of the app";
+
+/// Whether the app currently holds a claim on the reader.
+static CLAIMED: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+
+/// Bumped by every claim and release, so a dropped session can tell whether
+/// its claim is still the one held.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Receives the claim state whenever it changes.
+pub fn watch_claim() -> watch::Receiver<bool> {
+    CLAIMED.subscribe()
+}
+
+/// Whether the app currently holds a claim on the reader.
+pub fn is_claimed() -> bool {
+    *CLAIMED.borrow()
+}
+
+/// Whether claims are guarded by a lock file shared between app instances.
+static LOCK_DEVICE: AtomicBool = AtomicBool::new(true);
+
+/// Turns the lock file guarding claims on or off, on by default.
+pub fn set_device_locking(enabled: bool) {
+    LOCK_DEVICE.store(enabled, Ordering::Relaxed);
+}
+
+/// Lock file for the device at `path`, kept in `runtime_dir`.
+fn lock_path(runtime_dir: &Path, path: &str) -> PathBuf {
+    let name = path.trim_start_matches('/').replace('/', "_");
+    runtime_dir
+        .join("cosmic-ext-fprint")
+        .join(format!("{name}.lock"))
+}
+
+/// Takes the advisory lock on the device so a second instance of the app,
+/// even one on another bus, can't claim it at the same time.
+///
+/// Returns `None` when locking is disabled or the lock file can't be created.
+fn lock_device(device: &DeviceProxy<'_>) -> zbus::Result<Option<Flock<File>>> {
+    if !LOCK_DEVICE.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+
+    let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") else {
+        tracing::warn!("XDG_RUNTIME_DIR is not set, not locking the reader");
+        return Ok(None);
+    };
+    let path = lock_path(Path::new(&runtime_dir), device.inner().path().as_str());
+
+    let file = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| File::options().create(true).append(true).open(&path));
+    let file = match file {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::warn!(%e, "Failed to open lock file {}", path.display());
+            return Ok(None);
+        }
+    };
+
+    match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(lock) => Ok(Some(lock)),
+        Err((_, Errno::EWOULDBLOCK)) => Err(zbus::Error::Failure(
+            LOCKED_BY_OTHER_INSTANCE.to_string(),
+        )),
+        Err((_, e)) => {
+            tracing::warn!(%e, "Failed to lock {}", path.display());
+            Ok(None)
+        }
+    }
+}
+
+/// A claim on the reader for a single user, given back with
+/// [`DeviceSession::release`].
+///
+/// A session dropped while still holding its claim, because the operation
+/// returned early, panicked or was cancelled, releases the reader in the
+/// background so it is never left claimed and unusable for logging in.
+pub struct DeviceSession<'a, 'p> {
+    device: &'a DeviceProxy<'p>,
+    /// Value of [`GENERATION`] when the claim was made.
+    generation: u64,
+    released: bool,
+    // Unlocked once the reader is released
+    lock: Option<Flock<File>>,
+}
+
+impl<'a, 'p> DeviceSession<'a, 'p> {
+    /// Claims `device` to act on the prints of `username`.
+    pub async fn claim(device: &'a DeviceProxy<'p>, username: &str) -> zbus::Result<Self> {
+        let lock = lock_device(device)?;
+        timed("Claim", device.claim(username)).await?;
+        CLAIMED.send_replace(true);
+        Ok(Self {
+            device,
+            generation: GENERATION.fetch_add(1, Ordering::SeqCst) + 1,
+            released: false,
+            lock,
+        })
+    }
+
+    /// Gives the reader back to fprintd.
+    pub async fn release(mut self) -> zbus::Result<()> {
+        self.released = true;
+        release(self.device).await
+    }
+}
+
+impl Drop for DeviceSession<'_, '_> {
+    fn drop(&mut self) {
+        // Released already, or by whoever cancelled the operation
+        if self.released || GENERATION.load(Ordering::SeqCst) != self.generation {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!("Dropped a claim outside of the runtime, leaving it to fprintd");
+            return;
+        };
+
+        tracing::warn!("Releasing the reader after the operation ended early");
+        let connection = self.device.inner().connection().clone();
+        let path = OwnedObjectPath::from(self.device.inner().path().to_owned());
+        let lock = self.lock.take();
+        runtime.spawn(async move {
+            if let Err(e) = release_dropped(&connection, path).await {
+                tracing::warn!(%e, "Failed to release the reader");
+            }
+            drop(lock);
+        });
+    }
+}
+
+/// Stops whatever a dropped session left running on the reader at `path` and
+/// releases it.
+async fn release_dropped(connection: &zbus::Connection, path: OwnedObjectPath) -> zbus::Result<()> {
+    let device = DeviceProxy::builder(connection)
+        .path(path)?
+        .cache_properties(zbus::proxy::CacheProperties::No)
+        .build()
+        .await?;
+    // fprintd won't release a reader in the middle of a scan
+    let _ = device.enroll_stop().await;
+    let _ = device.verify_stop().await;
+    release(&device).await
+}
+
+/// Releases a claim made by an operation whose session is no longer reachable,
+/// such as an enrollment cancelled from the UI.
+pub async fn release(device: &DeviceProxy<'_>) -> zbus::Result<()> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    let res = timed("Release", device.release()).await;
+    CLAIMED.send_replace(false);
+    res
+}
+
+/// Forgets the claim after fprintd went away, which took it along.
+pub fn forget_claim() {
+    CLAIMED.send_replace(false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn test_lock_path() {
+        assert_eq!(
+            lock_path(
+                Path::new("/run/user/1000"),
+                "/net/reactivated/Fprint/Device/0"
+            ),
+            PathBuf::from("/run/user/1000/cosmic-ext-fprint/net_reactivated_Fprint_Device_0.lock")
+        );
+    }
+
+    #[test]
+    fn test_forget_claim() {
+        let mut claim = watch_claim();
+        CLAIMED.send_replace(true);
+        assert!(is_claimed());
+
+        forget_claim();
+        assert!(!is_claimed());
+        assert!(claim.has_changed().unwrap());
+        assert!(!*claim.borrow_and_update());
+    }
+
+    /// Records the calls made on a reader.
+    struct FakeDevice(Arc<Mutex<Vec<&'static str>>>);
+
+    #[zbus::interface(name = "net.reactivated.Fprint.Device")]
+    impl FakeDevice {
+        fn claim(&self, _username: &str) {
+            self.0.lock().unwrap().push("Claim");
+        }
+
+        fn release(&self) {
+            self.0.lock().unwrap().push("Release");
+        }
+
+        fn enroll_stop(&self) {
+            self.0.lock().unwrap().push("EnrollStop");
+        }
+
+        fn verify_stop(&self) {
+            self.0.lock().unwrap().push("VerifyStop");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_session_releases() {
+        const PATH: &str = "/net/reactivated/Fprint/Device/test_dropped_session";
+        // Keep lock files out of the real runtime directory
+        set_device_locking(false);
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let (server_stream, client_stream) = tokio::net::UnixStream::pair().unwrap();
+        let server = zbus::connection::Builder::unix_stream(server_stream)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(PATH, FakeDevice(calls.clone()))
+            .unwrap()
+            .build();
+        let client = zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build();
+        let (_server, client) = futures_util::try_join!(server, client).unwrap();
+        let device = DeviceProxy::builder(&client)
+            .path(PATH)
+            .unwrap()
+            .cache_properties(zbus::proxy::CacheProperties::No)
+            .build()
+            .await
+            .unwrap();
+        let released = || async {
+            for _ in 0..100 {
+                if !is_claimed() {
+                    return std::mem::take(&mut *calls.lock().unwrap());
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            panic!("the reader was never released");
+        };
+
+        // Released by hand
+        let session = DeviceSession::claim(&device, "alice").await.unwrap();
+        session.release().await.unwrap();
+        assert_eq!(released().await, ["Claim", "Release"]);
+
+        // Left behind by an operation that ended early
+        let session = DeviceSession::claim(&device, "alice").await.unwrap();
+        drop(session);
+        assert_eq!(
+            released().await,
+            ["Claim", "EnrollStop", "VerifyStop", "Release"]
+        );
+
+        // Cancelled from elsewhere, which already released it
+        let session = DeviceSession::claim(&device, "alice").await.unwrap();
+        release(&device).await.unwrap();
+        drop(session);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(released().await, ["Claim", "Release"]);
+    }
+}
@@ -1,19 +1,61 @@
 app-title = Fprint Enroll
+window-title-page = {$app} — {$page}
+list-separator = {", "}
 about = About
+device-info = Device Info
+settings = Settings
+activity-log = Activity Log
+operator-log = Operator Log
 view = View
 fprint = Register Fingerprint
 welcome = Register and/or delete fingerprints
 git-description = Git commit {$hash} on {$date}
+whats-new = What's new in {$version}
+whats-new-item = • {$note}
 register = Register
 delete = Delete
 deleted = Deleted fingerprint.
+deleted-in = Deleted fingerprint in {$secs} s.
 deleting = Deleting fingerprint...
+delete-unsupported = This version of fprintd can't delete that
 clear-device = Clear Device
 confirm-clear = Are you sure?
 clearing-device = Clearing all fingerprints from device for all known users...
 device-cleared = Device cleared for all known users.
+device-cleared-in = Device cleared for all known users in {$secs} s.
+clear-cancelling = Stopping after the current user...
+clear-stopped = Clearing stopped with {$count ->
+    [one] one user
+   *[other] {$count} users
+} left.
+clear-partial = Cleared {$cleared} of {$total ->
+    [one] one user
+   *[other] {$total} users
+}; failed for {$failures}.
+clear-user-failed = {$user}: {$error}
+clear-unfinished = Clearing the device did not finish. Prints of {$users} are left.
+clear-resume = Resume
+clear-resume-confirm = Finish clearing the fingerprints of {$users}?
 clear-device-confirm = Are you sure you want to clear fingerprints for ALL known users?
+clear-preview-loading = Listing enrolled fingerprints...
+clear-preview-empty = No enrolled fingerprints were found.
+clear-preview-user = {$count ->
+    [one] 1 print
+   *[other] {$count} prints
+} for {$user}
+clear-authenticating = Waiting for administrator authentication to clear the device...
+clear-authentication-failed = Authentication failed. Clearing the device needs administrator rights.
+dual-boot-warning = On dual-boot machines, wiping the sensor may also remove Windows Hello enrollments.
+dual-boot-warning-suppress = Don't show this warning again
+remote-session-notice = You are connected remotely. Changes apply to the fingerprint reader of this computer, not the one in front of you.
+remote-session-notice-host = You are connected remotely from {$host}. Changes apply to the fingerprint reader of this computer, not the one in front of you.
+headless-session-notice = This session has no local display. Changes apply to the fingerprint reader of this computer, which may be out of your reach.
+remote-session-confirm = I understand that I'm changing the reader of a remote computer
+clear-device-confirm-on-chip = Are you sure you want to clear fingerprints for ALL known users? This removes prints stored inside the sensor, affecting other operating systems too.
 cancel = Cancel
+dismiss = Dismiss
+account-settings = Account settings
+account-settings-failed = Could not open COSMIC Settings.
 
 page-right-thumb = Right Thumb
 page-right-index-finger = Right Index Finger
@@ -26,27 +68,307 @@ page-left-middle-finger = Left Middle Finger
 page-left-ring-finger = Left Ring Finger
 page-left-little-finger = Left Little Finger
 page-delete-all-users-prints = Delete All User's Prints
+page-overview = Overview
+page-test = Self-Test
+nav-right-hand = Right Hand
+nav-left-hand = Left Hand
+nav-manage = Manage
+
+overview-loading = Loading enrolled fingers...
+overview-others-hidden = Authenticate to see the fingerprints of other users.
+
+claim-held = This app is using the reader. Other fingerprint apps have to wait.
+claim-free = The reader is available to other apps.
+idle-released = Stopped after a period of inactivity. The reader was released.
 
 status-connecting = Connecting to system bus...
 status-searching-device = Searching for fingerprint reader...
+reconnecting = Lost the fingerprint service, reconnecting...
+exit-releasing = Releasing the fingerprint reader before closing...
 status-device-found = Device found. Ready.
 status-no-device-found = No fingerprint reader found.
+device-removed = The fingerprint reader was unplugged.
 status-starting-enrollment = Starting enrollment...
 
 enroll-starting = Place your finger on the reader to start.
-enroll-stage-passed = Scan successful. Keep going.
+enroll-starting-swipe = Swipe your finger across the reader to start.
+enroll-stage-passed = Scan successful. Lift your finger and touch the reader again.
+enroll-stage-passed-swipe = Scan successful. Swipe your finger again.
 enroll-retry-scan = Could not read fingerprint. Please try again.
 enroll-swipe-too-short = Swipe was too short. Please swipe the full length of the sensor.
 enroll-finger-not-centered = Finger not centered. Please place your finger in the middle of the sensor.
 enroll-remove-and-retry = Please remove your finger and try again.
 enroll-unknown-error = Unknown error occurred.
+status-unknown = The reader reported something unexpected ({$code}).
 enroll-completed = Fingerprint enrolled.
+enroll-completed-in = Fingerprint enrolled in {$secs} s.
 enroll-failed = Enrollment failed.
 enroll-disconnected = Device disconnected.
 enroll-data-full = Fingerprint storage is full. Please delete some fingerprints.
 enroll-too-fast = Swipe was too fast. Please swipe slower.
 enroll-duplicate = This finger is already enrolled.
 enroll-cancelled = Enrollment cancelled.
+stage-count = Scan {$done} of {$total}
+stage-count-retry = {$count} · {$reason}
+stage-retry-unreadable = Not readable
+stage-retry-too-short = Swipe too short
+stage-retry-too-fast = Swipe too fast
+stage-retry-not-centered = Not centered
+stage-retry-remove-finger = Lift your finger
+
+verify = Verify
+verify-prompt = Place your {$finger} on the reader to test it.
+verify-match = Fingerprint matched.
+verify-no-match = Fingerprint did not match.
+verify-cancelled = Verification cancelled.
+identify = Identify
+identify-prompt = Place any finger of {$user} on the reader to find out which one it is.
+identify-match = This is the {$finger} of {$user}.
+identify-match-any = This finger belongs to {$user}. The reader does not tell which one it is.
+delete-confirm = Delete the fingerprint of the {$finger} for {$user}?
+delete-all-confirm = Delete every fingerprint of {$user}?
+delete-all = Delete All
+delete-verify-prompt = Scan any enrolled finger to confirm deleting.
+delete-verify-failed = Fingerprint did not match. Nothing was deleted.
+
+replace = Replace
+replace-choose = Choose a finger to replace your {$finger}.
+replace-no-candidates = Every other finger is already enrolled.
+replace-continue = Continue
+replace-enrolling = Enrolling {$new} to replace {$old}.
+replace-verify = Verify
+replace-verify-prompt = Place your {$finger} on the reader to verify it.
+replace-confirm-delete = {$new} works. Delete the fingerprint of your {$old}?
+replace-confirm-delete-on-chip = {$new} works. Delete the fingerprint of your {$old}? This removes the print stored inside the sensor, affecting other operating systems too.
+replace-delete-old = Delete Old
+replace-keep-old = Keep Both
+replace-done = Replacement finished.
+replace-cancelled = Replacement cancelled.
+cleanup-title = The Reader Is Full
+cleanup-body = Select fingerprints to delete to make room. Enrollment starts again once they are gone.
+cleanup-loading = Listing fingerprints on the reader...
+cleanup-empty = No fingerprints you can manage were found on the reader.
+cleanup-print = {$finger} of {$user}
+cleanup-delete = Delete and Retry
+cleanup-deleting = Deleting selected fingerprints...
+cleanup-confirm = Delete {$count ->
+    [one] the selected fingerprint
+   *[other] {$count} selected fingerprints
+} of {$users}?
+cleanup-cancelled = Nothing was deleted.
+nav-menu-enroll = Enroll
+nav-menu-test = Test
+nav-menu-delete = Delete
+nav-menu-rename = Rename...
+nav-menu-hide = Hide Until Enrolled
+rename-title = Rename {$finger}
+rename-body = Shown in the sidebar instead of the finger's name. Leave empty to use the default name.
+rename-save = Rename
+wizard-open = Guided Setup
+wizard-title = Set Up Fingerprint Login
+wizard-step = Step {$step} of {$steps}
+wizard-back = Back
+wizard-next = Next
+wizard-choose-user = Whose fingerprint do you want to set up?
+wizard-choose-finger = Choose a finger to enroll. Fingers that are already enrolled are highlighted.
+wizard-instructions-press = Place your {$finger} flat on the reader, then lift it and touch again each time you are asked. Vary the position slightly so the whole fingertip is covered.
+wizard-instructions-swipe = Swipe your {$finger} across the reader in one smooth motion, and repeat each time you are asked. Start with the first joint on the sensor.
+wizard-start-enroll = Start
+wizard-enrolling = Enrolling your {$finger}.
+wizard-try-again = Try Again
+wizard-verify = Your {$finger} is enrolled. Verify it once to make sure it is recognized.
+wizard-verify-prompt = Place your {$finger} on the reader to verify it.
+wizard-skip = Skip
+wizard-done = All done. You can now use your {$finger} to log in.
+wizard-another = Enroll Another Finger
+wizard-finish = Finish
+
+batch-open = Enroll Multiple...
+batch-title = Enroll Multiple Fingers
+batch-body = Select the fingers to enroll. They are enrolled one after another, with a short pause to lift your finger in between.
+batch-start = Start
+batch-progress = {$done} of {$total ->
+    [one] {$total} finger
+   *[other] {$total} fingers
+} done
+batch-waiting = Waiting
+batch-enrolling = Enrolling...
+batch-enrolled = Enrolled
+batch-skipped = Skipped
+batch-failed = Failed
+batch-skip = Skip Finger
+batch-stop = Stop
+batch-done = Enrolled {$enrolled} of {$total ->
+    [one] {$total} finger
+   *[other] {$total} fingers
+}.
+batch-close = Close
+
+duplicate-checking = Checking other users' fingerprints...
+duplicate-title = Finger Already Enrolled
+duplicate-body = The {$finger} is already enrolled on this sensor for {$users}. The reader may reject the new enrollment as a duplicate.
+duplicate-switch = Switch to {$user}
+duplicate-enroll-anyway = Enroll Anyway
+
+recovery-title = Enrollment Interrupted
+recovery-body = Enrolling the {$finger} of {$user} did not finish last time. Do you want to try again?
+recovery-retry = Try Again
+recovery-dismiss = Dismiss
+
+help = What is this?
+help-user-picker = Choose whose fingerprints to manage. Managing fingerprints of other users requires administrator rights.
+help-clear-device = Removes the fingerprints of every known user from the reader. Fingerprints are kept by fprintd in /var/lib/fprint, or inside the sensor itself on readers that match on the chip.
+help-progress = Shows how many scans the reader still needs. Each successful scan checks off a step, while a failed scan marks the current step with the reason it has to be tried again.
+
+device-info-reader = Reader
+device-info-name = Name
+device-info-unknown = Unknown
+device-info-storage = Fingerprints stored
+device-info-storage-host = On this computer
+device-info-storage-device = Inside the sensor
+device-info-fprintd-conf = fprintd Configuration
+device-info-storage-backend = Storage backend
+device-info-storage-path = Storage path
+device-info-conf-unreadable = Could not read {$path}.
+
+journal-title = Last Enrollment
+journal-finger = Finger
+journal-outcome = Outcome
+journal-duration = Duration
+journal-seconds = {$secs} s
+journal-retries = Retries
+journal-export = Export as JSON
+journal-exported = Enrollment journal saved to {$path}.
+journal-export-failed = Could not export the enrollment journal.
+fprintd-conf-unknown-storage = Storage backend "{$kind}" is not the default "file" backend. Enrollments may be kept elsewhere than expected.
+fprintd-conf-custom-path = Fingerprints are stored in {$path} instead of /var/lib/fprint.
+
+authenticate = Authenticate
+authenticating = Waiting for authentication...
+authenticated = Authenticated. You can now manage the fingerprints of other users.
+authentication-failed = Authentication failed. Managing the fingerprints of other users needs administrator rights.
+
+pam-disabled = Fingerprints are not used for logging in or for sudo yet.
+pam-login-disabled = Fingerprints are not used for logging in yet.
+pam-sudo-disabled = Fingerprints are not used for sudo yet.
+pam-enable = Enable
+pam-enabling = Enabling fingerprint authentication...
+pam-enabled = Fingerprint authentication enabled.
+pam-enable-failed = Could not enable fingerprint authentication.
+
+status-history-hint = Press ↑ and ↓ to read earlier messages.
+status-history-position = {$count ->
+    [one] 1 message ago
+   *[other] {$count} messages ago
+}
+activity-log-description = The most recent messages, newest first. Times are in UTC.
+activity-log-empty = Nothing has happened yet.
+activity-log-copy = Copy to Clipboard
+
+operator-log-description = Enrollments, deletions and clears recorded in operator mode, newest first. Times are in UTC.
+operator-log-loading = Reading the log...
+operator-log-empty = Nothing has been recorded yet.
+operator-log-read-failed = Could not read the operator log: {$error}
+operator-log-write-failed = Could not write to the operator log: {$error}
+log-no-path = Neither XDG_STATE_HOME nor HOME is set, so there is nowhere to keep logs.
+operator-log-entry = {$when} · {$result}
+operator-log-summary = {$operator} {$action} for {$user}
+operator-log-enroll = enrolled {$finger}
+operator-log-enroll-any = enrolled a finger
+operator-log-delete = deleted {$finger}
+operator-log-delete-all = deleted every fingerprint
+operator-log-clear = cleared the reader
+operator-log-succeeded = Done
+operator-log-failed = Failed: {$error}
+operator-log-stopped = The clear was stopped before this user.
+operator-log-interrupted = Interrupted.
+
+debug-log-export = Export Debug Log
+debug-log-copied = Copied the debug log to the clipboard.
+debug-log-export-failed = Could not read the debug log: {$error}
+debug-log-off = There is no debug log yet. Turn it on in the settings, or start the app with --verbose.
+
+last-change = Last change: {$action}, {$when}
+last-change-enrolled = enrolled {$finger}
+last-change-enrolled-any = enrolled a fingerprint
+last-change-deleted = deleted {$finger}
+last-change-deleted-all = deleted all fingerprints
+last-change-verified = verified {$finger}
+last-change-verified-any = verified a fingerprint
+last-change-just-now = just now
+last-change-minutes = {$count ->
+    [one] 1 minute ago
+   *[other] {$count} minutes ago
+}
+last-change-hours = {$count ->
+    [one] 1 hour ago
+   *[other] {$count} hours ago
+}
+last-change-days = {$count ->
+    [one] 1 day ago
+   *[other] {$count} days ago
+}
+
+settings-general = General
+settings-theme = Theme
+settings-theme-system = Match desktop
+settings-theme-dark = Dark
+settings-theme-light = Light
+settings-default-user = User selected at startup
+settings-default-user-current = Last used
+settings-show-all-users = Show all users
+settings-show-all-users-description = When off, only your own account can be chosen.
+settings-show-system-users = Show system users
+settings-show-system-users-description = List accounts used by the system and services, which usually have no fingerprints.
+
+user-drop-unreadable = The dropped item is not a user account.
+user-drop-unknown = No account named {$user} was found.
+user-drop-hidden = {$user} is not listed. Turn on “Show all users” in the settings to manage their prints.
+settings-lock-device = Keep other app instances away from the reader
+settings-lock-device-description = Stops a second window of this app from using the reader at the same time.
+settings-notify = Notify when an enrollment ends
+settings-notify-description = Sends a desktop notification when the window is in the background.
+settings-hidden-fingers = Hidden fingers
+settings-hidden-fingers-description = {$count ->
+    [0] Fingers you never enroll can be hidden from the sidebar by right-clicking them.
+    [one] One finger is hidden from the sidebar until it is enrolled.
+   *[other] {$count} fingers are hidden from the sidebar until they are enrolled.
+}
+settings-show-all-fingers = Show All
+settings-idle-release = Stop after inactivity
+settings-idle-release-description = Stops an enrollment or verification that waits for a finger this long and releases the reader.
+settings-idle-release-never = Never
+settings-idle-release-minutes = {$mins ->
+    [one] 1 minute
+   *[other] {$mins} minutes
+}
+settings-deleting = Deleting
+settings-confirm-before-delete = Confirm before deleting
+settings-require-verify-before-delete = Require a fingerprint scan before deleting
+settings-require-verify-before-delete-description = Stops someone else at the computer from removing your fingerprints.
+settings-require-auth-before-clear = Require administrator authentication to clear the device
+settings-require-auth-before-clear-description = Asks for an administrator password before showing whose fingerprints would be removed.
+settings-administration = Administration
+settings-operator-mode = Operator mode
+settings-operator-mode-description = Records every enrollment, deletion and clear, with the account that made it, in {$path}.
+settings-troubleshooting = Troubleshooting
+settings-debug-log = Write a debug log
+settings-debug-log-description = Records what the app does in {$path}, to attach to bug reports. Starting the app with --verbose writes it for that run.
+
+in-use-guidance = Another program keeps using the reader. Close the fingerprint settings of other desktops or apps and try again.
+in-use-guidance-services = Another program keeps using the reader. Close {$services} and try again.
+reader-busy-seconds = The reader has been in use by another program for {$secs} s.
+reader-busy-minutes = The reader has been in use by another program for {$mins} min.
+reader-busy-next-check = Checking again in {$secs} s.
+reader-busy-checking = Checking whether it is free...
+reader-busy-with-check = {$busy} {$check}
+reader-busy-retry = Retry Now
+reader-busy-pause = Stop Checking
+reader-busy-resume = Keep Checking
+reader-available = The reader is free again.
+device-wait = Waiting for the reader to become available…
+device-wait-remaining = Waiting for the reader to become available… Giving up in {$secs} s.
+device-wait-cancelled = Stopped waiting for the reader.
 
 error-permission-denied = Permission denied.
 error-already-in-use = Device is already in use by another application.
@@ -56,4 +378,65 @@ error-claim-device = Could not claim the device.
 error-device-not-found = Fingerprint device not found.
 error-timeout = Operation timed out.
 error-prints-not-deleted = Could not delete fingerprints.
+error-service-unavailable = The fingerprint service is not running.
+error-service-not-installed = The fingerprint service is not installed.
 error-connect-dbus = Failed to connect to DBus: {$err}
+error-invalid-signal = The reader sent a message that could not be read.
+
+error-permission-denied-title = Permission Denied
+error-already-in-use-title = Device Busy
+error-internal-title = Reader Error
+error-no-enrolled-prints-title = Nothing Enrolled
+error-claim-device-title = Device Unavailable
+error-device-not-found-title = No Reader Found
+error-timeout-title = Timed Out
+error-prints-not-deleted-title = Not Deleted
+error-service-unavailable-title = Service Not Running
+error-service-not-installed-title = Service Not Installed
+error-connect-dbus-title = No System Connection
+error-unknown-title = Something Went Wrong
+
+error-permission-denied-detail = You are not allowed to manage these fingerprints. Authenticate as an administrator and try again.
+error-already-in-use-detail = Another application is using the fingerprint reader. Wait for it to finish, or close it, and try again.
+error-internal-detail = The fingerprint service ran into a problem with the reader. Unplugging and reconnecting the reader may help.
+error-no-enrolled-prints-detail = There are no fingerprints enrolled for this finger yet, so there is nothing to use or remove.
+error-claim-device-detail = The fingerprint service could not reserve the reader. Make sure no other program holds it and try again.
+error-device-not-found-detail = No supported fingerprint reader was found. Check that it is connected and supported by fprintd.
+error-timeout-detail = The reader did not respond in time. Try again, and make sure the finger touches the sensor.
+error-prints-not-deleted-detail = The fingerprint service could not remove the fingerprints. They may still be stored on the reader.
+error-service-unavailable-detail = The fingerprint service fprintd is not running and could not be started automatically. Start it and try again.
+error-service-not-installed-detail = The fingerprint service fprintd was not found on this system. Install the fprintd package with your package manager and try again.
+error-connect-dbus-detail = The system message bus could not be reached, so the fingerprint service is unavailable: {$err}
+
+error-action-retry = Try Again
+error-action-troubleshoot = Troubleshooting
+error-action-start-daemon = Start Service
+error-action-restart-daemon = Restart Service
+error-action-starting-daemon = Starting the fingerprint service…
+error-action-start-daemon-failed = Could not start the fingerprint service.
+error-action-daemon-started = The fingerprint service is running again.
+error-details = Details
+error-details-hide = Hide Details
+
+notification-enrolled = Fingerprint enrolled for {$finger}
+notification-enroll-failed = Could not enroll {$finger}
+
+self-test-body = Scan an enrolled finger again and again to see how reliably the reader recognizes it before relying on it to log in or for sudo.
+self-test-no-prints = Enroll a finger first.
+self-test-prompt = Place an enrolled finger on the reader
+self-test-tally = {$matches} matched · {$no_matches} not matched · {$retries} retried
+self-test-rate = {$rate}% recognized
+self-test-last-match = Last scan: recognized
+self-test-last-no-match = Last scan: not recognized
+self-test-last-retry = Last scan: try again
+self-test-start = Start Test
+self-test-stop = Stop Test
+
+applet-loading = Listing your fingerprints...
+applet-none-enrolled = No fingerprints enrolled.
+applet-enrolled = {$count ->
+    [one] 1 finger enrolled
+   *[other] {$count} fingers enrolled
+}
+applet-enroll-new = Enroll New Finger
+applet-open = Open Fprint
@@ -26,6 +26,11 @@ page-left-middle-finger = Vasen Keskisormi
 page-left-ring-finger = Vasen Nimetön
 page-left-little-finger = Vasen Pikkusormi
 page-delete-all-users-prints = Poista kaikki käyttäjien sormenjäljet
+page-overview = Yleiskatsaus
+page-test = Lukijan testaus
+nav-right-hand = Oikea Käsi
+nav-left-hand = Vasen Käsi
+nav-manage = Hallinta
 
 status-connecting = Yhdistetään järjestelmäväylään...
 status-searching-device = Etsitään sormenjälkilukijaa...
@@ -26,6 +26,11 @@ page-left-middle-finger = Vänster långfinger
 page-left-ring-finger = Vänster ringfinger
 page-left-little-finger = Vänster lillfinger
 page-delete-all-users-prints = Radera alla användarens fingeravtryck
+page-overview = Översikt
+page-test = Testa läsaren
+nav-right-hand = Höger hand
+nav-left-hand = Vänster hand
+nav-manage = Hantera
 
 status-connecting = Ansluter till system bus...
 status-searching-device = Söker efter fingeravtrycksläsare...
@@ -1,25 +0,0 @@
-// SPDX-License-Identifier: MPL-2.0
-
-use zbus::proxy;
-
-#[proxy(
-    interface = "org.freedesktop.Accounts",
-    default_service = "org.freedesktop.Accounts",
-    default_path = "/org/freedesktop/Accounts"
-)]
-pub trait Accounts {
-    fn list_cached_users(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
-    fn find_user_by_name(&self, name: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
-}
-
-#[proxy(
-    interface = "org.freedesktop.Accounts.User",
-    default_service = "org.freedesktop.Accounts"
-)]
-pub trait User {
-    #[zbus(property)]
-    fn user_name(&self) -> zbus::Result<String>;
-
-    #[zbus(property)]
-    fn real_name(&self) -> zbus::Result<String>;
-}
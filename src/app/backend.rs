@@ -0,0 +1,762 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The fprintd operations the app relies on, behind a trait so the app logic
+//! can run against something other than the system's fprintd.
+
+use crate::app::error::AppError;
+use crate::app::message::{DeviceOption, Message, UserOption};
+use crate::app::remote::SessionLocation;
+use crate::app::users::{fetch_users, watch_users};
+use fprint_client::fprint::{
+    Capabilities, ClearReport, Event, ServiceState, authorize_other_users,
+    clear_all_fingers_dbus, delete_fingerprint_dbus, delete_fingers, enroll_fingerprint_process,
+    find_devices, find_users_with_finger, list_enrolled_fingers_dbus, list_fingers_by_user,
+    may_manage_other_users, probe_capabilities, probe_claim, service_state, start_fprintd,
+    verify_fingerprint_process, watch_devices, watch_fprintd,
+};
+use fprint_client::fprint_dbus::DeviceProxy;
+use fprint_client::logind_dbus::SessionProxy;
+use fprint_client::power_profiles_dbus::{POWER_SAVER_PROFILE, PowerProfilesProxy};
+use fprint_client::session;
+use futures_util::future::{self, BoxFuture};
+use futures_util::sink::Sink;
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use zbus::zvariant::OwnedObjectPath;
+
+/// Talks to fprintd, or stands in for it.
+///
+/// Futures and streams are `'static` so they can be handed to tasks and
+/// subscriptions as they are. Readers are identified by their object path.
+pub trait FprintBackend: Send + Sync {
+    /// Readers known to fprintd, the default one first.
+    fn find_devices(&self) -> BoxFuture<'static, zbus::Result<Vec<DeviceOption>>>;
+
+    /// Users whose prints can be managed.
+    fn list_users(&self) -> BoxFuture<'static, Vec<UserOption>>;
+
+    /// Asks for the right to manage the prints of other users.
+    fn authorize_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>>;
+
+    /// Whether the prints of other users could be managed at all, after
+    /// authenticating if need be. Never prompts.
+    fn may_manage_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>>;
+
+    fn list_enrolled_fingers(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<Vec<String>>>;
+
+    /// Enrolled fingers of each of `usernames` that has any, in the given order.
+    fn list_fingers_by_user(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+    ) -> BoxFuture<'static, Vec<(String, Vec<String>)>>;
+
+    /// Which of `usernames` already have `finger` enrolled.
+    fn find_users_with_finger(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+        finger: String,
+    ) -> BoxFuture<'static, Vec<String>>;
+
+    fn delete_finger(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>>;
+
+    fn delete_fingers(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>>;
+
+    /// Clears the prints of `usernames` in turn, sending `Message::ClearUserDone`
+    /// for each user and finishing with `Message::ClearComplete`.
+    fn clear_users(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+        cancel: Arc<AtomicBool>,
+    ) -> BoxStream<'static, Message>;
+
+    /// Claims and releases the reader to find out whether it is free.
+    fn probe_claim(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>>;
+
+    /// Enrolls `finger`, reporting progress as messages. Failures are sent as
+    /// `Message::OperationError`.
+    fn enroll(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message>;
+
+    /// Verifies `finger`, or any enrolled finger for "any", like [`FprintBackend::enroll`].
+    fn verify(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message>;
+
+    /// Stops a running enrollment and releases the reader.
+    fn enroll_stop(&self, device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>>;
+
+    /// Stops a running verification and releases the reader.
+    fn verify_stop(&self, device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>>;
+
+    /// Sends `Message::ServiceChanged` whenever fprintd starts or goes away, and
+    /// `Message::ConnectionLost` once the connection to it is gone for good.
+    fn watch_service(&self) -> BoxStream<'static, Message>;
+
+    /// Sends `Message::DevicesChanged` whenever a reader is plugged in or out.
+    fn watch_devices(&self) -> BoxStream<'static, Message>;
+
+    /// Sends `Message::UsersChanged` whenever an account is created or deleted.
+    fn watch_users(&self) -> BoxStream<'static, Message>;
+
+    /// Sends `Message::PowerSaverChanged` with whether battery saver is on, and
+    /// again whenever it is switched.
+    fn watch_power_saver(&self) -> BoxStream<'static, Message>;
+
+    /// Where the session running the app is, from logind or else the environment.
+    fn session_location(&self) -> BoxFuture<'static, SessionLocation>;
+
+    /// Whether fprintd is running, can be started, or is missing.
+    fn service_state(&self) -> BoxFuture<'static, zbus::Result<ServiceState>>;
+
+    /// Starts fprintd when it isn't running.
+    fn start_service(&self) -> BoxFuture<'static, zbus::Result<()>>;
+
+    /// Which optional methods the fprintd serving `device` offers.
+    fn probe_capabilities(
+        &self,
+        device: OwnedObjectPath,
+    ) -> BoxFuture<'static, zbus::Result<Capabilities>>;
+}
+
+/// The system's fprintd, reached over DBus.
+#[derive(Clone, Debug)]
+pub struct ZbusBackend {
+    connection: zbus::Connection,
+}
+
+impl ZbusBackend {
+    pub fn new(connection: zbus::Connection) -> Self {
+        Self { connection }
+    }
+
+    async fn device(
+        connection: &zbus::Connection,
+        path: OwnedObjectPath,
+    ) -> zbus::Result<DeviceProxy<'static>> {
+        DeviceProxy::builder(connection).path(path)?.build().await
+    }
+}
+
+impl FprintBackend for ZbusBackend {
+    fn find_devices(&self) -> BoxFuture<'static, zbus::Result<Vec<DeviceOption>>> {
+        let connection = self.connection.clone();
+        async move {
+            let devices = find_devices(&connection).await?;
+            Ok(devices.into_iter().map(DeviceOption::from).collect())
+        }
+        .boxed()
+    }
+
+    fn list_users(&self) -> BoxFuture<'static, Vec<UserOption>> {
+        let connection = self.connection.clone();
+        async move { fetch_users(&connection).await }.boxed()
+    }
+
+    fn authorize_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+        let connection = self.connection.clone();
+        async move { authorize_other_users(&connection).await }.boxed()
+    }
+
+    fn may_manage_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+        let connection = self.connection.clone();
+        async move { may_manage_other_users(&connection).await }.boxed()
+    }
+
+    fn list_enrolled_fingers(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<Vec<String>>> {
+        let connection = self.connection.clone();
+        async move {
+            let device = Self::device(&connection, device).await?;
+            list_enrolled_fingers_dbus(&device, username).await
+        }
+        .boxed()
+    }
+
+    fn list_fingers_by_user(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+    ) -> BoxFuture<'static, Vec<(String, Vec<String>)>> {
+        let connection = self.connection.clone();
+        async move {
+            match Self::device(&connection, device).await {
+                Ok(device) => list_fingers_by_user(&device, usernames).await,
+                Err(e) => {
+                    tracing::warn!(%e, "Failed to reach the device");
+                    Vec::new()
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn find_users_with_finger(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+        finger: String,
+    ) -> BoxFuture<'static, Vec<String>> {
+        let connection = self.connection.clone();
+        async move {
+            match Self::device(&connection, device).await {
+                Ok(device) => find_users_with_finger(&device, usernames, &finger).await,
+                Err(e) => {
+                    tracing::warn!(%e, "Failed to reach the device");
+                    Vec::new()
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn delete_finger(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move { delete_fingerprint_dbus(&connection, device, finger, username).await }.boxed()
+    }
+
+    fn delete_fingers(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move { delete_fingers(&connection, device, username).await }.boxed()
+    }
+
+    fn clear_users(
+        &self,
+        device: OwnedObjectPath,
+        usernames: Vec<String>,
+        cancel: Arc<AtomicBool>,
+    ) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let res = clear_all_fingers_dbus(
+                &connection,
+                device,
+                usernames,
+                &cancel,
+                &mut messages(&mut output),
+            )
+            .await;
+            let res = res
+                .map(|report| report.map_err(AppError::from))
+                .map_err(AppError::from);
+            let _ = output.send(Message::ClearComplete(res)).await;
+        })
+        .boxed()
+    }
+
+    fn probe_claim(
+        &self,
+        device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move {
+            let device = Self::device(&connection, device).await?;
+            probe_claim(&device, username).await
+        }
+        .boxed()
+    }
+
+    fn enroll(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(100, move |mut output| async move {
+            let res = enroll_fingerprint_process(
+                connection,
+                &device,
+                &finger,
+                &username,
+                &mut messages(&mut output),
+            )
+            .await;
+            if let Err(e) = res {
+                let _ = output
+                    .send(Message::OperationError(AppError::from(e)))
+                    .await;
+            }
+        })
+        .boxed()
+    }
+
+    fn verify(
+        &self,
+        device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(100, move |mut output| async move {
+            let res = verify_fingerprint_process(
+                connection,
+                &device,
+                &finger,
+                &username,
+                &mut messages(&mut output),
+            )
+            .await;
+            if let Err(e) = res {
+                let _ = output
+                    .send(Message::OperationError(AppError::from(e)))
+                    .await;
+            }
+        })
+        .boxed()
+    }
+
+    fn enroll_stop(&self, device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move {
+            let device = Self::device(&connection, device).await?;
+            let _ = device.enroll_stop().await;
+            session::release(&device).await
+        }
+        .boxed()
+    }
+
+    fn verify_stop(&self, device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move {
+            let device = Self::device(&connection, device).await?;
+            let _ = device.verify_stop().await;
+            session::release(&device).await
+        }
+        .boxed()
+    }
+
+    fn watch_service(&self) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            match watch_fprintd(&connection).await {
+                Ok(changes) => {
+                    let mut changes = std::pin::pin!(changes);
+                    while let Some(running) = changes.next().await {
+                        let _ = output.send(Message::ServiceChanged(running)).await;
+                    }
+                }
+                Err(e) => tracing::warn!(%e, "Failed to watch fprintd"),
+            }
+            let _ = output.send(Message::ConnectionLost).await;
+        })
+        .boxed()
+    }
+
+    fn watch_devices(&self) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            match watch_devices(&connection).await {
+                Ok(changes) => {
+                    let mut changes = std::pin::pin!(changes);
+                    while changes.next().await.is_some() {
+                        let _ = output.send(Message::DevicesChanged).await;
+                    }
+                }
+                // Readers are still picked up when fprintd restarts
+                Err(e) => tracing::debug!(%e, "Failed to watch readers"),
+            }
+        })
+        .boxed()
+    }
+
+    fn watch_users(&self) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            match watch_users(&connection).await {
+                Ok(changes) => {
+                    let mut changes = std::pin::pin!(changes);
+                    while changes.next().await.is_some() {
+                        let _ = output.send(Message::UsersChanged).await;
+                    }
+                }
+                // Without AccountsService the user list is never more than the current user
+                Err(e) => tracing::debug!(%e, "Failed to watch AccountsService"),
+            }
+        })
+        .boxed()
+    }
+
+    fn watch_power_saver(&self) -> BoxStream<'static, Message> {
+        let connection = self.connection.clone();
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let proxy = match PowerProfilesProxy::new(&connection).await {
+                Ok(proxy) => proxy,
+                Err(e) => {
+                    tracing::debug!(%e, "Failed to watch power profiles");
+                    return;
+                }
+            };
+            // Without power-profiles-daemon there is no battery saver to follow
+            let Ok(profile) = proxy.active_profile().await else {
+                return;
+            };
+            let _ = output
+                .send(Message::PowerSaverChanged(profile == POWER_SAVER_PROFILE))
+                .await;
+
+            let mut changes = proxy.receive_active_profile_changed().await;
+            while let Some(change) = changes.next().await {
+                if let Ok(profile) = change.get().await {
+                    let _ = output
+                        .send(Message::PowerSaverChanged(profile == POWER_SAVER_PROFILE))
+                        .await;
+                }
+            }
+        })
+        .boxed()
+    }
+
+    fn session_location(&self) -> BoxFuture<'static, SessionLocation> {
+        let connection = self.connection.clone();
+        async move {
+            let session = async {
+                let proxy = SessionProxy::new(&connection).await?;
+                let (seat, _) = proxy.seat().await?;
+                Ok::<_, zbus::Error>(SessionLocation::from_logind(
+                    proxy.remote().await?,
+                    &proxy.remote_host().await?,
+                    &proxy.session_type().await?,
+                    &seat,
+                ))
+            };
+            session.await.unwrap_or_else(|e| {
+                tracing::debug!(%e, "Failed to read the logind session");
+                SessionLocation::from_env()
+            })
+        }
+        .boxed()
+    }
+
+    fn service_state(&self) -> BoxFuture<'static, zbus::Result<ServiceState>> {
+        let connection = self.connection.clone();
+        async move { service_state(&connection).await }.boxed()
+    }
+
+    fn start_service(&self) -> BoxFuture<'static, zbus::Result<()>> {
+        let connection = self.connection.clone();
+        async move { start_fprintd(&connection).await }.boxed()
+    }
+
+    fn probe_capabilities(
+        &self,
+        device: OwnedObjectPath,
+    ) -> BoxFuture<'static, zbus::Result<Capabilities>> {
+        let connection = self.connection.clone();
+        async move { probe_capabilities(&connection, device).await }.boxed()
+    }
+}
+
+/// Turns the library's events into app messages on their way to `output`.
+fn messages<S>(output: &mut S) -> impl Sink<Event, Error = S::Error> + Unpin + Send
+where
+    S: Sink<Message> + Unpin + Send,
+    S::Error: Send,
+{
+    output.with(|event| future::ready(Ok::<_, S::Error>(Message::from(event))))
+}
+
+/// An in-memory stand-in for fprintd, for testing the app without a reader.
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use fprint_client::fprint::{DeviceCaps, ScanType};
+    use futures_util::{future, stream};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::sync::atomic::Ordering;
+
+    pub const DEVICE_PATH: &str = "/net/reactivated/Fprint/Device/0";
+    const ENROLL_STAGES: u32 = 3;
+
+    /// A single press reader whose enrollments always succeed.
+    #[derive(Clone, Debug, Default)]
+    pub struct MockBackend {
+        users: Vec<String>,
+        prints: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    }
+
+    impl MockBackend {
+        pub fn new(users: &[&str]) -> Self {
+            Self {
+                users: users.iter().map(|user| user.to_string()).collect(),
+                prints: Arc::default(),
+            }
+        }
+
+        pub fn device() -> DeviceOption {
+            DeviceOption {
+                path: Arc::new(OwnedObjectPath::try_from(DEVICE_PATH).unwrap()),
+                name: Arc::new("Mock Reader".to_string()),
+                scan_type: ScanType::Press,
+                stages: Some(ENROLL_STAGES),
+                caps: DeviceCaps::default(),
+            }
+        }
+
+        pub fn prints(&self, username: &str) -> Vec<String> {
+            self.prints
+                .lock()
+                .unwrap()
+                .get(username)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        pub fn add_print(&self, username: &str, finger: &str) {
+            self.prints
+                .lock()
+                .unwrap()
+                .entry(username.to_string())
+                .or_default()
+                .push(finger.to_string());
+        }
+    }
+
+    impl FprintBackend for MockBackend {
+        fn find_devices(&self) -> BoxFuture<'static, zbus::Result<Vec<DeviceOption>>> {
+            future::ready(Ok(vec![Self::device()])).boxed()
+        }
+
+        fn list_users(&self) -> BoxFuture<'static, Vec<UserOption>> {
+            let users = self
+                .users
+                .iter()
+                .map(|name| UserOption {
+                    username: Arc::new(name.clone()),
+                    realname: Arc::new(String::new()),
+                    system: false,
+                    avatar: None,
+                })
+                .collect();
+            future::ready(users).boxed()
+        }
+
+        fn authorize_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+            future::ready(Ok(true)).boxed()
+        }
+
+        fn may_manage_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+            future::ready(Ok(true)).boxed()
+        }
+
+        fn list_enrolled_fingers(
+            &self,
+            _device: OwnedObjectPath,
+            username: String,
+        ) -> BoxFuture<'static, zbus::Result<Vec<String>>> {
+            future::ready(Ok(self.prints(&username))).boxed()
+        }
+
+        fn list_fingers_by_user(
+            &self,
+            _device: OwnedObjectPath,
+            usernames: Vec<String>,
+        ) -> BoxFuture<'static, Vec<(String, Vec<String>)>> {
+            let fingers = usernames
+                .into_iter()
+                .map(|username| {
+                    let fingers = self.prints(&username);
+                    (username, fingers)
+                })
+                .filter(|(_, fingers)| !fingers.is_empty())
+                .collect();
+            future::ready(fingers).boxed()
+        }
+
+        fn find_users_with_finger(
+            &self,
+            _device: OwnedObjectPath,
+            usernames: Vec<String>,
+            finger: String,
+        ) -> BoxFuture<'static, Vec<String>> {
+            let users = usernames
+                .into_iter()
+                .filter(|username| self.prints(username).contains(&finger))
+                .collect();
+            future::ready(users).boxed()
+        }
+
+        fn delete_finger(
+            &self,
+            _device: OwnedObjectPath,
+            finger: String,
+            username: String,
+        ) -> BoxFuture<'static, zbus::Result<()>> {
+            if let Some(fingers) = self.prints.lock().unwrap().get_mut(&username) {
+                fingers.retain(|f| *f != finger);
+            }
+            future::ready(Ok(())).boxed()
+        }
+
+        fn delete_fingers(
+            &self,
+            _device: OwnedObjectPath,
+            username: String,
+        ) -> BoxFuture<'static, zbus::Result<()>> {
+            self.prints.lock().unwrap().remove(&username);
+            future::ready(Ok(())).boxed()
+        }
+
+        fn clear_users(
+            &self,
+            _device: OwnedObjectPath,
+            usernames: Vec<String>,
+            cancel: Arc<AtomicBool>,
+        ) -> BoxStream<'static, Message> {
+            let mut messages = Vec::new();
+            let mut report = ClearReport::default();
+            for username in usernames {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                self.prints.lock().unwrap().remove(&username);
+                messages.push(Message::ClearUserDone(username.clone()));
+                report.cleared.push(username);
+            }
+            messages.push(Message::ClearComplete(Ok(report)));
+            stream::iter(messages).boxed()
+        }
+
+        fn probe_claim(
+            &self,
+            _device: OwnedObjectPath,
+            _username: String,
+        ) -> BoxFuture<'static, zbus::Result<()>> {
+            future::ready(Ok(())).boxed()
+        }
+
+        fn enroll(
+            &self,
+            _device: OwnedObjectPath,
+            finger: String,
+            username: String,
+        ) -> BoxStream<'static, Message> {
+            let mut messages = vec![Message::EnrollStart(Some(ENROLL_STAGES))];
+            for _ in 1..ENROLL_STAGES {
+                messages.push(Message::EnrollStatus(
+                    "enroll-stage-passed".to_string(),
+                    false,
+                ));
+            }
+            messages.push(Message::EnrollStatus("enroll-completed".to_string(), true));
+
+            self.add_print(&username, &finger);
+            stream::iter(messages).boxed()
+        }
+
+        fn verify(
+            &self,
+            _device: OwnedObjectPath,
+            finger: String,
+            username: String,
+        ) -> BoxStream<'static, Message> {
+            let fingers = self.prints(&username);
+            let matched = if finger == "any" {
+                !fingers.is_empty()
+            } else {
+                fingers.contains(&finger)
+            };
+            let result = if matched {
+                "verify-match"
+            } else {
+                "verify-no-match"
+            };
+            // Like fprintd on readers that can't identify, compare against the first print
+            let selected = if finger == "any" {
+                fingers.first().cloned().unwrap_or(finger)
+            } else {
+                finger
+            };
+            stream::iter([
+                Message::VerifyFingerSelected(selected),
+                Message::VerifyStatus(result.to_string(), true),
+            ])
+            .boxed()
+        }
+
+        fn enroll_stop(&self, _device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+            future::ready(Ok(())).boxed()
+        }
+
+        fn verify_stop(&self, _device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+            future::ready(Ok(())).boxed()
+        }
+
+        fn watch_service(&self) -> BoxStream<'static, Message> {
+            stream::pending().boxed()
+        }
+
+        fn watch_devices(&self) -> BoxStream<'static, Message> {
+            stream::pending().boxed()
+        }
+
+        fn watch_users(&self) -> BoxStream<'static, Message> {
+            stream::pending().boxed()
+        }
+
+        fn watch_power_saver(&self) -> BoxStream<'static, Message> {
+            stream::pending().boxed()
+        }
+
+        fn session_location(&self) -> BoxFuture<'static, SessionLocation> {
+            future::ready(SessionLocation::Local).boxed()
+        }
+
+        fn service_state(&self) -> BoxFuture<'static, zbus::Result<ServiceState>> {
+            future::ready(Ok(ServiceState::Running)).boxed()
+        }
+
+        fn start_service(&self) -> BoxFuture<'static, zbus::Result<()>> {
+            future::ready(Ok(())).boxed()
+        }
+
+        fn probe_capabilities(
+            &self,
+            _device: OwnedObjectPath,
+        ) -> BoxFuture<'static, zbus::Result<Capabilities>> {
+            future::ready(Ok(Capabilities::default())).boxed()
+        }
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Enrolling several fingers of a user one after another.
+
+use crate::app::page::Page;
+
+/// How the enrollment of a finger in the batch ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchOutcome {
+    Enrolled,
+    /// Skipped by the user, or never reached because the batch was stopped.
+    Skipped,
+    Failed,
+}
+
+/// A finger offered for the batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchFinger {
+    pub page: Page,
+    pub selected: bool,
+    /// `None` until the finger had its turn.
+    pub outcome: Option<BatchOutcome>,
+}
+
+/// State of the "Enroll multiple" flow: tick fingers, then enroll them in turn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchEnroll {
+    pub fingers: Vec<BatchFinger>,
+    /// Index in `fingers` of the finger being enrolled.
+    pub current: Option<usize>,
+    /// Whether enrolling has started, which fixes the choice of fingers.
+    pub started: bool,
+    /// Whether the batch stops once the current finger is done.
+    pub stopping: bool,
+}
+
+impl BatchEnroll {
+    /// Offers every finger that has no print yet.
+    pub fn new(enrolled_fingers: &[String]) -> Self {
+        let fingers = Page::all()
+            .iter()
+            .copied()
+            .filter(|page| {
+                page.as_finger_id()
+                    .is_some_and(|id| !enrolled_fingers.iter().any(|f| f == id))
+            })
+            .map(|page| BatchFinger {
+                page,
+                selected: false,
+                outcome: None,
+            })
+            .collect();
+
+        Self {
+            fingers,
+            current: None,
+            started: false,
+            stopping: false,
+        }
+    }
+
+    pub fn toggle(&mut self, index: usize, selected: bool) {
+        if self.started {
+            return;
+        }
+        if let Some(finger) = self.fingers.get_mut(index) {
+            finger.selected = selected;
+        }
+    }
+
+    /// Fingers picked for the batch.
+    pub fn selected(&self) -> impl Iterator<Item = &BatchFinger> {
+        self.fingers.iter().filter(|finger| finger.selected)
+    }
+
+    /// Moves on to the next picked finger that didn't have its turn, `None`
+    /// once every one did.
+    pub fn next_finger(&mut self) -> Option<Page> {
+        self.started = true;
+        self.current = self
+            .fingers
+            .iter()
+            .position(|finger| finger.selected && finger.outcome.is_none());
+        self.current.map(|index| self.fingers[index].page)
+    }
+
+    /// Records how the enrollment of the current finger ended.
+    pub fn finish_current(&mut self, outcome: BatchOutcome) {
+        if let Some(finger) = self.current.take().and_then(|i| self.fingers.get_mut(i)) {
+            finger.outcome = Some(outcome);
+        }
+        if self.stopping {
+            self.stop();
+        }
+    }
+
+    /// Ends the batch, skipping the fingers that didn't have their turn.
+    pub fn stop(&mut self) {
+        self.started = true;
+        self.current = None;
+        for finger in &mut self.fingers {
+            if finger.selected && finger.outcome.is_none() {
+                finger.outcome = Some(BatchOutcome::Skipped);
+            }
+        }
+    }
+
+    /// Whether the batch is between two fingers.
+    pub fn is_waiting(&self) -> bool {
+        self.started && self.current.is_none() && !self.is_done()
+    }
+
+    /// Whether fingers are being enrolled, as opposed to chosen or done.
+    pub fn is_running(&self) -> bool {
+        self.started && !self.is_done()
+    }
+
+    /// Whether every picked finger had its turn.
+    pub fn is_done(&self) -> bool {
+        self.started && self.selected().all(|finger| finger.outcome.is_some())
+    }
+
+    /// Number of picked fingers that had their turn, and of picked fingers.
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self
+            .selected()
+            .filter(|finger| finger.outcome.is_some())
+            .count();
+        (done, self.selected().count())
+    }
+
+    /// Number of picked fingers whose enrollment ended with `outcome`.
+    pub fn count(&self, outcome: BatchOutcome) -> usize {
+        self.selected()
+            .filter(|finger| finger.outcome == Some(outcome))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pick(batch: &mut BatchEnroll, pages: &[Page]) {
+        for index in 0..batch.fingers.len() {
+            let selected = pages.contains(&batch.fingers[index].page);
+            batch.toggle(index, selected);
+        }
+    }
+
+    #[test]
+    fn test_offers_fingers_without_prints() {
+        let batch = BatchEnroll::new(&["left-index-finger".to_string()]);
+        assert_eq!(batch.fingers.len(), 9);
+        assert!(batch.fingers.iter().all(|f| f.page != Page::LeftIndex));
+        assert!(
+            batch
+                .fingers
+                .iter()
+                .all(|f| f.page.as_finger_id().is_some())
+        );
+        assert!(!batch.is_running());
+    }
+
+    #[test]
+    fn test_enrolls_picked_fingers_in_turn() {
+        let mut batch = BatchEnroll::new(&[]);
+        pick(
+            &mut batch,
+            &[Page::LeftThumb, Page::RightIndex, Page::RightMiddle],
+        );
+
+        // In the order of the sidebar
+        assert_eq!(batch.next_finger(), Some(Page::RightIndex));
+        assert!(batch.is_running());
+        assert!(!batch.is_waiting());
+        batch.finish_current(BatchOutcome::Enrolled);
+        assert!(batch.is_waiting());
+        assert_eq!(batch.progress(), (1, 3));
+
+        // The choice is fixed once started
+        batch.toggle(0, true);
+        assert_eq!(batch.selected().count(), 3);
+
+        assert_eq!(batch.next_finger(), Some(Page::RightMiddle));
+        batch.finish_current(BatchOutcome::Skipped);
+        assert_eq!(batch.next_finger(), Some(Page::LeftThumb));
+        batch.finish_current(BatchOutcome::Failed);
+
+        assert!(batch.is_done());
+        assert!(!batch.is_waiting());
+        assert_eq!(batch.next_finger(), None);
+        assert_eq!(batch.progress(), (3, 3));
+        assert_eq!(batch.count(BatchOutcome::Enrolled), 1);
+        assert_eq!(batch.count(BatchOutcome::Skipped), 1);
+        assert_eq!(batch.count(BatchOutcome::Failed), 1);
+    }
+
+    #[test]
+    fn test_stopping_skips_the_rest() {
+        let mut batch = BatchEnroll::new(&[]);
+        pick(
+            &mut batch,
+            &[Page::LeftThumb, Page::RightIndex, Page::RightMiddle],
+        );
+
+        batch.next_finger();
+        batch.stopping = true;
+        batch.finish_current(BatchOutcome::Skipped);
+
+        assert!(batch.is_done());
+        assert_eq!(batch.count(BatchOutcome::Skipped), 3);
+    }
+}
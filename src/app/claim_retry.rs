@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Retrying to claim a reader that another program holds, backing off between attempts.
+
+use std::time::{Duration, Instant};
+
+/// Delay before the first retry, doubled after every failed attempt.
+const INITIAL_DELAY: Duration = Duration::from_secs(2);
+const MAX_DELAY: Duration = Duration::from_secs(60);
+/// How long the reader must be unavailable before it is worth mentioning.
+const SHOW_AFTER: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub struct ClaimRetry {
+    /// When the reader was first found in use.
+    since: Instant,
+    attempts: u32,
+    next_attempt: Instant,
+    /// Whether an attempt is running.
+    attempting: bool,
+    paused: bool,
+}
+
+impl ClaimRetry {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            since: now,
+            attempts: 0,
+            next_attempt: now + INITIAL_DELAY,
+            attempting: false,
+            paused: false,
+        }
+    }
+
+    fn delay(attempts: u32) -> Duration {
+        INITIAL_DELAY
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(MAX_DELAY)
+    }
+
+    /// Starts an attempt if one is due, returning whether it was started.
+    pub fn start_attempt(&mut self, now: Instant) -> bool {
+        if self.attempting || self.paused || now < self.next_attempt {
+            return false;
+        }
+        self.attempting = true;
+        true
+    }
+
+    /// Records a failed attempt and schedules the next one.
+    pub fn failed(&mut self, now: Instant) {
+        self.attempting = false;
+        self.attempts = self.attempts.saturating_add(1);
+        self.next_attempt = now + Self::delay(self.attempts);
+    }
+
+    /// Makes the next attempt due right away.
+    pub fn retry_now(&mut self, now: Instant) {
+        self.paused = false;
+        self.next_attempt = now;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_attempting(&self) -> bool {
+        self.attempting
+    }
+
+    /// How long the reader has been unavailable.
+    pub fn unavailable_for(&self, now: Instant) -> Duration {
+        now.duration_since(self.since)
+    }
+
+    /// Time left until the next attempt, `None` while paused or attempting.
+    pub fn next_attempt_in(&self, now: Instant) -> Option<Duration> {
+        (!self.paused && !self.attempting)
+            .then(|| self.next_attempt.saturating_duration_since(now))
+    }
+
+    /// Whether the reader has been unavailable long enough to tell the user.
+    pub fn is_shown(&self, now: Instant) -> bool {
+        self.unavailable_for(now) >= SHOW_AFTER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let start = Instant::now();
+        let mut retry = ClaimRetry::new(start);
+
+        assert!(!retry.start_attempt(start));
+        assert!(retry.start_attempt(start + INITIAL_DELAY));
+        // Only one attempt runs at a time
+        assert!(!retry.start_attempt(start + INITIAL_DELAY));
+
+        let now = start + INITIAL_DELAY;
+        retry.failed(now);
+        assert_eq!(retry.next_attempt_in(now), Some(INITIAL_DELAY * 2));
+
+        for _ in 0..10 {
+            retry.failed(now);
+        }
+        assert_eq!(retry.next_attempt_in(now), Some(MAX_DELAY));
+    }
+
+    #[test]
+    fn test_pause_and_retry_now() {
+        let start = Instant::now();
+        let mut retry = ClaimRetry::new(start);
+
+        retry.set_paused(true);
+        assert!(!retry.start_attempt(start + MAX_DELAY));
+        assert_eq!(retry.next_attempt_in(start), None);
+
+        retry.retry_now(start);
+        assert!(!retry.is_paused());
+        assert!(retry.start_attempt(start));
+    }
+
+    #[test]
+    fn test_is_shown() {
+        let start = Instant::now();
+        let retry = ClaimRetry::new(start);
+
+        assert!(!retry.is_shown(start));
+        assert!(retry.is_shown(start + SHOW_AFTER));
+        assert_eq!(retry.unavailable_for(start + SHOW_AFTER), SHOW_AFTER);
+    }
+}
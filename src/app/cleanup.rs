@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Freeing space on a reader that ran out of room for another print.
+
+use crate::app::finger_name::finger_id_to_localized;
+
+/// A print kept on the reader, offered for deletion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredPrint {
+    pub username: String,
+    pub finger: String,
+    pub selected: bool,
+}
+
+impl StoredPrint {
+    /// The finger's name, or its fprintd id if it isn't one the app knows.
+    pub fn localized_finger(&self) -> String {
+        finger_id_to_localized(&self.finger)
+    }
+}
+
+/// State of the flow offered after an enrollment fails with
+/// `enroll-data-full`: pick prints to delete, then enroll again.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageCleanup {
+    /// Prints on the reader, `None` until they are listed.
+    pub prints: Option<Vec<StoredPrint>>,
+    /// Whether the selected prints are being deleted.
+    pub deleting: bool,
+}
+
+impl StorageCleanup {
+    /// Fills in the prints listed for each user.
+    pub fn set_prints(&mut self, by_user: Vec<(String, Vec<String>)>) {
+        self.prints = Some(
+            by_user
+                .into_iter()
+                .flat_map(|(username, fingers)| {
+                    fingers.into_iter().map(move |finger| StoredPrint {
+                        username: username.clone(),
+                        finger,
+                        selected: false,
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    pub fn toggle(&mut self, index: usize, selected: bool) {
+        if let Some(print) = self.prints.as_mut().and_then(|prints| prints.get_mut(index)) {
+            print.selected = selected;
+        }
+    }
+
+    /// Prints picked for deletion.
+    pub fn selected(&self) -> Vec<StoredPrint> {
+        self.prints
+            .iter()
+            .flatten()
+            .filter(|print| print.selected)
+            .cloned()
+            .collect()
+    }
+
+    /// Drops prints that were deleted, so a partial failure can be retried.
+    pub fn remove(&mut self, deleted: &[StoredPrint]) {
+        if let Some(prints) = &mut self.prints {
+            prints.retain(|print| {
+                !deleted
+                    .iter()
+                    .any(|d| d.username == print.username && d.finger == print.finger)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleanup() -> StorageCleanup {
+        let mut cleanup = StorageCleanup::default();
+        cleanup.set_prints(vec![
+            (
+                "alice".to_string(),
+                vec!["right-index-finger".to_string(), "left-thumb".to_string()],
+            ),
+            ("bob".to_string(), vec!["right-index-finger".to_string()]),
+        ]);
+        cleanup
+    }
+
+    #[test]
+    fn test_set_prints_flattens_users() {
+        let cleanup = cleanup();
+        let prints = cleanup.prints.as_ref().unwrap();
+
+        assert_eq!(prints.len(), 3);
+        assert_eq!(prints[2].username, "bob");
+        assert!(cleanup.selected().is_empty());
+    }
+
+    #[test]
+    fn test_toggle_and_remove() {
+        let mut cleanup = cleanup();
+        cleanup.toggle(1, true);
+        cleanup.toggle(2, true);
+        cleanup.toggle(2, false);
+        // Out of range toggles are ignored
+        cleanup.toggle(10, true);
+
+        let selected = cleanup.selected();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].finger, "left-thumb");
+
+        cleanup.remove(&selected);
+        let prints = cleanup.prints.as_ref().unwrap();
+        assert_eq!(prints.len(), 2);
+        assert!(prints.iter().all(|print| print.finger != "left-thumb"));
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::cleanup::StoredPrint;
+use crate::app::message::UserOption;
+use crate::app::page::Page;
+use crate::fl;
+use fprint_client::fprint::TemplateStorage;
+
+/// A destructive action waiting for the user's go-ahead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Removing the prints of every user from the reader.
+    ClearDevice,
+    /// Finishing a clear that stopped before `users` were cleared.
+    ClearResume { users: Vec<String> },
+    /// Deleting the finger shown on `page`, or every print when it isn't a finger.
+    Delete { page: Page, user: UserOption },
+    /// Deleting the prints picked to make room on a full reader.
+    Cleanup { prints: Vec<StoredPrint> },
+}
+
+impl Confirmation {
+    pub fn title(&self) -> String {
+        match self {
+            Self::ClearDevice | Self::ClearResume { .. } => fl!("clear-device"),
+            Self::Delete { .. } | Self::Cleanup { .. } => fl!("delete"),
+        }
+    }
+
+    /// What will be removed, naming the affected user and finger.
+    pub fn body(&self, storage: TemplateStorage) -> String {
+        match self {
+            Self::ClearDevice => match storage {
+                TemplateStorage::Host => fl!("clear-device-confirm"),
+                TemplateStorage::Device => fl!("clear-device-confirm-on-chip"),
+            },
+            Self::ClearResume { users } => fl!(
+                "clear-resume-confirm",
+                users = crate::i18n::join_list(users)
+            ),
+            Self::Delete { page, user } if page.as_finger_id().is_some() => fl!(
+                "delete-confirm",
+                finger = page.localized_name(),
+                user = user.to_string()
+            ),
+            Self::Delete { user, .. } => fl!("delete-all-confirm", user = user.to_string()),
+            Self::Cleanup { prints } => {
+                let mut users: Vec<&str> = prints.iter().map(|p| p.username.as_str()).collect();
+                users.dedup();
+                fl!(
+                    "cleanup-confirm",
+                    count = prints.len(),
+                    users = crate::i18n::join_list(users)
+                )
+            }
+        }
+    }
+
+    /// Label of the button that goes ahead.
+    pub fn confirm_label(&self) -> String {
+        match self {
+            Self::ClearDevice => fl!("clear-device"),
+            Self::ClearResume { .. } => fl!("clear-resume"),
+            Self::Delete { page, .. } if page.as_finger_id().is_some() => fl!("delete"),
+            Self::Delete { .. } => fl!("delete-all"),
+            Self::Cleanup { .. } => fl!("delete"),
+        }
+    }
+
+    /// Whether going ahead clears the prints of whole users.
+    pub fn clears_users(&self) -> bool {
+        matches!(self, Self::ClearDevice | Self::ClearResume { .. })
+    }
+}
+
+/// How many prints clearing the device removes of each user, like
+/// "3 prints for alice, 1 print for bob".
+pub fn clear_impact(preview: &[(String, Vec<String>)]) -> String {
+    let users: Vec<String> = preview
+        .iter()
+        .map(|(username, fingers)| {
+            fl!(
+                "clear-preview-user",
+                count = fingers.len(),
+                user = username.as_str()
+            )
+        })
+        .collect();
+    crate::i18n::join_list(users)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_delete_names_user_and_finger() {
+        let user = UserOption {
+            username: Arc::new("alice".to_string()),
+            realname: Arc::new(String::new()),
+            system: false,
+            avatar: None,
+        };
+
+        let finger = Confirmation::Delete {
+            page: Page::LeftThumb,
+            user: user.clone(),
+        };
+        let body = finger.body(TemplateStorage::Host);
+        assert!(body.contains("alice"));
+        assert!(body.contains(&Page::LeftThumb.localized_name()));
+
+        let all = Confirmation::Delete {
+            page: Page::DeleteAllUsersPrints,
+            user,
+        };
+        assert!(all.body(TemplateStorage::Host).contains("alice"));
+        assert_ne!(all.confirm_label(), finger.confirm_label());
+    }
+
+    #[test]
+    fn test_cleanup_names_each_user_once() {
+        let print = |username: &str, finger: &str| StoredPrint {
+            username: username.to_string(),
+            finger: finger.to_string(),
+            selected: true,
+        };
+        let cleanup = Confirmation::Cleanup {
+            prints: vec![
+                print("alice", "left-thumb"),
+                print("alice", "right-thumb"),
+                print("bob", "left-index-finger"),
+            ],
+        };
+        let body = cleanup.body(TemplateStorage::Host);
+        assert!(body.contains('3'));
+        assert_eq!(body.matches("alice").count(), 1);
+        assert!(body.contains("bob"));
+    }
+
+    #[test]
+    fn test_clear_impact_counts_prints() {
+        let preview = vec![
+            (
+                "alice".to_string(),
+                vec!["left-thumb".to_string(), "right-thumb".to_string()],
+            ),
+            ("bob".to_string(), vec!["left-index-finger".to_string()]),
+        ];
+        let impact = clear_impact(&preview);
+        let (alice, bob) = impact.split_once(", ").unwrap();
+        assert!(alice.contains('2') && alice.contains("alice"));
+        assert!(bob.contains("bob") && !bob.contains('2'));
+        assert_eq!(clear_impact(&[]), "");
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Detection of other services that keep the reader busy.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Failures within this window count as repeated.
+const WINDOW: Duration = Duration::from_secs(60);
+/// Number of failures within the window before guidance is shown.
+const THRESHOLD: usize = 3;
+
+/// Session bus names of settings apps known to claim the reader while open.
+const KNOWN_CONFLICTS: &[&str] = &[
+    "org.gnome.Settings",
+    "org.gnome.ControlCenter",
+    "org.kde.systemsettings",
+];
+
+/// Counts recent `AlreadyInUse` failures.
+#[derive(Debug, Default)]
+pub struct InUseTracker {
+    failures: VecDeque<Instant>,
+}
+
+impl InUseTracker {
+    /// Records a failure and returns whether failures keep repeating.
+    pub fn record(&mut self, now: Instant) -> bool {
+        while self
+            .failures
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > WINDOW)
+        {
+            self.failures.pop_front();
+        }
+        self.failures.push_back(now);
+        self.failures.len() >= THRESHOLD
+    }
+
+    pub fn reset(&mut self) {
+        self.failures.clear();
+    }
+}
+
+/// Lists running services that are known to compete for the reader.
+pub async fn find_conflicting_services() -> Vec<String> {
+    let names = async {
+        let connection = zbus::Connection::session().await?;
+        let dbus = zbus::fdo::DBusProxy::new(&connection).await?;
+        Ok::<_, zbus::Error>(dbus.list_names().await?)
+    };
+
+    match names.await {
+        Ok(names) => names
+            .into_iter()
+            .map(|name| name.to_string())
+            .filter(|name| KNOWN_CONFLICTS.contains(&name.as_str()))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(%e, "Failed to list session bus names");
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_use_tracker_threshold() {
+        let mut tracker = InUseTracker::default();
+        let start = Instant::now();
+
+        assert!(!tracker.record(start));
+        assert!(!tracker.record(start + Duration::from_secs(10)));
+        assert!(tracker.record(start + Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_in_use_tracker_window() {
+        let mut tracker = InUseTracker::default();
+        let start = Instant::now();
+
+        assert!(!tracker.record(start));
+        assert!(!tracker.record(start + Duration::from_secs(30)));
+        // The first failure has expired by now
+        assert!(!tracker.record(start + Duration::from_secs(70)));
+        assert!(tracker.record(start + Duration::from_secs(80)));
+
+        tracker.reset();
+        assert!(!tracker.record(start + Duration::from_secs(90)));
+    }
+}
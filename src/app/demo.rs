@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A simulated reader for trying out the app without fingerprint hardware.
+
+use crate::app::backend::FprintBackend;
+use crate::app::message::{DeviceOption, Message, UserOption};
+use crate::app::remote::SessionLocation;
+use fprint_client::fprint::{Capabilities, ClearReport, DeviceCaps, ScanType, ServiceState};
+use futures_util::future::{self, BoxFuture};
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use nix::unistd::{Uid, User};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use zbus::zvariant::OwnedObjectPath;
+
+const DEVICE_PATH: &str = "/net/reactivated/Fprint/Device/0";
+const DEVICE_NAME: &str = "Demo Fingerprint Reader";
+const ENROLL_STAGES: u32 = 5;
+/// How long the simulated finger takes for each scan.
+const SCAN_INTERVAL: Duration = Duration::from_millis(1200);
+/// The scan that is rejected once, so retry prompts show up too.
+const RETRY_STAGE: u32 = 3;
+/// Accounts listed besides the current user.
+const OTHER_USERS: [(&str, &str); 2] = [("alex", "Alex Example"), ("sam", "Sam Sample")];
+/// Finger the current user starts out with.
+const ENROLLED_FINGER: &str = "right-index-finger";
+
+/// Pretends to be fprintd with a single press reader.
+///
+/// Prints only live in memory, so nothing on the system is touched.
+#[derive(Clone, Debug)]
+pub struct DemoBackend {
+    users: Vec<UserOption>,
+    prints: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    scan_interval: Duration,
+}
+
+impl DemoBackend {
+    pub fn new() -> Self {
+        let current = User::from_uid(Uid::current())
+            .ok()
+            .flatten()
+            .map(|user| (user.name, user.gecos.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| ("demo".to_string(), String::new()));
+
+        let prints = HashMap::from([(current.0.clone(), vec![ENROLLED_FINGER.to_string()])]);
+        let users = std::iter::once(current)
+            .chain(
+                OTHER_USERS
+                    .iter()
+                    .map(|(name, realname)| (name.to_string(), realname.to_string())),
+            )
+            .map(|(username, realname)| UserOption {
+                username: Arc::new(username),
+                realname: Arc::new(realname),
+                system: false,
+                avatar: None,
+            })
+            .collect();
+
+        Self {
+            users,
+            prints: Arc::new(Mutex::new(prints)),
+            scan_interval: SCAN_INTERVAL,
+        }
+    }
+
+    fn prints(&self, username: &str) -> Vec<String> {
+        self.prints
+            .lock()
+            .unwrap()
+            .get(username)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for DemoBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FprintBackend for DemoBackend {
+    fn find_devices(&self) -> BoxFuture<'static, zbus::Result<Vec<DeviceOption>>> {
+        let device = OwnedObjectPath::try_from(DEVICE_PATH).map(|path| DeviceOption {
+            path: Arc::new(path),
+            name: Arc::new(DEVICE_NAME.to_string()),
+            scan_type: ScanType::Press,
+            stages: Some(ENROLL_STAGES),
+            caps: DeviceCaps::default(),
+        });
+        future::ready(device.map(|device| vec![device]).map_err(zbus::Error::from)).boxed()
+    }
+
+    fn list_users(&self) -> BoxFuture<'static, Vec<UserOption>> {
+        future::ready(self.users.clone()).boxed()
+    }
+
+    fn authorize_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+        future::ready(Ok(true)).boxed()
+    }
+
+    fn may_manage_other_users(&self) -> BoxFuture<'static, zbus::Result<bool>> {
+        future::ready(Ok(true)).boxed()
+    }
+
+    fn list_enrolled_fingers(
+        &self,
+        _device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<Vec<String>>> {
+        future::ready(Ok(self.prints(&username))).boxed()
+    }
+
+    fn list_fingers_by_user(
+        &self,
+        _device: OwnedObjectPath,
+        usernames: Vec<String>,
+    ) -> BoxFuture<'static, Vec<(String, Vec<String>)>> {
+        let fingers = usernames
+            .into_iter()
+            .map(|username| {
+                let fingers = self.prints(&username);
+                (username, fingers)
+            })
+            .filter(|(_, fingers)| !fingers.is_empty())
+            .collect();
+        future::ready(fingers).boxed()
+    }
+
+    fn find_users_with_finger(
+        &self,
+        _device: OwnedObjectPath,
+        usernames: Vec<String>,
+        finger: String,
+    ) -> BoxFuture<'static, Vec<String>> {
+        let users = usernames
+            .into_iter()
+            .filter(|username| self.prints(username).contains(&finger))
+            .collect();
+        future::ready(users).boxed()
+    }
+
+    fn delete_finger(
+        &self,
+        _device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        if let Some(fingers) = self.prints.lock().unwrap().get_mut(&username) {
+            fingers.retain(|f| *f != finger);
+        }
+        future::ready(Ok(())).boxed()
+    }
+
+    fn delete_fingers(
+        &self,
+        _device: OwnedObjectPath,
+        username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        self.prints.lock().unwrap().remove(&username);
+        future::ready(Ok(())).boxed()
+    }
+
+    fn clear_users(
+        &self,
+        _device: OwnedObjectPath,
+        usernames: Vec<String>,
+        cancel: Arc<AtomicBool>,
+    ) -> BoxStream<'static, Message> {
+        let prints = self.prints.clone();
+        let interval = self.scan_interval;
+        cosmic::iced::stream::channel(4, move |mut output| async move {
+            let mut report = ClearReport::default();
+            for username in usernames {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                // Give the cancel button something to do
+                tokio::time::sleep(interval / 2).await;
+                prints.lock().unwrap().remove(&username);
+                let _ = output.send(Message::ClearUserDone(username.clone())).await;
+                report.cleared.push(username);
+            }
+            let _ = output.send(Message::ClearComplete(Ok(report))).await;
+        })
+        .boxed()
+    }
+
+    fn probe_claim(
+        &self,
+        _device: OwnedObjectPath,
+        _username: String,
+    ) -> BoxFuture<'static, zbus::Result<()>> {
+        future::ready(Ok(())).boxed()
+    }
+
+    fn enroll(
+        &self,
+        _device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message> {
+        let prints = self.prints.clone();
+        let interval = self.scan_interval;
+        cosmic::iced::stream::channel(8, move |mut output| async move {
+            let _ = output.send(Message::EnrollStart(Some(ENROLL_STAGES))).await;
+
+            for stage in 1..=ENROLL_STAGES {
+                if stage == RETRY_STAGE {
+                    tokio::time::sleep(interval).await;
+                    let retry = Message::EnrollStatus("enroll-finger-not-centered".into(), false);
+                    let _ = output.send(retry).await;
+                }
+                tokio::time::sleep(interval).await;
+
+                if stage < ENROLL_STAGES {
+                    let passed = Message::EnrollStatus("enroll-stage-passed".into(), false);
+                    let _ = output.send(passed).await;
+                }
+            }
+
+            prints
+                .lock()
+                .unwrap()
+                .entry(username)
+                .or_default()
+                .push(finger);
+            let _ = output
+                .send(Message::EnrollStatus("enroll-completed".into(), true))
+                .await;
+        })
+        .boxed()
+    }
+
+    fn verify(
+        &self,
+        _device: OwnedObjectPath,
+        finger: String,
+        username: String,
+    ) -> BoxStream<'static, Message> {
+        let fingers = self.prints(&username);
+        let interval = self.scan_interval;
+        cosmic::iced::stream::channel(2, move |mut output| async move {
+            tokio::time::sleep(interval).await;
+            let matched = if finger == "any" {
+                !fingers.is_empty()
+            } else {
+                fingers.contains(&finger)
+            };
+            let result = if matched {
+                "verify-match"
+            } else {
+                "verify-no-match"
+            };
+            let selected = match fingers.first() {
+                Some(first) if finger == "any" => first.clone(),
+                _ => finger,
+            };
+            let _ = output.send(Message::VerifyFingerSelected(selected)).await;
+            let _ = output
+                .send(Message::VerifyStatus(result.to_string(), true))
+                .await;
+        })
+        .boxed()
+    }
+
+    fn enroll_stop(&self, _device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+        future::ready(Ok(())).boxed()
+    }
+
+    fn verify_stop(&self, _device: OwnedObjectPath) -> BoxFuture<'static, zbus::Result<()>> {
+        future::ready(Ok(())).boxed()
+    }
+
+    /// The simulated service never goes away.
+    fn watch_service(&self) -> BoxStream<'static, Message> {
+        futures_util::stream::pending().boxed()
+    }
+
+    /// The simulated reader is never unplugged.
+    fn watch_devices(&self) -> BoxStream<'static, Message> {
+        futures_util::stream::pending().boxed()
+    }
+
+    /// The simulated accounts never change.
+    fn watch_users(&self) -> BoxStream<'static, Message> {
+        futures_util::stream::pending().boxed()
+    }
+
+    /// Battery saver is left to the real system.
+    fn watch_power_saver(&self) -> BoxStream<'static, Message> {
+        futures_util::stream::pending().boxed()
+    }
+
+    /// The simulated reader sits next to whoever tries the app.
+    fn session_location(&self) -> BoxFuture<'static, SessionLocation> {
+        future::ready(SessionLocation::Local).boxed()
+    }
+
+    fn service_state(&self) -> BoxFuture<'static, zbus::Result<ServiceState>> {
+        future::ready(Ok(ServiceState::Running)).boxed()
+    }
+
+    fn start_service(&self) -> BoxFuture<'static, zbus::Result<()>> {
+        future::ready(Ok(())).boxed()
+    }
+
+    fn probe_capabilities(
+        &self,
+        _device: OwnedObjectPath,
+    ) -> BoxFuture<'static, zbus::Result<Capabilities>> {
+        future::ready(Ok(Capabilities::default())).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_enroll_reports_every_stage() {
+        let backend = DemoBackend {
+            scan_interval: Duration::ZERO,
+            ..DemoBackend::new()
+        };
+        let device = OwnedObjectPath::try_from(DEVICE_PATH).unwrap();
+
+        let messages: Vec<Message> = backend
+            .enroll(device, "left-thumb".to_string(), "alex".to_string())
+            .collect()
+            .await;
+
+        assert!(matches!(messages.first(), Some(Message::EnrollStart(Some(ENROLL_STAGES)))));
+        let passed = messages
+            .iter()
+            .filter(|m| matches!(m, Message::EnrollStatus(s, false) if s == "enroll-stage-passed"))
+            .count();
+        assert_eq!(passed, ENROLL_STAGES as usize - 1);
+        assert!(matches!(
+            messages.last(),
+            Some(Message::EnrollStatus(s, true)) if s == "enroll-completed"
+        ));
+        assert_eq!(backend.prints("alex"), vec!["left-thumb".to_string()]);
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::page::Page;
+
+/// Steps of the guided enrollment flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WizardStep {
+    /// Choosing whose prints are enrolled.
+    ChooseUser,
+    /// Choosing the finger to enroll.
+    ChooseFinger,
+    /// Explaining how to present the finger to the reader.
+    Instructions,
+    /// Enrolling the finger.
+    Enroll,
+    /// Checking that the new print matches.
+    Verify,
+    /// The finger is enrolled.
+    Done,
+}
+
+/// State of the wizard that walks first-time users through enrolling a finger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnrollWizard {
+    pub step: WizardStep,
+    pub finger: Option<Page>,
+    /// Whether the flow starts by choosing a user.
+    choose_user: bool,
+}
+
+impl EnrollWizard {
+    pub fn new(choose_user: bool) -> Self {
+        Self {
+            step: if choose_user {
+                WizardStep::ChooseUser
+            } else {
+                WizardStep::ChooseFinger
+            },
+            finger: None,
+            choose_user,
+        }
+    }
+
+    /// Chooses the finger to enroll. Fingers that already have a print can't
+    /// be chosen and clear the choice.
+    pub fn select_finger(&mut self, page: Page, enrolled_fingers: &[String]) {
+        if self.step != WizardStep::ChooseFinger {
+            return;
+        }
+
+        self.finger = Some(page).filter(|page| {
+            page.as_finger_id()
+                .is_some_and(|id| !enrolled_fingers.iter().any(|f| f == id))
+        });
+    }
+
+    /// Moves on to the next step, if the current one is complete.
+    pub fn next(&mut self) {
+        self.step = match self.step {
+            WizardStep::ChooseUser => WizardStep::ChooseFinger,
+            WizardStep::ChooseFinger if self.finger.is_some() => WizardStep::Instructions,
+            WizardStep::Instructions => WizardStep::Enroll,
+            WizardStep::Enroll => WizardStep::Verify,
+            WizardStep::Verify => WizardStep::Done,
+            step => step,
+        };
+    }
+
+    /// Returns to the previous step.
+    ///
+    /// A failed enrollment goes back to the instructions to try again; once a
+    /// print exists there is no way back.
+    pub fn back(&mut self) {
+        self.step = match self.step {
+            WizardStep::ChooseFinger if self.choose_user => WizardStep::ChooseUser,
+            WizardStep::Instructions => WizardStep::ChooseFinger,
+            WizardStep::Enroll => WizardStep::Instructions,
+            step => step,
+        };
+    }
+
+    /// Starts over with another finger of the same user.
+    pub fn restart(&mut self) {
+        self.step = WizardStep::ChooseFinger;
+        self.finger = None;
+    }
+
+    /// Whether the user and finger must stay as they are.
+    pub fn locks_selection(&self) -> bool {
+        matches!(self.step, WizardStep::Enroll | WizardStep::Verify)
+    }
+
+    /// The current step counted from one, and the number of steps.
+    pub fn position(&self) -> (usize, usize) {
+        let index = match self.step {
+            WizardStep::ChooseUser => 0,
+            WizardStep::ChooseFinger => 1,
+            WizardStep::Instructions => 2,
+            WizardStep::Enroll => 3,
+            WizardStep::Verify | WizardStep::Done => 4,
+        };
+
+        if self.choose_user {
+            (index + 1, 5)
+        } else {
+            (index, 4)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow() {
+        let mut wizard = EnrollWizard::new(true);
+        assert_eq!(wizard.step, WizardStep::ChooseUser);
+        assert_eq!(wizard.position(), (1, 5));
+
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::ChooseFinger);
+
+        // A finger must be chosen before moving on
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::ChooseFinger);
+
+        wizard.select_finger(Page::LeftIndex, &[]);
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::Instructions);
+
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::Enroll);
+        assert!(wizard.locks_selection());
+
+        // A failed enrollment returns to the instructions
+        wizard.back();
+        assert_eq!(wizard.step, WizardStep::Instructions);
+
+        wizard.next();
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::Verify);
+        wizard.back();
+        assert_eq!(wizard.step, WizardStep::Verify);
+
+        wizard.next();
+        assert_eq!(wizard.step, WizardStep::Done);
+        assert_eq!(wizard.position(), (5, 5));
+
+        wizard.restart();
+        assert_eq!(wizard.step, WizardStep::ChooseFinger);
+        assert_eq!(wizard.finger, None);
+    }
+
+    #[test]
+    fn test_without_user_step() {
+        let mut wizard = EnrollWizard::new(false);
+        assert_eq!(wizard.step, WizardStep::ChooseFinger);
+        assert_eq!(wizard.position(), (1, 4));
+
+        wizard.back();
+        assert_eq!(wizard.step, WizardStep::ChooseFinger);
+    }
+
+    #[test]
+    fn test_select_finger_skips_enrolled() {
+        let enrolled = vec!["left-index-finger".to_string()];
+        let mut wizard = EnrollWizard::new(false);
+
+        wizard.select_finger(Page::LeftIndex, &enrolled);
+        assert_eq!(wizard.finger, None);
+
+        wizard.select_finger(Page::LeftThumb, &enrolled);
+        assert_eq!(wizard.finger, Some(Page::LeftThumb));
+
+        wizard.select_finger(Page::DeleteAllUsersPrints, &enrolled);
+        assert_eq!(wizard.finger, None);
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Where an enrollment is in its life, from claiming the reader to releasing
+//! it. Each transition is taken on a message from fprintd or the user, and
+//! those that don't fit the current state are refused, so a late or repeated
+//! message can't resurrect a finished enrollment or stop one twice.
+
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Enrollment {
+    #[default]
+    Idle,
+    /// Claiming the reader and asking fprintd to start enrolling the finger.
+    Claiming(Arc<String>),
+    /// fprintd is taking scans of the finger.
+    Enrolling(Arc<String>),
+    /// Asked fprintd to stop, waiting for it to confirm.
+    Cancelling(Arc<String>),
+    /// The enrollment ended but fprintd still holds the reader.
+    Releasing,
+}
+
+impl Enrollment {
+    /// Starts enrolling `finger`, unless an enrollment is running or fprintd
+    /// still holds the reader from the last one.
+    pub fn start(&mut self, finger: &str) -> bool {
+        if *self != Self::Idle {
+            return false;
+        }
+        *self = Self::Claiming(Arc::new(finger.to_string()));
+        true
+    }
+
+    /// fprintd claimed the reader and started enrolling.
+    pub fn started(&mut self) -> bool {
+        match std::mem::take(self) {
+            Self::Claiming(finger) => {
+                *self = Self::Enrolling(finger);
+                true
+            }
+            other => {
+                *self = other;
+                false
+            }
+        }
+    }
+
+    /// The user asked to stop. `false` when there is nothing to stop or
+    /// stopping was already asked for.
+    pub fn cancel(&mut self) -> bool {
+        match std::mem::take(self) {
+            Self::Claiming(finger) | Self::Enrolling(finger) => {
+                *self = Self::Cancelling(finger);
+                true
+            }
+            other => {
+                *self = other;
+                false
+            }
+        }
+    }
+
+    /// fprintd reported the last status of the enrollment, whether it
+    /// completed, failed or was cancelled. Returns the finger it was for.
+    pub fn finish(&mut self, claimed: bool) -> Option<Arc<String>> {
+        let finger = self.finger().cloned()?;
+        *self = if claimed { Self::Releasing } else { Self::Idle };
+        Some(finger)
+    }
+
+    /// The reader is no longer claimed.
+    pub fn released(&mut self) {
+        if *self == Self::Releasing {
+            *self = Self::Idle;
+        }
+    }
+
+    /// Drops the enrollment without waiting for fprintd, e.g. when it went
+    /// away. Returns the finger it was for.
+    pub fn abandon(&mut self) -> Option<Arc<String>> {
+        let finger = self.finger().cloned();
+        *self = Self::Idle;
+        finger
+    }
+
+    /// The finger being enrolled, from claiming the reader until fprintd
+    /// reports the end of the enrollment.
+    pub fn finger(&self) -> Option<&Arc<String>> {
+        match self {
+            Self::Claiming(finger) | Self::Enrolling(finger) | Self::Cancelling(finger) => {
+                Some(finger)
+            }
+            Self::Idle | Self::Releasing => None,
+        }
+    }
+
+    /// Whether a finger is being enrolled.
+    pub fn is_active(&self) -> bool {
+        self.finger().is_some()
+    }
+
+    pub fn is_cancelling(&self) -> bool {
+        matches!(self, Self::Cancelling(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enrollment_completes() {
+        let mut enrollment = Enrollment::default();
+        assert!(enrollment.start("left-thumb"));
+        assert!(!enrollment.start("right-thumb"));
+        assert_eq!(enrollment.finger().map(|f| f.as_str()), Some("left-thumb"));
+
+        assert!(enrollment.started());
+        assert!(!enrollment.started());
+        assert!(matches!(enrollment, Enrollment::Enrolling(_)));
+
+        let finger = enrollment.finish(true);
+        assert_eq!(finger.as_deref().map(String::as_str), Some("left-thumb"));
+        assert_eq!(enrollment, Enrollment::Releasing);
+        assert!(!enrollment.is_active());
+        // A late status of the finished enrollment changes nothing
+        assert_eq!(enrollment.finish(true), None);
+        assert!(!enrollment.cancel());
+
+        enrollment.released();
+        assert_eq!(enrollment, Enrollment::Idle);
+    }
+
+    #[test]
+    fn test_enrollment_cancelled_once() {
+        let mut enrollment = Enrollment::default();
+        assert!(!enrollment.cancel());
+
+        enrollment.start("left-thumb");
+        assert!(enrollment.cancel());
+        assert!(enrollment.is_cancelling());
+        assert!(!enrollment.cancel());
+        // Confirming the start after stopping was asked for keeps stopping
+        assert!(!enrollment.started());
+        assert!(enrollment.is_cancelling());
+
+        // Never claimed, so nothing to release
+        assert!(enrollment.finish(false).is_some());
+        assert_eq!(enrollment, Enrollment::Idle);
+    }
+
+    #[test]
+    fn test_enrollment_abandoned() {
+        let mut enrollment = Enrollment::default();
+        enrollment.start("left-thumb");
+        enrollment.started();
+        assert!(enrollment.abandon().is_some());
+        assert_eq!(enrollment, Enrollment::Idle);
+        assert_eq!(enrollment.abandon(), None);
+
+        // Starting again waits until fprintd released the reader
+        enrollment.start("left-thumb");
+        enrollment.finish(true);
+        assert!(!enrollment.start("right-thumb"));
+        assert_eq!(enrollment, Enrollment::Releasing);
+        enrollment.released();
+        assert!(enrollment.start("right-thumb"));
+    }
+}
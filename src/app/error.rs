@@ -1,8 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::fl;
+use fprint_client::fprint::ServiceState;
+use fprint_client::session::LOCKED_BY_OTHER_INSTANCE;
+use std::fmt;
 
-#[derive(Debug, Clone, PartialEq)]
+/// Errors are equal when they are the same kind of error; the DBus error
+/// they came from is only kept for showing.
+#[derive(Debug, Clone)]
 pub enum AppError {
     PermissionDenied,
     AlreadyInUse,
@@ -12,11 +17,60 @@ pub enum AppError {
     PrintsNotDeleted,
     Timeout,
     DeviceNotFound,
+    ServiceUnavailable,
+    ServiceNotInstalled,
     ConnectDbus(String),
     Unknown(String),
+    /// A known error with the DBus error fprintd or the bus replied with.
+    Dbus(Box<AppError>, DbusError),
+}
+
+/// A DBus error reply as it came over the bus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbusError {
+    /// Name of the error, e.g. `net.reactivated.Fprint.Error.PermissionDenied`.
+    pub name: String,
+    /// Explanation the sender gave, if any.
+    pub message: Option<String>,
+}
+
+impl fmt::Display for DbusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {message}", self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+impl PartialEq for AppError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.kind(), other.kind()) {
+            (AppError::ConnectDbus(a), AppError::ConnectDbus(b))
+            | (AppError::Unknown(a), AppError::Unknown(b)) => a == b,
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
 }
 
 impl AppError {
+    /// The kind of error, without the DBus error it came from.
+    pub fn kind(&self) -> &AppError {
+        match self {
+            AppError::Dbus(kind, _) => kind.kind(),
+            kind => kind,
+        }
+    }
+
+    /// The DBus error behind a known error, to show on request.
+    pub fn dbus_error(&self) -> Option<&DbusError> {
+        match self {
+            AppError::Dbus(_, error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The error in one line, as shown in the status area.
     pub fn localized_message(&self) -> String {
         match self {
             AppError::PermissionDenied => fl!("error-permission-denied"),
@@ -27,8 +81,84 @@ impl AppError {
             AppError::PrintsNotDeleted => fl!("error-prints-not-deleted"),
             AppError::Timeout => fl!("error-timeout"),
             AppError::DeviceNotFound => fl!("error-device-not-found"),
+            AppError::ServiceUnavailable => fl!("error-service-unavailable"),
+            AppError::ServiceNotInstalled => fl!("error-service-not-installed"),
             AppError::ConnectDbus(msg) => fl!("error-connect-dbus", err = msg),
             AppError::Unknown(msg) => msg.clone(),
+            AppError::Dbus(kind, _) => kind.localized_message(),
+        }
+    }
+
+    /// A short heading for dialogs and banners.
+    pub fn localized_title(&self) -> String {
+        match self {
+            AppError::PermissionDenied => fl!("error-permission-denied-title"),
+            AppError::AlreadyInUse => fl!("error-already-in-use-title"),
+            AppError::Internal => fl!("error-internal-title"),
+            AppError::NoEnrolledPrints => fl!("error-no-enrolled-prints-title"),
+            AppError::ClaimDevice => fl!("error-claim-device-title"),
+            AppError::PrintsNotDeleted => fl!("error-prints-not-deleted-title"),
+            AppError::Timeout => fl!("error-timeout-title"),
+            AppError::DeviceNotFound => fl!("error-device-not-found-title"),
+            AppError::ServiceUnavailable => fl!("error-service-unavailable-title"),
+            AppError::ServiceNotInstalled => fl!("error-service-not-installed-title"),
+            AppError::ConnectDbus(_) => fl!("error-connect-dbus-title"),
+            AppError::Unknown(_) => fl!("error-unknown-title"),
+            AppError::Dbus(kind, _) => kind.localized_title(),
+        }
+    }
+
+    /// What went wrong and what can be done about it, to go with [`AppError::localized_title`].
+    pub fn localized_detail(&self) -> String {
+        match self {
+            AppError::PermissionDenied => fl!("error-permission-denied-detail"),
+            AppError::AlreadyInUse => fl!("error-already-in-use-detail"),
+            AppError::Internal => fl!("error-internal-detail"),
+            AppError::NoEnrolledPrints => fl!("error-no-enrolled-prints-detail"),
+            AppError::ClaimDevice => fl!("error-claim-device-detail"),
+            AppError::PrintsNotDeleted => fl!("error-prints-not-deleted-detail"),
+            AppError::Timeout => fl!("error-timeout-detail"),
+            AppError::DeviceNotFound => fl!("error-device-not-found-detail"),
+            AppError::ServiceUnavailable => fl!("error-service-unavailable-detail"),
+            AppError::ServiceNotInstalled => fl!("error-service-not-installed-detail"),
+            AppError::ConnectDbus(msg) => fl!("error-connect-dbus-detail", err = msg),
+            AppError::Unknown(msg) => msg.clone(),
+            AppError::Dbus(kind, _) => kind.localized_detail(),
+        }
+    }
+
+    /// The step most likely to get past the error, if there is one.
+    pub fn suggested_action(&self) -> Option<ErrorAction> {
+        match self {
+            AppError::PermissionDenied => Some(ErrorAction::Authenticate),
+            AppError::AlreadyInUse
+            | AppError::ClaimDevice
+            | AppError::PrintsNotDeleted
+            | AppError::Timeout => Some(ErrorAction::Retry),
+            AppError::Internal => Some(ErrorAction::RestartDaemon),
+            AppError::ServiceUnavailable => Some(ErrorAction::StartDaemon),
+            AppError::DeviceNotFound
+            | AppError::ServiceNotInstalled
+            | AppError::ConnectDbus(_)
+            | AppError::Unknown(_) => Some(ErrorAction::Troubleshoot),
+            AppError::NoEnrolledPrints => None,
+            AppError::Dbus(kind, _) => kind.suggested_action(),
+        }
+    }
+
+    /// Refines an error from looking up the readers with what the bus knows
+    /// about fprintd, telling a missing service apart from a missing reader.
+    pub fn with_service_state(self, state: ServiceState) -> Self {
+        if let AppError::Dbus(kind, error) = self {
+            return AppError::Dbus(Box::new(kind.with_service_state(state)), error);
+        }
+        match (state, self) {
+            (ServiceState::NotInstalled, _) => AppError::ServiceNotInstalled,
+            (ServiceState::Activatable, AppError::Unknown(_) | AppError::ServiceNotInstalled) => {
+                AppError::ServiceUnavailable
+            }
+            (ServiceState::Running, AppError::Unknown(_)) => AppError::DeviceNotFound,
+            (_, err) => err,
         }
     }
 
@@ -40,10 +170,37 @@ impl AppError {
     }
 }
 
+/// Something the user can do about an [`AppError`], offered next to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorAction {
+    /// Run the failed operation again.
+    Retry,
+    /// Open the fprint documentation on supported readers and common problems.
+    Troubleshoot,
+    /// Start the fprintd service through DBus activation.
+    StartDaemon,
+    /// Restart the fprintd service, which asks for an administrator password.
+    RestartDaemon,
+    /// Ask polkit for permission to manage the prints.
+    Authenticate,
+}
+
+impl ErrorAction {
+    pub fn localized_label(&self) -> String {
+        match self {
+            ErrorAction::Retry => fl!("error-action-retry"),
+            ErrorAction::Troubleshoot => fl!("error-action-troubleshoot"),
+            ErrorAction::StartDaemon => fl!("error-action-start-daemon"),
+            ErrorAction::RestartDaemon => fl!("error-action-restart-daemon"),
+            ErrorAction::Authenticate => fl!("authenticate"),
+        }
+    }
+}
+
 impl From<zbus::Error> for AppError {
     fn from(err: zbus::Error) -> Self {
-        if let zbus::Error::MethodError(name, _, _) = &err {
-            match name.as_str() {
+        if let zbus::Error::MethodError(name, message, _) = &err {
+            let kind = match name.as_str() {
                 "net.reactivated.Fprint.Error.PermissionDenied" => AppError::PermissionDenied,
                 "net.reactivated.Fprint.Error.AlreadyInUse" => AppError::AlreadyInUse,
                 "net.reactivated.Fprint.Error.Internal" => AppError::Internal,
@@ -52,8 +209,22 @@ impl From<zbus::Error> for AppError {
                 "net.reactivated.Fprint.Error.PrintsNotDeleted" => AppError::PrintsNotDeleted,
                 "net.reactivated.Fprint.Error.Timeout" => AppError::Timeout,
                 "net.reactivated.Fprint.Error.DeviceNotFound" => AppError::DeviceNotFound,
-                _ => AppError::Unknown(err.to_string()),
-            }
+                // Nothing on the bus can be activated as fprintd
+                "org.freedesktop.DBus.Error.ServiceUnknown" => AppError::ServiceNotInstalled,
+                // fprintd is not running and could not be activated
+                "org.freedesktop.DBus.Error.NameHasNoOwner"
+                | "org.freedesktop.DBus.Error.Spawn.ChildExited" => AppError::ServiceUnavailable,
+                _ => return AppError::Unknown(err.to_string()),
+            };
+            let error = DbusError {
+                name: name.to_string(),
+                message: message.clone(),
+            };
+            AppError::Dbus(Box::new(kind), error)
+        } else if let zbus::Error::Failure(msg) = &err
+            && msg == LOCKED_BY_OTHER_INSTANCE
+        {
+            AppError::AlreadyInUse
         } else {
             AppError::Unknown(err.to_string())
         }
@@ -90,6 +261,8 @@ mod tests {
             ("net.reactivated.Fprint.Error.PrintsNotDeleted", AppError::PrintsNotDeleted),
             ("net.reactivated.Fprint.Error.Timeout", AppError::Timeout),
             ("net.reactivated.Fprint.Error.DeviceNotFound", AppError::DeviceNotFound),
+            ("org.freedesktop.DBus.Error.ServiceUnknown", AppError::ServiceNotInstalled),
+            ("org.freedesktop.DBus.Error.NameHasNoOwner", AppError::ServiceUnavailable),
         ];
 
         for (error_str, expected) in test_cases {
@@ -99,6 +272,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_keeps_dbus_error() {
+        let zbus_err = create_method_error("net.reactivated.Fprint.Error.ClaimDevice");
+        let app_err = AppError::from(zbus_err);
+        assert_eq!(app_err.kind(), &AppError::ClaimDevice);
+        assert_eq!(
+            app_err.dbus_error().map(ToString::to_string).as_deref(),
+            Some("net.reactivated.Fprint.Error.ClaimDevice")
+        );
+        assert_eq!(app_err.localized_title(), AppError::ClaimDevice.localized_title());
+        assert_eq!(app_err.suggested_action(), Some(ErrorAction::Retry));
+
+        let error = DbusError {
+            name: "net.reactivated.Fprint.Error.Internal".to_string(),
+            message: Some("Failed to open storage".to_string()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "net.reactivated.Fprint.Error.Internal: Failed to open storage"
+        );
+        let app_err = AppError::Dbus(Box::new(AppError::Unknown("x".to_string())), error);
+        // The reader lookup refines the kind but keeps what fprintd said
+        let refined = app_err.with_service_state(ServiceState::Running);
+        assert_eq!(refined, AppError::DeviceNotFound);
+        assert!(refined.dbus_error().is_some());
+        assert_ne!(AppError::Unknown("a".to_string()), AppError::Unknown("b".to_string()));
+    }
+
     #[test]
     fn test_unknown_zbus_error() {
         let error_str = "net.reactivated.Fprint.Error.UnknownOne";
@@ -112,6 +313,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_title_and_detail() {
+        let known = [
+            AppError::PermissionDenied,
+            AppError::AlreadyInUse,
+            AppError::Internal,
+            AppError::NoEnrolledPrints,
+            AppError::ClaimDevice,
+            AppError::PrintsNotDeleted,
+            AppError::Timeout,
+            AppError::DeviceNotFound,
+            AppError::ServiceUnavailable,
+            AppError::ServiceNotInstalled,
+            AppError::ConnectDbus("bus gone".to_string()),
+        ];
+
+        for err in known {
+            let title = err.localized_title();
+            let detail = err.localized_detail();
+            assert!(!title.is_empty(), "Missing title for {:?}", err);
+            assert!(detail.len() > title.len(), "Detail too short for {:?}", err);
+        }
+
+        let detail = AppError::ConnectDbus("bus gone".to_string()).localized_detail();
+        assert!(detail.contains("bus gone"));
+        assert_eq!(AppError::Unknown("oops".to_string()).localized_detail(), "oops");
+    }
+
+    #[test]
+    fn test_suggested_action() {
+        assert_eq!(AppError::PermissionDenied.suggested_action(), Some(ErrorAction::Authenticate));
+        assert_eq!(AppError::Timeout.suggested_action(), Some(ErrorAction::Retry));
+        assert_eq!(AppError::ServiceUnavailable.suggested_action(), Some(ErrorAction::StartDaemon));
+        assert_eq!(AppError::DeviceNotFound.suggested_action(), Some(ErrorAction::Troubleshoot));
+        assert_eq!(AppError::Internal.suggested_action(), Some(ErrorAction::RestartDaemon));
+        assert_eq!(AppError::NoEnrolledPrints.suggested_action(), None);
+    }
+
+    #[test]
+    fn test_with_service_state() {
+        let unknown = AppError::Unknown("no reply".to_string());
+        assert_eq!(
+            unknown.clone().with_service_state(ServiceState::NotInstalled),
+            AppError::ServiceNotInstalled
+        );
+        assert_eq!(
+            unknown.clone().with_service_state(ServiceState::Activatable),
+            AppError::ServiceUnavailable
+        );
+        assert_eq!(
+            unknown.with_service_state(ServiceState::Running),
+            AppError::DeviceNotFound
+        );
+        assert_eq!(
+            AppError::PermissionDenied.with_service_state(ServiceState::Running),
+            AppError::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn test_locked_by_other_instance() {
+        let zbus_err = zbus::Error::Failure(LOCKED_BY_OTHER_INSTANCE.to_string());
+        assert_eq!(AppError::from(zbus_err), AppError::AlreadyInUse);
+    }
+
     #[test]
     fn test_non_method_error() {
         // Test a different zbus::Error variant
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A stand-in for AccountsService, served over a private connection so the
+//! user list can be tested without a system bus.
+
+use std::time::Duration;
+use zbus::fdo;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::OwnedObjectPath;
+
+const ACCOUNTS_PATH: &str = "/org/freedesktop/Accounts";
+
+/// An account as the fake reports it. Properties left `None` can't be read,
+/// like those older AccountsService versions lack.
+#[derive(Clone, Debug)]
+pub struct FakeUser {
+    pub uid: u32,
+    pub name: String,
+    pub real_name: Option<String>,
+    pub system_account: Option<bool>,
+    pub account_type: Option<i32>,
+    pub icon_file: Option<String>,
+    /// How long reading the account's properties takes.
+    pub delay: Duration,
+}
+
+impl FakeUser {
+    /// A person's account with every property set.
+    pub fn new(uid: u32, name: &str) -> Self {
+        Self {
+            uid,
+            name: name.to_string(),
+            real_name: Some(String::new()),
+            system_account: Some(false),
+            account_type: Some(0),
+            icon_file: Some(String::new()),
+            delay: Duration::ZERO,
+        }
+    }
+
+    fn path(&self) -> OwnedObjectPath {
+        user_path(self.uid)
+    }
+}
+
+fn user_path(uid: u32) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(format!("{ACCOUNTS_PATH}/User{uid}")).unwrap()
+}
+
+fn readable<T: Clone>(value: &Option<T>) -> fdo::Result<T> {
+    value
+        .clone()
+        .ok_or_else(|| fdo::Error::UnknownProperty("Not supported".to_string()))
+}
+
+struct Accounts {
+    users: Vec<OwnedObjectPath>,
+    /// Whether listing the accounts fails.
+    broken: bool,
+}
+
+#[zbus::interface(name = "org.freedesktop.Accounts")]
+impl Accounts {
+    fn list_cached_users(&self) -> fdo::Result<Vec<OwnedObjectPath>> {
+        if self.broken {
+            return Err(fdo::Error::Failed("Listing accounts failed".to_string()));
+        }
+        Ok(self.users.clone())
+    }
+
+    #[zbus(signal)]
+    async fn user_added(emitter: &SignalEmitter<'_>, user: OwnedObjectPath) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn user_deleted(emitter: &SignalEmitter<'_>, user: OwnedObjectPath) -> zbus::Result<()>;
+}
+
+struct User(FakeUser);
+
+#[zbus::interface(name = "org.freedesktop.Accounts.User")]
+impl User {
+    #[zbus(property)]
+    async fn user_name(&self) -> String {
+        tokio::time::sleep(self.0.delay).await;
+        self.0.name.clone()
+    }
+
+    #[zbus(property)]
+    fn real_name(&self) -> fdo::Result<String> {
+        readable(&self.0.real_name)
+    }
+
+    #[zbus(property)]
+    fn system_account(&self) -> fdo::Result<bool> {
+        readable(&self.0.system_account)
+    }
+
+    #[zbus(property)]
+    fn account_type(&self) -> fdo::Result<i32> {
+        readable(&self.0.account_type)
+    }
+
+    #[zbus(property)]
+    fn icon_file(&self) -> fdo::Result<String> {
+        readable(&self.0.icon_file)
+    }
+}
+
+/// The service side of the fake, for changing the accounts during a test.
+pub struct FakeAccounts {
+    server: zbus::Connection,
+}
+
+impl FakeAccounts {
+    /// Serves `users`, returning the fake and the connection the app talks
+    /// to it over.
+    pub async fn serve(users: Vec<FakeUser>) -> (Self, zbus::Connection) {
+        Self::serve_with(users, false).await
+    }
+
+    /// Serves accounts that can't be listed.
+    pub async fn broken() -> (Self, zbus::Connection) {
+        Self::serve_with(Vec::new(), true).await
+    }
+
+    async fn serve_with(users: Vec<FakeUser>, broken: bool) -> (Self, zbus::Connection) {
+        let (server_stream, client_stream) = tokio::net::UnixStream::pair().unwrap();
+
+        let accounts = Accounts {
+            users: users.iter().map(FakeUser::path).collect(),
+            broken,
+        };
+        let mut server = zbus::connection::Builder::unix_stream(server_stream)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(ACCOUNTS_PATH, accounts)
+            .unwrap();
+        for user in users {
+            server = server.serve_at(user.path(), User(user)).unwrap();
+        }
+        let client = zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build();
+
+        let (server, client) = futures_util::try_join!(server.build(), client).unwrap();
+        (Self { server }, client)
+    }
+
+    /// Creates an account and announces it with `UserAdded`.
+    pub async fn add_user(&self, user: FakeUser) {
+        let path = user.path();
+        let objects = self.server.object_server();
+        objects.at(&path, User(user)).await.unwrap();
+
+        let accounts = objects
+            .interface::<_, Accounts>(ACCOUNTS_PATH)
+            .await
+            .unwrap();
+        accounts.get_mut().await.users.push(path.clone());
+        Accounts::user_added(accounts.signal_emitter(), path)
+            .await
+            .unwrap();
+    }
+
+    /// Deletes the account of `uid` and announces it with `UserDeleted`.
+    pub async fn delete_user(&self, uid: u32) {
+        let path = user_path(uid);
+        let objects = self.server.object_server();
+        objects.remove::<User, _>(&path).await.unwrap();
+
+        let accounts = objects
+            .interface::<_, Accounts>(ACCOUNTS_PATH)
+            .await
+            .unwrap();
+        accounts.get_mut().await.users.retain(|user| *user != path);
+        Accounts::user_deleted(accounts.signal_emitter(), path)
+            .await
+            .unwrap();
+    }
+}
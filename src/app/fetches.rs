@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Fetches that a newer one of the same kind makes stale, like listing the
+//! prints of a user after switching to another. Each is aborted when it is
+//! replaced or the window closes, dropping its DBus calls so a late result
+//! can't overwrite the newer one.
+
+use cosmic::iced::task::{Handle, Task};
+
+/// The outstanding fetch of each kind.
+#[derive(Debug, Default)]
+pub struct Fetches {
+    /// Listing the users from AccountsService.
+    pub users: Option<Handle>,
+    /// Listing the prints of the selected user.
+    pub fingers: Option<Handle>,
+    /// Listing the prints of every user for the overview.
+    pub overview: Option<Handle>,
+}
+
+impl Fetches {
+    pub fn abort_all(&mut self) {
+        for slot in [&mut self.users, &mut self.fingers, &mut self.overview] {
+            if let Some(handle) = slot.take() {
+                handle.abort();
+            }
+        }
+    }
+}
+
+/// Runs `task` as the fetch held in `slot`, aborting the one it replaces.
+pub fn replace<T: 'static>(slot: &mut Option<Handle>, task: Task<T>) -> Task<T> {
+    let (task, handle) = task.abortable();
+    if let Some(previous) = slot.replace(handle) {
+        previous.abort();
+    }
+    task
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Names of fingers as fprintd enumerates them, e.g. `left-little-finger`,
+//! for showing to the user.
+
+use crate::app::page::Page;
+
+/// The localized name of the finger fprintd calls `finger_id`, the same one
+/// its page has. Fingers the app doesn't know keep their fprintd id.
+pub fn finger_id_to_localized(finger_id: &str) -> String {
+    Page::from_finger_id(finger_id)
+        .map_or_else(|| finger_id.to_string(), |page| page.localized_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finger_id_to_localized() {
+        for page in Page::all() {
+            if let Some(finger_id) = page.as_finger_id() {
+                assert_eq!(finger_id_to_localized(finger_id), page.localized_name());
+            }
+        }
+        assert_eq!(finger_id_to_localized("any"), "any");
+    }
+}
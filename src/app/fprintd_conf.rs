@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::fl;
+
+pub const FPRINTD_CONF_PATH: &str = "/etc/fprintd.conf";
+
+const DEFAULT_STORAGE_TYPE: &str = "file";
+const DEFAULT_STORAGE_PATH: &str = "/var/lib/fprint";
+
+/// Storage related settings of fprintd, read from `/etc/fprintd.conf`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FprintdConf {
+    storage_type: Option<String>,
+    storage_path: Option<String>,
+}
+
+/// Settings that explain unexpected behavior of the daemon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfWarning {
+    UnknownStorageType(String),
+    CustomStoragePath(String),
+}
+
+impl ConfWarning {
+    pub fn localized_message(&self) -> String {
+        match self {
+            ConfWarning::UnknownStorageType(kind) => {
+                fl!("fprintd-conf-unknown-storage", kind = kind.as_str())
+            }
+            ConfWarning::CustomStoragePath(path) => {
+                fl!("fprintd-conf-custom-path", path = path.as_str())
+            }
+        }
+    }
+}
+
+impl FprintdConf {
+    /// Parses the `[storage]` section of an fprintd.conf file.
+    pub fn parse(contents: &str) -> Self {
+        let mut conf = Self::default();
+        let mut in_storage = false;
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_storage = section.trim() == "storage";
+                continue;
+            }
+
+            if !in_storage {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().to_string();
+                match key.trim() {
+                    "type" => conf.storage_type = Some(value),
+                    "path" => conf.storage_path = Some(value),
+                    _ => {}
+                }
+            }
+        }
+
+        conf
+    }
+
+    pub fn storage_type(&self) -> &str {
+        self.storage_type.as_deref().unwrap_or(DEFAULT_STORAGE_TYPE)
+    }
+
+    pub fn storage_path(&self) -> &str {
+        self.storage_path.as_deref().unwrap_or(DEFAULT_STORAGE_PATH)
+    }
+
+    pub fn warnings(&self) -> Vec<ConfWarning> {
+        let mut warnings = Vec::new();
+
+        if self.storage_type() != DEFAULT_STORAGE_TYPE {
+            warnings.push(ConfWarning::UnknownStorageType(self.storage_type().to_string()));
+        }
+
+        let path = self.storage_path().trim_end_matches('/');
+        if path != DEFAULT_STORAGE_PATH {
+            warnings.push(ConfWarning::CustomStoragePath(self.storage_path().to_string()));
+        }
+
+        warnings
+    }
+}
+
+/// Reads fprintd.conf, treating a missing file as the daemon's defaults.
+///
+/// Returns `None` when the file exists but can't be read.
+pub async fn read_fprintd_conf() -> Option<FprintdConf> {
+    match tokio::fs::read_to_string(FPRINTD_CONF_PATH).await {
+        Ok(contents) => Some(FprintdConf::parse(&contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(FprintdConf::default()),
+        Err(e) => {
+            tracing::warn!(%e, "Failed to read {FPRINTD_CONF_PATH}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_defaults() {
+        let conf = FprintdConf::parse("");
+        assert_eq!(conf.storage_type(), "file");
+        assert_eq!(conf.storage_path(), "/var/lib/fprint");
+        assert!(conf.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_parse_storage_section() {
+        let conf = FprintdConf::parse(
+            "# comment\n[other]\ntype=ignored\n\n[storage]\ntype = file\npath=/srv/fprint\n",
+        );
+        assert_eq!(conf.storage_type(), "file");
+        assert_eq!(conf.storage_path(), "/srv/fprint");
+        assert_eq!(
+            conf.warnings(),
+            vec![ConfWarning::CustomStoragePath("/srv/fprint".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unknown_storage_type() {
+        let conf = FprintdConf::parse("[storage]\ntype=ldap\n");
+        assert_eq!(
+            conf.warnings(),
+            vec![ConfWarning::UnknownStorageType("ldap".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_trailing_slash_is_default_path() {
+        let conf = FprintdConf::parse("[storage]\npath=/var/lib/fprint/\n");
+        assert!(conf.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_warning_messages() {
+        let warning = ConfWarning::UnknownStorageType("ldap".to_string());
+        assert!(warning.localized_message().contains("ldap"));
+    }
+}
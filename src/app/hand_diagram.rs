@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! An interactive drawing of both hands for choosing a finger.
+//!
+//! Every finger is its own button, so the fingertips are the hit regions and
+//! hover, focus and keyboard activation come from the button widget.
+
+use crate::app::page::Page;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::{Alignment, Background, Color, Length};
+use cosmic::prelude::*;
+use cosmic::widget::{self, button, text, tooltip};
+use cosmic::{cosmic_theme, theme};
+
+const FINGER_WIDTH: f32 = 22.0;
+const PALM_HEIGHT: f32 = 56.0;
+const BORDER_WIDTH: f32 = 2.0;
+const SELECTED_BORDER_WIDTH: f32 = 4.0;
+const GLYPH_SIZE: u16 = 12;
+
+/// Enrollment state of a finger, shown by color and by a glyph so it reads
+/// without color too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FingerStatus {
+    Enrolled,
+    NotEnrolled,
+    /// Needs attention, such as an enrollment that did not finish.
+    Attention,
+}
+
+impl FingerStatus {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            FingerStatus::Enrolled => "✓",
+            FingerStatus::NotEnrolled => "○",
+            FingerStatus::Attention => "!",
+        }
+    }
+}
+
+/// Fingers of the left hand as seen from the back, with their heights.
+const LEFT_HAND: [(Page, f32); 5] = [
+    (Page::LeftPinky, 44.0),
+    (Page::LeftRing, 60.0),
+    (Page::LeftMiddle, 66.0),
+    (Page::LeftIndex, 60.0),
+    (Page::LeftThumb, 36.0),
+];
+
+/// Fingers of the right hand as seen from the back, with their heights.
+const RIGHT_HAND: [(Page, f32); 5] = [
+    (Page::RightThumb, 36.0),
+    (Page::RightIndex, 60.0),
+    (Page::RightMiddle, 66.0),
+    (Page::RightRing, 60.0),
+    (Page::RightPinky, 44.0),
+];
+
+/// Draws both hands, highlighting `selected` and marking each finger with its
+/// `status`. Clicking a finger emits `on_select`.
+pub fn view<'a, M: Clone + 'a>(
+    selected: Option<Page>,
+    status: impl Fn(Page) -> FingerStatus,
+    on_select: impl Fn(Page) -> M,
+) -> Element<'a, M> {
+    let cosmic_theme::Spacing {
+        space_xxs, space_xl, ..
+    } = theme::active().cosmic().spacing;
+
+    let hand = |fingers: [(Page, f32); 5]| {
+        let row = fingers
+            .into_iter()
+            .fold(widget::row().spacing(space_xxs), |row, (page, height)| {
+                let finger = finger(
+                    height,
+                    Some(page) == selected,
+                    status(page),
+                    on_select(page),
+                );
+                row.push(tooltip(
+                    finger,
+                    text::body(page.localized_name()),
+                    tooltip::Position::Top,
+                ))
+            })
+            .align_y(Vertical::Bottom);
+
+        // The palm spans the four fingers next to the thumb
+        let palm_width = 4.0 * FINGER_WIDTH + 3.0 * f32::from(space_xxs);
+        widget::column()
+            .push(row)
+            .push(
+                widget::Space::new(Length::Fixed(palm_width), Length::Fixed(PALM_HEIGHT))
+                    .apply(widget::container)
+                    .class(theme::Container::custom(|theme| {
+                        let cosmic = theme.cosmic();
+                        widget::container::Style {
+                            background: Some(Background::Color(
+                                cosmic.palette.neutral_3.into(),
+                            )),
+                            border: cosmic::iced::Border {
+                                radius: cosmic.corner_radii.radius_m.into(),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }
+                    })),
+            )
+            .spacing(space_xxs)
+    };
+
+    widget::row()
+        .push(hand(LEFT_HAND).align_x(Alignment::Start))
+        .push(hand(RIGHT_HAND).align_x(Alignment::End))
+        .spacing(space_xl)
+        .into()
+}
+
+fn finger<'a, M: Clone + 'a>(
+    height: f32,
+    selected: bool,
+    status: FingerStatus,
+    on_press: M,
+) -> Element<'a, M> {
+    let style = move |theme: &theme::Theme, hovered: bool| {
+        let cosmic = theme.cosmic();
+        let accent: Color = cosmic.accent_color().into();
+
+        let background = if status == FingerStatus::Enrolled {
+            accent
+        } else if status == FingerStatus::Attention {
+            cosmic.warning.base.into()
+        } else if hovered {
+            Color {
+                a: 0.3,
+                ..accent
+            }
+        } else {
+            cosmic.palette.neutral_3.into()
+        };
+
+        button::Style {
+            background: Some(Background::Color(background)),
+            border_radius: (FINGER_WIDTH / 2.0).into(),
+            border_width: if selected {
+                SELECTED_BORDER_WIDTH
+            } else {
+                BORDER_WIDTH
+            },
+            border_color: if selected || hovered {
+                accent
+            } else {
+                cosmic.palette.neutral_6.into()
+            },
+            ..button::Style::new()
+        }
+    };
+
+    let glyph_color: Color = {
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        match status {
+            FingerStatus::Enrolled => cosmic.accent.on,
+            FingerStatus::NotEnrolled => cosmic.background.on,
+            FingerStatus::Attention => cosmic.warning.on,
+        }
+        .into()
+    };
+
+    let glyph = text::body(status.glyph())
+        .size(GLYPH_SIZE)
+        .class(theme::Text::Color(glyph_color))
+        .apply(widget::container)
+        .width(Length::Fixed(FINGER_WIDTH))
+        .height(Length::Fixed(height))
+        .align_x(Horizontal::Center)
+        .padding([BORDER_WIDTH * 2.0, 0.0]);
+
+    button::custom(glyph)
+        .padding(0)
+        .class(theme::Button::Custom {
+            active: Box::new(move |_, theme| style(theme, false)),
+            disabled: Box::new(move |theme| style(theme, false)),
+            hovered: Box::new(move |_, theme| style(theme, true)),
+            pressed: Box::new(move |_, theme| style(theme, true)),
+        })
+        .on_press(on_press)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyphs_are_distinct() {
+        let glyphs = [
+            FingerStatus::Enrolled.glyph(),
+            FingerStatus::NotEnrolled.glyph(),
+            FingerStatus::Attention.glyph(),
+        ];
+        assert_ne!(glyphs[0], glyphs[1]);
+        assert_ne!(glyphs[1], glyphs[2]);
+        assert_ne!(glyphs[0], glyphs[2]);
+    }
+
+    #[test]
+    fn test_hands_cover_every_finger_once() {
+        let fingers: Vec<Page> = LEFT_HAND
+            .into_iter()
+            .chain(RIGHT_HAND)
+            .map(|(page, _)| page)
+            .collect();
+        assert_eq!(fingers.len(), 10);
+
+        for page in Page::all().iter().filter(|p| p.as_finger_id().is_some()) {
+            assert_eq!(fingers.iter().filter(|f| *f == page).count(), 1, "{page:?}");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A compact map of both hands showing the progress of a multi-finger flow.
+
+use crate::app::page::Page;
+use cosmic::iced::{Background, Border, Length};
+use cosmic::prelude::*;
+use cosmic::widget::{self, container};
+use cosmic::{cosmic_theme, theme};
+
+const DOT_SIZE: f32 = 12.0;
+
+/// Fingers of the left hand, from the little finger to the thumb.
+const LEFT_HAND: [Page; 5] = [
+    Page::LeftPinky,
+    Page::LeftRing,
+    Page::LeftMiddle,
+    Page::LeftIndex,
+    Page::LeftThumb,
+];
+
+/// Fingers of the right hand, from the thumb to the little finger.
+const RIGHT_HAND: [Page; 5] = [
+    Page::RightThumb,
+    Page::RightIndex,
+    Page::RightMiddle,
+    Page::RightRing,
+    Page::RightPinky,
+];
+
+/// How a finger is drawn on the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FingerMark {
+    /// Finished, drawn filled.
+    Done,
+    /// Being worked on, drawn pulsing.
+    Active,
+    /// Still to come, drawn outlined.
+    Pending,
+    /// Not part of the flow.
+    Unused,
+}
+
+/// Draws both hands side by side, marking each finger with `mark`.
+///
+/// `pulse` alternates the active finger between filled and outlined.
+pub fn view<'a, M: 'a>(mark: impl Fn(Page) -> FingerMark, pulse: bool) -> Element<'a, M> {
+    let cosmic_theme::Spacing {
+        space_xxs, space_l, ..
+    } = theme::active().cosmic().spacing;
+
+    let hand = |fingers: [Page; 5]| {
+        fingers
+            .into_iter()
+            .fold(widget::row().spacing(space_xxs), |row, page| {
+                row.push(finger_dot(mark(page), pulse))
+            })
+    };
+
+    widget::row()
+        .push(hand(LEFT_HAND))
+        .push(hand(RIGHT_HAND))
+        .spacing(space_l)
+        .into()
+}
+
+fn finger_dot<'a, M: 'a>(mark: FingerMark, pulse: bool) -> Element<'a, M> {
+    let filled = match mark {
+        FingerMark::Done => true,
+        FingerMark::Active => pulse,
+        FingerMark::Pending | FingerMark::Unused => false,
+    };
+
+    widget::Space::new(Length::Fixed(DOT_SIZE), Length::Fixed(DOT_SIZE))
+        .apply(widget::container)
+        .class(theme::Container::custom(move |theme| {
+            let cosmic = theme.cosmic();
+            let color = match mark {
+                FingerMark::Unused => cosmic.palette.neutral_5,
+                _ => cosmic.accent_color(),
+            };
+
+            container::Style {
+                background: filled.then(|| Background::Color(color.into())),
+                border: Border {
+                    color: color.into(),
+                    width: 1.5,
+                    radius: (DOT_SIZE / 2.0).into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hands_cover_every_finger_once() {
+        let fingers: Vec<Page> = LEFT_HAND.into_iter().chain(RIGHT_HAND).collect();
+        assert_eq!(fingers.len(), 10);
+
+        for page in Page::all().iter().filter(|p| p.as_finger_id().is_some()) {
+            assert_eq!(fingers.iter().filter(|f| *f == page).count(), 1, "{page:?}");
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::fl;
+
+/// Controls that have an inline "What is this?" popover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelpTopic {
+    UserPicker,
+    ClearDevice,
+    Progress,
+}
+
+impl HelpTopic {
+    pub fn all() -> &'static [Self] {
+        &[Self::UserPicker, Self::ClearDevice, Self::Progress]
+    }
+
+    pub fn localized_text(&self) -> String {
+        match self {
+            Self::UserPicker => fl!("help-user-picker"),
+            Self::ClearDevice => fl!("help-clear-device"),
+            Self::Progress => fl!("help-progress"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_topics_distinct() {
+        let texts: Vec<String> = HelpTopic::all().iter().map(HelpTopic::localized_text).collect();
+        for (i, text) in texts.iter().enumerate() {
+            assert!(!texts[..i].contains(text), "{:?} repeats a topic", HelpTopic::all()[i]);
+        }
+    }
+}
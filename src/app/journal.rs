@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A record of a single enrollment attempt, exported as JSON for bug reports.
+
+use crate::app::last_change::unix_now;
+use fprint_client::fprint::ScanType;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Signals asking for the finger to be presented again.
+const RETRY_SIGNALS: [&str; 4] = [
+    "enroll-retry-scan",
+    "enroll-swipe-too-short",
+    "enroll-finger-not-centered",
+    "enroll-remove-and-retry",
+];
+
+/// Every signal of one enrollment with its timing, from the start to the outcome.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnrollJournal {
+    pub app_version: String,
+    pub device: Option<String>,
+    pub scan_type: String,
+    pub username: String,
+    pub finger: String,
+    /// Seconds since the Unix epoch.
+    pub started_at: u64,
+    /// Scans fprintd asked for, if it said.
+    pub stages: Option<u32>,
+    pub retries: u32,
+    /// The final signal, or the error that ended the enrollment. None while running.
+    pub outcome: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub events: Vec<JournalEvent>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalEvent {
+    /// Milliseconds since the enrollment started.
+    pub elapsed_ms: u64,
+    pub signal: String,
+    pub done: bool,
+}
+
+impl EnrollJournal {
+    pub fn start(
+        device: Option<String>,
+        scan_type: ScanType,
+        username: &str,
+        finger: &str,
+    ) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            device,
+            scan_type: format!("{scan_type:?}").to_lowercase(),
+            username: username.to_string(),
+            finger: finger.to_string(),
+            started_at: unix_now(),
+            stages: None,
+            retries: 0,
+            outcome: None,
+            duration_ms: None,
+            events: Vec::new(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn set_stages(&mut self, stages: Option<u32>) {
+        self.stages = stages;
+    }
+
+    /// Adds a signal from fprintd, finishing the journal when it is the last one.
+    pub fn record(&mut self, signal: &str, done: bool) {
+        if self.is_finished() {
+            return;
+        }
+        let elapsed_ms = self.elapsed_ms();
+        self.events.push(JournalEvent {
+            elapsed_ms,
+            signal: signal.to_string(),
+            done,
+        });
+        if RETRY_SIGNALS.contains(&signal) {
+            self.retries += 1;
+        }
+        if done {
+            self.outcome = Some(signal.to_string());
+            self.duration_ms = Some(elapsed_ms);
+        }
+    }
+
+    /// Ends a running journal with `reason` when the enrollment stopped without a final signal.
+    pub fn fail(&mut self, reason: &str) {
+        self.record(&format!("error: {reason}"), true);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("fprint-enrollment-{}.json", self.started_at)
+    }
+}
+
+/// Folder exports go to: Downloads when there is one, the home folder otherwise.
+fn export_dir() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    let downloads = home.join("Downloads");
+    Some(if downloads.is_dir() { downloads } else { home })
+}
+
+/// Writes `journal` as JSON and returns where it was saved.
+pub async fn export(journal: EnrollJournal) -> Result<PathBuf, String> {
+    let path = export_dir()
+        .ok_or("HOME is not set")?
+        .join(journal.file_name());
+    let json = journal.to_json().map_err(|e| e.to_string())?;
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_until_done() {
+        let mut journal = EnrollJournal::start(None, ScanType::Swipe, "alice", "left-thumb");
+        journal.set_stages(Some(3));
+        journal.record("enroll-stage-passed", false);
+        journal.record("enroll-swipe-too-short", false);
+        journal.record("enroll-completed", true);
+        // Nothing is added once the enrollment is over
+        journal.fail("late");
+
+        assert_eq!(journal.events.len(), 3);
+        assert_eq!(journal.retries, 1);
+        assert_eq!(journal.outcome.as_deref(), Some("enroll-completed"));
+        assert!(journal.duration_ms.is_some());
+
+        let json = journal.to_json().unwrap();
+        assert!(json.contains("\"scan_type\": \"swipe\""));
+        assert!(json.contains("enroll-swipe-too-short"));
+    }
+
+    #[test]
+    fn test_fail() {
+        let mut journal = EnrollJournal::start(None, ScanType::Press, "alice", "left-thumb");
+        journal.fail("Timeout");
+        assert!(journal.is_finished());
+        assert_eq!(journal.outcome.as_deref(), Some("error: Timeout"));
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Describes the last change made to the prints of a user.
+
+use crate::app::finger_name::finger_id_to_localized;
+use crate::config::{ChangeKind, LastChange};
+use crate::fl;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+impl LastChange {
+    /// A change to `finger` that happened just now.
+    pub fn now(kind: ChangeKind, finger: Option<String>) -> Self {
+        Self {
+            kind,
+            finger,
+            timestamp: unix_now(),
+        }
+    }
+
+    /// The footer line, e.g. "Last change: enrolled right index finger, 2 days ago".
+    pub fn localized_summary(&self, now: u64) -> String {
+        let finger = self.finger.as_deref().map(finger_id_to_localized);
+
+        let action = match (self.kind, finger) {
+            (ChangeKind::Enrolled, Some(finger)) => fl!("last-change-enrolled", finger = finger),
+            (ChangeKind::Enrolled, None) => fl!("last-change-enrolled-any"),
+            (ChangeKind::Deleted, Some(finger)) => fl!("last-change-deleted", finger = finger),
+            (ChangeKind::Deleted, None) => fl!("last-change-deleted-all"),
+            (ChangeKind::Verified, Some(finger)) => fl!("last-change-verified", finger = finger),
+            (ChangeKind::Verified, None) => fl!("last-change-verified-any"),
+        };
+
+        fl!(
+            "last-change",
+            action = action,
+            when = localized_elapsed(now.saturating_sub(self.timestamp))
+        )
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// How long ago something happened, in the largest unit that fits.
+fn localized_elapsed(secs: u64) -> String {
+    if secs < MINUTE {
+        return fl!("last-change-just-now");
+    }
+
+    let (minutes, hours, days) = (secs / MINUTE, secs / HOUR, secs / DAY);
+    if secs < HOUR {
+        fl!("last-change-minutes", count = minutes)
+    } else if secs < DAY {
+        fl!("last-change-hours", count = hours)
+    } else {
+        fl!("last-change-days", count = days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::page::Page;
+
+    #[test]
+    fn test_summary() {
+        let change = LastChange {
+            kind: ChangeKind::Enrolled,
+            finger: Some("right-index-finger".to_string()),
+            timestamp: 1_000,
+        };
+        let summary = change.localized_summary(1_000 + 2 * DAY + HOUR);
+        assert!(summary.contains(&Page::RightIndex.localized_name()));
+        assert!(summary.contains('2'));
+
+        let cleared = LastChange {
+            kind: ChangeKind::Deleted,
+            finger: None,
+            timestamp: 1_000,
+        };
+        assert_ne!(cleared.localized_summary(1_010), change.localized_summary(1_010));
+    }
+
+    #[test]
+    fn test_elapsed_units() {
+        assert_eq!(localized_elapsed(5), fl!("last-change-just-now"));
+        assert_eq!(localized_elapsed(3 * MINUTE), fl!("last-change-minutes", count = 3));
+        assert_eq!(localized_elapsed(DAY - 1), fl!("last-change-hours", count = 23));
+        assert_eq!(localized_elapsed(DAY), fl!("last-change-days", count = 1));
+    }
+}
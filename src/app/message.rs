@@ -1,10 +1,22 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::config::Config;
-use crate::app::page::ContextPage;
+use crate::fl;
+use crate::app::page::{ContextPage, Page};
+use crate::app::cleanup::StoredPrint;
+use crate::app::NavMenuAction;
+use std::path::PathBuf;
 use std::sync::Arc;
-use crate::app::error::AppError;
-use crate::fprint_dbus::DeviceProxy;
+use crate::app::error::{AppError, ErrorAction};
+use crate::app::help::HelpTopic;
+use crate::app::fprintd_conf::FprintdConf;
+use crate::app::open_target::OpenTarget;
+use crate::app::operator_log::OperatorEntry;
+use crate::app::pam::PamStatus;
+use crate::app::remote::SessionLocation;
+use fprint_client::fprint::{
+    Capabilities, ClearReport, DeviceCaps, DeviceInfo, Event, ScanType,
+};
 
 /// Messages emitted by the application and its widgets.
 #[derive(Debug, Clone)]
@@ -16,24 +28,231 @@ pub enum Message {
     Delete,
     Register,
     ConnectionReady(zbus::Connection),
-    DeviceFound(Option<(zbus::zvariant::OwnedObjectPath, DeviceProxy<'static>)>),
+    ConnectionLost,
+    ServiceChanged(bool),
+    ReconnectTick,
+    ReconnectFailed(String),
+    DevicesFound(Vec<DeviceOption>),
+    DeviceSelected(DeviceOption),
+    RescanDevices,
+    PulseTick,
+    /// The main window gained or lost focus.
+    WindowFocused(bool),
+    /// The main window was resized, to its new width and height.
+    WindowResized(u32, u32),
+    /// Saves the window size unless it was resized again since, by count.
+    SaveWindowSize(u64),
+    /// Battery saver was switched on or off.
+    PowerSaverChanged(bool),
+    /// The session bus connection serving the enrollment progress signal.
+    ProgressBusReady(zbus::Connection),
+    /// Whether the app runs at the reader's machine or away from it.
+    SessionLocated(SessionLocation),
+    FocusStatus(bool),
+    SelectFinger(Page),
+    Authenticate,
+    AuthenticationComplete(Result<bool, AppError>),
+    ManageOthersChecked(bool),
+    /// What the fprintd serving the selected reader offers.
+    CapabilitiesProbed(Capabilities),
+    StatusOlder,
+    StatusNewer,
     OperationError(AppError),
+    ErrorAction(ErrorAction),
+    StartDaemonComplete(Result<(), String>),
+    DismissError,
+    ToggleErrorDetails,
     EnrollStart(Option<u32>),
     EnrollStatus(String, bool),
     EnrollStop,
+    /// The window was closed while the reader is scanning.
+    Exit,
+    /// The scan was stopped and the reader released, so the app can exit.
+    ExitReady,
+    ExportJournal,
+    CopyActivityLog,
+    ExportDebugLog,
+    DebugLogExported(Result<String, String>),
+    JournalExported(Result<std::path::PathBuf, String>),
     DeleteComplete,
     ClearDevice,
-    CancelClear,
-    ClearComplete(Result<(), AppError>),
-    EnrolledFingers(Vec<String>),
+    ClearPreview(Vec<(String, Vec<String>)>),
+    /// Whether polkit allowed clearing the device, when that is required.
+    ClearAuthorized(Result<bool, AppError>),
+    OverviewLoaded(Vec<(String, Vec<String>)>),
+    ClearUserDone(String),
+    ClearCancel,
+    ClearComplete(Result<ClearReport<AppError>, AppError>),
+    ClearResume,
+    ClearDismissResume,
+    RefreshFingers,
+    /// The enrolled fingers of the user they were listed for.
+    EnrolledFingers(String, Vec<String>),
     UsersFound(Vec<UserOption>),
+    /// AccountsService created or deleted an account.
+    UsersChanged,
+    /// fprintd added or removed a reader.
+    DevicesChanged,
     UserSelected(UserOption),
+    /// A user was dragged onto the window, `None` if the payload wasn't a username.
+    UserDropped(Option<String>),
+    /// Show a page or user, as asked on the command line or over DBus.
+    Open(OpenTarget),
+    Verify,
+    VerifyStatus(String, bool),
+    /// The finger fprintd compares the scan against.
+    VerifyFingerSelected(String),
+    /// Scans a finger to find out which of the user's prints it matches.
+    Identify,
+    VerifyStop,
+    ReplaceStart,
+    ReplaceFingerSelected(usize),
+    ReplaceContinue,
+    ReplaceVerify,
+    ReplaceDeleteOld,
+    ReplaceKeepOld,
+    ReplaceCancel,
+    CleanupPrints(Vec<(String, Vec<String>)>),
+    CleanupToggle(usize, bool),
+    CleanupDelete,
+    /// Whether polkit allowed the cleanup to delete prints of other users.
+    CleanupAuthorized(Result<bool, AppError>),
+    CleanupDeleted(Vec<StoredPrint>, Option<AppError>),
+    CleanupCancel,
+    NavMenu(NavMenuAction),
+    RenameInput(String),
+    RenameSave,
+    RenameCancel,
+    ReportSent(Result<(), String>),
+    OperatorLogWritten(Result<(), String>),
+    OperatorLogLoaded(Result<Vec<OperatorEntry>, String>),
+    WizardStart,
+    WizardNext,
+    WizardBack,
+    WizardVerify,
+    WizardAnother,
+    WizardClose,
+    /// Opens the panel to enroll several fingers one after another.
+    BatchOpen,
+    BatchToggle(usize, bool),
+    BatchStart,
+    /// Enrolls the next finger of the batch.
+    BatchNext,
+    /// Stops the batch after cancelling the finger being enrolled.
+    BatchStop,
+    BatchClose,
+    /// Starts scanning over and over on the self-test page.
+    SelfTestStart,
+    /// Takes the next scan of the self-test.
+    SelfTestNext,
+    SelfTestStop,
+    ToggleHelp(HelpTopic),
+    CloseHelp,
+    ToggleUserMenu,
+    CloseUserMenu,
+    RecoveryRetry,
+    RecoveryDismiss,
+    FprintdConfLoaded(Option<FprintdConf>),
+    SuppressDualBootWarning(bool),
+    ConfirmAction,
+    CancelConfirm,
+    AcknowledgeRemote(bool),
+    SetAppTheme(usize),
+    SetDefaultUser(usize),
+    SetShowAllUsers(bool),
+    SetShowSystemUsers(bool),
+    SetConfirmBeforeDelete(bool),
+    SetRequireVerifyBeforeDelete(bool),
+    SetRequireAuthBeforeClear(bool),
+    SetLockDevice(bool),
+    SetIdleRelease(usize),
+    SetOperatorMode(bool),
+    SetNotifyInBackground(bool),
+    ShowAllFingers,
+    SetDebugLog(bool),
+    OpenAccountSettings,
+    AccountSettingsFailed(String),
+    DuplicateCheckComplete(String, Vec<String>),
+    EnrollAnyway,
+    DuplicateDismiss,
+    ClaimChanged(bool),
+    ClaimRetryTick,
+    ClaimRetryNow,
+    ClaimRetryPause(bool),
+    /// Stops waiting for the reader to run the operation that found it in use.
+    DeviceWaitCancel,
+    ClaimProbed(Result<(), AppError>),
+    IdleTick,
+    ConflictsFound(Vec<String>),
+    PamStatusLoaded(Option<PamStatus>, bool),
+    EnablePam,
+    EnablePamComplete(Result<(), String>),
+    DismissPamBanner,
+    DismissInUseGuidance,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UserOption {
     pub username: Arc<String>,
     pub realname: Arc<String>,
+    /// A system or service account rather than a person's.
+    pub system: bool,
+    /// Picture chosen for the account, if it has one.
+    pub avatar: Option<Arc<PathBuf>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeviceOption {
+    pub path: Arc<zbus::zvariant::OwnedObjectPath>,
+    pub name: Arc<String>,
+    pub scan_type: ScanType,
+    pub stages: Option<u32>,
+    pub caps: DeviceCaps,
+}
+
+impl PartialEq for DeviceOption {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for DeviceOption {}
+
+impl From<DeviceInfo> for DeviceOption {
+    fn from(device: DeviceInfo) -> Self {
+        Self {
+            path: Arc::new(device.path),
+            name: Arc::new(device.name),
+            scan_type: device.scan_type,
+            stages: device.stages,
+            caps: device.caps,
+        }
+    }
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::EnrollStart(stages) => Message::EnrollStart(stages),
+            Event::EnrollStatus(result, done) => Message::EnrollStatus(result, done),
+            Event::VerifyStatus(result, done) => Message::VerifyStatus(result, done),
+            Event::VerifyFingerSelected(finger) => Message::VerifyFingerSelected(finger),
+            Event::UserCleared(username) => Message::ClearUserDone(username),
+            Event::InvalidSignal => {
+                Message::OperationError(AppError::Unknown(fl!("error-invalid-signal")))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}", self.path.as_str())
+        } else {
+            write!(f, "{}", self.name)
+        }
+    }
 }
 
 impl std::fmt::Display for UserOption {
@@ -56,6 +275,8 @@ mod tests {
         let user_option = UserOption {
             username: Arc::new("jdoe".to_string()),
             realname: Arc::new("John Doe".to_string()),
+            system: false,
+            avatar: None,
         };
         assert_eq!(user_option.to_string(), "John Doe (jdoe)");
     }
@@ -65,6 +286,8 @@ mod tests {
         let user_option = UserOption {
             username: Arc::new("jdoe".to_string()),
             realname: Arc::new("".to_string()),
+            system: false,
+            avatar: None,
         };
         assert_eq!(user_option.to_string(), "jdoe");
     }
@@ -74,6 +297,8 @@ mod tests {
         let user_option = UserOption {
             username: Arc::new("jdoe".to_string()),
             realname: Arc::new("   ".to_string()),
+            system: false,
+            avatar: None,
         };
         assert_eq!(user_option.to_string(), "    (jdoe)");
     }
@@ -83,6 +308,8 @@ mod tests {
         let user_option = UserOption {
             username: Arc::new("".to_string()),
             realname: Arc::new("John Doe".to_string()),
+            system: false,
+            avatar: None,
         };
         assert_eq!(user_option.to_string(), "John Doe ()");
     }
@@ -92,7 +319,25 @@ mod tests {
         let user_option = UserOption {
             username: Arc::new("".to_string()),
             realname: Arc::new("".to_string()),
+            system: false,
+            avatar: None,
         };
         assert_eq!(user_option.to_string(), "");
     }
+
+    #[test]
+    fn test_message_from_event() {
+        assert!(matches!(
+            Message::from(Event::EnrollStatus("enroll-completed".to_string(), true)),
+            Message::EnrollStatus(result, true) if result == "enroll-completed"
+        ));
+        assert!(matches!(
+            Message::from(Event::UserCleared("jdoe".to_string())),
+            Message::ClearUserDone(username) if username == "jdoe"
+        ));
+        assert!(matches!(
+            Message::from(Event::InvalidSignal),
+            Message::OperationError(AppError::Unknown(_))
+        ));
+    }
 }
@@ -1,46 +1,159 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::accounts_dbus::{AccountsProxy, UserProxy};
-use crate::config::Config;
+use crate::changelog;
+use crate::config::{AppTheme, ChangeKind, Config, EnrollmentMarker, LastChange, State};
+use crate::debug_log;
 use crate::fl;
-use crate::fprint_dbus::DeviceProxy;
+use crate::i18n;
 use cosmic::app::context_drawer;
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::clipboard::mime::AllowedMimeTypes;
+use cosmic::iced::keyboard::{self, Key, key::Named};
 use cosmic::iced::widget::pick_list;
+use cosmic::iced::widget::scrollable::{Direction, Scrollbar};
 use cosmic::iced::{Alignment, Length, Subscription};
 use cosmic::prelude::*;
 use cosmic::widget::{self, icon, menu, nav_bar, text, dialog};
 use cosmic::{cosmic_theme, theme};
-use futures_util::stream::{self, StreamExt};
-use futures_util::SinkExt;
+use futures_util::{SinkExt, StreamExt};
 use nix::unistd::{Uid, User};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 pub mod page;
+pub mod backend;
+pub mod confirm;
+pub mod demo;
 pub mod message;
-pub mod fprint;
 pub mod error;
+#[cfg(test)]
+pub mod fake_accounts;
+pub mod fetches;
+pub mod finger_name;
+pub mod replace;
+pub mod cleanup;
+pub mod enroll_wizard;
+pub mod enrollment;
+pub mod batch_enroll;
+pub mod help;
+pub mod fprintd_conf;
+pub mod conflicts;
+pub mod claim_retry;
+pub mod hand_diagram;
+pub mod hand_map;
+pub mod journal;
+pub mod last_change;
+pub mod notification;
+pub mod open_target;
+pub mod operator_log;
+pub mod status;
+pub mod status_text;
+pub mod users;
+pub mod pam;
+pub mod progress_signal;
+pub mod reconnect;
+pub mod remote;
+pub mod report;
+pub mod self_test;
+pub mod stage_progress;
 
-use page::{ContextPage, Page};
-use message::{Message, UserOption};
-use fprint::{
-    delete_fingerprint_dbus, delete_fingers, enroll_fingerprint_process, find_device,
-    clear_all_fingers_dbus,list_enrolled_fingers_dbus,
+use page::{ContextPage, NavSection, Page};
+use message::{DeviceOption, Message, UserOption};
+use backend::{FprintBackend, ZbusBackend};
+use enrollment::Enrollment;
+use fetches::Fetches;
+use finger_name::finger_id_to_localized;
+use notification::Notification;
+use open_target::OpenTarget;
+use self_test::{ScanResult, SelfTest};
+use confirm::{Confirmation, clear_impact};
+use demo::DemoBackend;
+use fprint_client::fprint::{
+    Capabilities, DeviceCaps, ScanType, TemplateStorage, restart_fprintd,
 };
-use error::AppError;
+use fprint_client::session;
+use error::{AppError, ErrorAction};
+use replace::{ReplaceStep, ReplaceWizard};
+use cleanup::{StorageCleanup, StoredPrint};
+use enroll_wizard::{EnrollWizard, WizardStep};
+use batch_enroll::{BatchEnroll, BatchOutcome};
+use help::HelpTopic;
+use hand_diagram::FingerStatus;
+use conflicts::{InUseTracker, find_conflicting_services};
+use claim_retry::ClaimRetry;
+use journal::EnrollJournal;
+use reconnect::Reconnect;
+use remote::SessionLocation;
+use operator_log::{OperatorAction, OperatorEntry, date_time};
+use report::{OperationReport, ReportHook};
+use stage_progress::RetryReason;
+use fprintd_conf::{FprintdConf, FPRINTD_CONF_PATH, read_fprintd_conf};
+use status::{StatusHistory, clock};
+use status_text::{StatusContext, enroll_message, verify_message};
+use users::parse_dropped_user;
+use pam::{PamStatus, enable_command, enable_fingerprint_auth, read_pam_status};
 
 const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+/// Supported readers and answers to common fprint problems.
+const TROUBLESHOOTING_URL: &str = "https://fprint.freedesktop.org/";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
 const APP_ICON: &[u8] = include_bytes!("../../resources/icons/hicolor/scalable/apps/icon.svg");
 const FPRINT_ICON: &[u8] = include_bytes!("../../resources/icons/hicolor/scalable/apps/fprint.svg");
 
+pub const WINDOW_MIN_WIDTH: f32 = 400.0;
+pub const WINDOW_MIN_HEIGHT: f32 = 380.0;
+/// How long the window size has to stay put before it is saved, so dragging
+/// a window edge doesn't write the config on every step.
+const WINDOW_SIZE_SAVE_DELAY: Duration = Duration::from_millis(500);
 const STATUS_TEXT_SIZE: u16 = 16;
-const PROGRESS_BAR_HEIGHT: u16 = 10;
+const HELP_POPUP_WIDTH: f32 = 280.0;
+const PICKER_WIDTH: f32 = 200.0;
+const USER_MENU_MAX_HEIGHT: f32 = 320.0;
+const AVATAR_SIZE: u16 = 24;
 const MAIN_SPACING: u16 = 20;
 const MAIN_PADDING: u16 = 20;
 
-const USER_FETCH_CONCURRENCY: usize = 10;
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Inactivity timeouts offered in the settings, in seconds. Zero never stops.
+const IDLE_RELEASE_CHOICES: [u64; 5] = [0, 60, 120, 300, 600];
+/// Also refreshes how long the reader has been unavailable.
+const CLAIM_RETRY_TICK: Duration = Duration::from_secs(1);
+/// fprintd only reports added readers while it runs, and exits when idle,
+/// so poll while none is present, which also starts it again.
+const HOTPLUG_POLL_INTERVAL: Duration = Duration::from_secs(3);
+const PULSE_INTERVAL: Duration = Duration::from_millis(600);
+/// Slower pulse while nobody is looking or battery saver is on.
+const PULSE_INTERVAL_LOW_POWER: Duration = Duration::from_millis(1500);
+/// How often a lost connection checks whether its next attempt is due.
+const RECONNECT_TICK: Duration = Duration::from_secs(1);
+/// fprintd doesn't signal changed prints, so reload them to catch edits made elsewhere.
+const FINGERS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+/// Pause between the fingers of a batch enrollment, to lift the last one.
+const BATCH_PAUSE: Duration = Duration::from_millis(1500);
+/// Pause between the scans of the self-test, to lift the finger.
+const SELF_TEST_PAUSE: Duration = Duration::from_secs(1);
+/// Longest a closed window waits for fprintd to give the reader back.
+const EXIT_RELEASE_TIMEOUT: Duration = Duration::from_secs(3);
+
+const NAV_ICON: &str = "applications-utilities-symbolic";
+/// Marks fingers that already have a print in the nav bar.
+const NAV_ENROLLED_ICON: &str = "object-select-symbolic";
+/// Marks fingers whose enrollment did not finish.
+const NAV_ATTENTION_ICON: &str = "dialog-warning-symbolic";
+const NAV_OVERVIEW_ICON: &str = "view-grid-symbolic";
+const NAV_TEST_ICON: &str = "emblem-ok-symbolic";
+/// Width of a finger column in the overview table.
+const OVERVIEW_CELL_WIDTH: f32 = 72.0;
+const BATCH_WIDTH: f32 = 360.0;
+const OVERVIEW_USER_WIDTH: f32 = 160.0;
+
+/// COSMIC Settings executable and the page listing user accounts.
+const SETTINGS_COMMAND: &str = "cosmic-settings";
+const SETTINGS_USERS_PAGE: &str = "users";
 
 /// The application model stores app-specific state used to describe its interface and
 /// drive its logic.
@@ -55,29 +168,221 @@ pub struct AppModel {
     key_binds: HashMap<menu::KeyBind, MenuAction>,
     // Configuration data that persists between application runs.
     config: Config,
+    // Handle for writing configuration changes
+    config_handler: Option<cosmic_config::Config>,
     // Status text for the UI
     status: String,
+    // Recent status messages
+    status_history: StatusHistory,
+    // When the running enrollment, deletion or clear started
+    operation_started: Option<Instant>,
+    // Name of the account running the app, looked up once as NSS may be slow
+    current_username: Option<String>,
+    // Whether polkit allowed managing the prints of other users
+    authorized_for_others: bool,
+    // Whether polkit would allow it at all, hiding other users when not
+    may_manage_others: bool,
+    // What the running fprintd offers, probed for each reader
+    capabilities: Capabilities,
+    // Whether the last operation was refused, offering to authenticate
+    permission_denied: bool,
+    // Whether arrow keys browse the status history
+    status_focused: bool,
+    // Readers known to fprintd
+    devices: Vec<DeviceOption>,
     // Currently selected device path
     device_path: Option<Arc<zbus::zvariant::OwnedObjectPath>>,
-    // Reused device proxy
-    device_proxy: Option<DeviceProxy<'static>>,
-    // Shared DBus connection
-    connection: Option<zbus::Connection>,
+    // Selected device and what it can do
+    device: Option<DeviceOption>,
+    // Storage settings of fprintd (None if unreadable)
+    fprintd_conf: Option<FprintdConf>,
+    // fprintd, or a stand-in for it
+    backend: Option<Arc<dyn FprintBackend>>,
     // Whether an operation is in progress
     busy: bool,
-    // Finger currently being enrolled (None if not enrolling)
-    enrolling_finger: Option<Arc<String>>,
+    // Whether the window was closed and the app exits once the reader is released
+    exiting: bool,
+    // Where the enrollment is, from claiming the reader to releasing it
+    enrollment: Enrollment,
     // Enrollment progress
     enroll_progress: u32,
     enroll_total_stages: Option<u32>,
+    // Why the current scan has to be tried again, if it does
+    enroll_retry: Option<RetryReason>,
+    // Session bus connection serving the enrollment progress signal and
+    // sending notifications
+    progress_bus: Option<zbus::Connection>,
     // List of users (username, realname)
     users: Vec<UserOption>,
+    // Users offered in the picker, depending on the show all users setting
+    shown_users: Vec<UserOption>,
+    // Whether the configured default user was considered since startup
+    default_user_applied: bool,
+    // Whether the reader used last was considered since startup
+    last_device_applied: bool,
+    // User to select once the users are known, as asked at startup
+    open_user: Option<String>,
+    // Tally of the self-test page
+    self_test: SelfTest,
+    // Whether the configuration has been loaded
+    config_loaded: bool,
+    // Names shown in the settings dropdowns
+    theme_names: Vec<String>,
+    default_user_names: Vec<String>,
+    idle_release_names: Vec<String>,
     // Selected user
     selected_user: Option<UserOption>,
+    // Whether the list of the user picker is open
+    user_menu_open: bool,
     // List of enrolled fingers
     enrolled_fingers: Vec<String>,
-    // Confirmation state for clearing the device
-    confirm_clear: bool,
+    // Status of every page the nav bar icons currently show
+    nav_badges: Vec<FingerStatus>,
+    // Pages the nav bar currently lists
+    nav_pages: Vec<Page>,
+    // Destructive action the confirmation dialog asks about
+    confirmation: Option<Confirmation>,
+    // Enrolled fingers of every listed user for the overview, `None` while loading
+    overview: Option<Vec<(String, Vec<String>)>>,
+    // Fingers of each user that clearing the device removes, `None` while loading
+    clear_preview: Option<Vec<(String, Vec<String>)>>,
+    // Asks a running device clear to stop before the next user
+    clear_cancel: Option<Arc<AtomicBool>>,
+    // Listings still running, aborted once a newer one makes them stale
+    fetches: Fetches,
+    // Whether "don't show again" is ticked for the dual-boot warning
+    suppress_dual_boot_warning: bool,
+    // Finger currently being verified (None if not verifying)
+    verifying_finger: Option<Arc<String>>,
+    // Page whose prints are deleted once verification succeeds
+    pending_delete: Option<Page>,
+    // Prints the cleanup deletes once polkit agrees or a finger matched
+    pending_cleanup: Option<Vec<StoredPrint>>,
+    // The clear asked about once polkit gave administrator rights
+    pending_clear: Option<Confirmation>,
+    // Whether the running verification identifies the finger, and the finger
+    // fprintd picked to compare against if it said
+    identifying: Option<Option<String>>,
+    // Whether the active finger on the hand map is currently drawn filled
+    pulse: bool,
+    // Whether the main window has focus, to slow down animations otherwise
+    window_focused: bool,
+    // Last size of the main window, saved once it stops changing
+    window_size: (u32, u32),
+    // Resizes so far, telling a pending save whether it is the latest
+    window_resizes: u64,
+    // Whether battery saver is on
+    power_saver: bool,
+    // Whether the app runs away from the reader, which destructive actions warn about
+    session_location: SessionLocation,
+    // Whether the user ticked that they mean to act from a remote session
+    remote_acknowledged: bool,
+    // State of the replace finger wizard (None if not replacing)
+    replace: Option<ReplaceWizard>,
+    // Prints offered for deletion after the reader ran out of room
+    cleanup: Option<StorageCleanup>,
+    // State of the guided enrollment wizard (None if not shown)
+    enroll_wizard: Option<EnrollWizard>,
+    // Whether the wizard was already offered for a user without prints
+    wizard_offered: bool,
+    // Fingers picked to enroll one after another (None if not shown)
+    batch: Option<BatchEnroll>,
+    // Help popover currently open
+    help: Option<HelpTopic>,
+    // Finger being renamed and the name typed so far
+    rename: Option<(Page, String)>,
+    // Completed changes not yet handed to the report hook
+    pending_reports: Vec<OperationReport>,
+    // Action on prints in progress, logged once it ends in operator mode
+    operator_action: Option<OperatorEntry>,
+    // Finished actions not yet appended to the operator log
+    pending_operator_entries: Vec<OperatorEntry>,
+    // Operator log shown on its page, None while it is read
+    operator_entries: Option<Result<Vec<OperatorEntry>, String>>,
+    // Handle for writing persisted state
+    state_handler: Option<cosmic_config::Config>,
+    // State persisted between runs
+    state: State,
+    // Interrupted enrollment offered for retry on startup
+    recovery: Option<EnrollmentMarker>,
+    // Other users that already have the finger about to be enrolled
+    duplicate_conflict: Option<DuplicateConflict>,
+    // Whether this app currently holds a claim on the reader
+    claimed: bool,
+    // Last progress of an enrollment or verification
+    last_activity: Instant,
+    // Whether the running operation was stopped for being idle
+    idle_timed_out: bool,
+    // Recent failures caused by another program holding the reader
+    in_use: InUseTracker,
+    // Guidance about services competing for the reader, with any that were found
+    in_use_guidance: Option<Vec<String>>,
+    // Retries claiming the reader while another program holds it
+    claim_retry: Option<ClaimRetry>,
+    // Operation to run again once the reader is free, and when to stop waiting
+    device_wait: Option<(Message, Instant)>,
+    // Trying to reach fprintd again after the bus or the service went away
+    reconnect: Option<Reconnect>,
+    // Bumped for every new backend, so its service watch starts over
+    backend_generation: u64,
+    // Signals and timings of the running or last enrollment
+    enroll_journal: Option<EnrollJournal>,
+    // The last failure with the message that runs the failed operation again
+    last_error: Option<(AppError, Message)>,
+    // Whether the DBus error behind the last failure is shown
+    error_details_open: bool,
+    // Whether login and sudo consult fprintd, None until read
+    pam_status: Option<PamStatus>,
+    // Whether fingerprint authentication can be enabled from the app
+    pam_can_enable: bool,
+    pam_banner_dismissed: bool,
+}
+
+/// A finger about to be enrolled that the sensor already stores for other users.
+pub struct DuplicateConflict {
+    finger: String,
+    users: Vec<UserOption>,
+}
+
+/// A user dragged onto the window, e.g. from the Users page of COSMIC Settings.
+#[derive(Clone, Debug)]
+struct DroppedUser(String);
+
+impl AllowedMimeTypes for DroppedUser {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![
+            "text/plain;charset=utf-8".to_string(),
+            "text/plain".to_string(),
+            "text/uri-list".to_string(),
+        ])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for DroppedUser {
+    type Error = ();
+
+    fn try_from((data, _mime): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        let data = String::from_utf8(data).map_err(|_| ())?;
+        parse_dropped_user(&data).map(Self).ok_or(())
+    }
+}
+
+/// Options the app is started with.
+#[derive(Clone, Debug, Default)]
+pub struct Flags {
+    /// Use a simulated reader instead of fprintd.
+    pub demo: bool,
+    /// Page and user to show, sent to the instance already running if there is one.
+    pub open: Option<OpenTarget>,
+}
+
+impl cosmic::app::CosmicFlags for Flags {
+    type SubCommand = OpenTarget;
+    type Args = Vec<String>;
+
+    fn action(&self) -> Option<&OpenTarget> {
+        self.open.as_ref()
+    }
 }
 
 /// Create a COSMIC application from the app model
@@ -86,7 +391,7 @@ impl cosmic::Application for AppModel {
     type Executor = cosmic::executor::Default;
 
     /// Data that your application receives to its init method.
-    type Flags = ();
+    type Flags = Flags;
 
     /// Messages which the application and its widgets will emit.
     type Message = Message;
@@ -105,17 +410,27 @@ impl cosmic::Application for AppModel {
     /// Initializes the application with any given flags and startup commands.
     fn init(
         core: cosmic::Core,
-        _flags: Self::Flags,
+        flags: Self::Flags,
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
         // Create a nav bar for every fingerprint
-        let mut nav = nav_bar::Model::default();
+        let nav = build_nav(Page::all(), None);
 
-        for page in Page::all() {
-            nav.insert()
-                .text(page.localized_name())
-                .data::<Page>(*page)
-                .icon(icon::from_name("applications-utilities-symbolic"));
-        }
+        let state_handler = cosmic_config::Config::new_state(Self::APP_ID, State::VERSION).ok();
+        let state = state_handler
+            .as_ref()
+            .map(|context| match State::get_entry(context) {
+                Ok(state) => state,
+                Err((errors, state)) => {
+                    for why in errors {
+                        tracing::error!(%why, "error loading app state");
+                    }
+
+                    state
+                }
+            })
+            .unwrap_or_default();
+
+        let current_user = User::from_uid(Uid::current()).ok().flatten();
 
         // Construct the app model with the runtime's core.
         let mut app = AppModel {
@@ -125,39 +440,133 @@ impl cosmic::Application for AppModel {
             key_binds: HashMap::new(),
             // Optional configuration file for an application.
             config: Config::default(),
+            config_handler: cosmic_config::Config::new(Self::APP_ID, Config::VERSION).ok(),
             status: fl!("status-connecting"),
+            status_history: StatusHistory::default(),
+            operation_started: None,
+            current_username: current_user.as_ref().map(|u| u.name.clone()),
+            authorized_for_others: false,
+            may_manage_others: true,
+            capabilities: Capabilities::default(),
+            permission_denied: false,
+            status_focused: false,
+            devices: Vec::new(),
             device_path: None,
-            device_proxy: None,
-            connection: None,
+            device: None,
+            fprintd_conf: None,
+            backend: None,
             busy: true,
-            enrolling_finger: None,
+            exiting: false,
+            enrollment: Enrollment::Idle,
             enroll_progress: 0,
             enroll_total_stages: None,
+            enroll_retry: None,
+            progress_bus: None,
             users: Vec::new(),
-            selected_user: User::from_uid(Uid::current())
-                .ok()
-                .flatten()
-                .map(|u| UserOption {
-                    username: Arc::new(u.name),
-                    realname: Arc::new(u.gecos.to_string_lossy().into_owned()),
-                }),
+            shown_users: Vec::new(),
+            user_menu_open: false,
+            default_user_applied: false,
+            last_device_applied: false,
+            open_user: None,
+            self_test: SelfTest::default(),
+            config_loaded: false,
+            theme_names: AppTheme::all()
+                .iter()
+                .map(|theme| match theme {
+                    AppTheme::System => fl!("settings-theme-system"),
+                    AppTheme::Dark => fl!("settings-theme-dark"),
+                    AppTheme::Light => fl!("settings-theme-light"),
+                })
+                .collect(),
+            default_user_names: vec![fl!("settings-default-user-current")],
+            idle_release_names: IDLE_RELEASE_CHOICES
+                .iter()
+                .map(|&secs| match secs / 60 {
+                    0 => fl!("settings-idle-release-never"),
+                    mins => fl!("settings-idle-release-minutes", mins = mins),
+                })
+                .collect(),
+            selected_user: current_user.map(|u| UserOption {
+                username: Arc::new(u.name),
+                realname: Arc::new(u.gecos.to_string_lossy().into_owned()),
+                system: false,
+                avatar: None,
+            }),
             enrolled_fingers: Vec::new(),
-            confirm_clear: false,
+            nav_badges: Vec::new(),
+            nav_pages: Page::all().to_vec(),
+            confirmation: None,
+            overview: None,
+            clear_preview: None,
+            clear_cancel: None,
+            fetches: Fetches::default(),
+            suppress_dual_boot_warning: false,
+            verifying_finger: None,
+            pending_delete: None,
+            pending_cleanup: None,
+            pending_clear: None,
+            identifying: None,
+            pulse: false,
+            window_focused: true,
+            window_size: (0, 0),
+            window_resizes: 0,
+            power_saver: false,
+            session_location: SessionLocation::Local,
+            remote_acknowledged: false,
+            replace: None,
+            cleanup: None,
+            enroll_wizard: None,
+            wizard_offered: false,
+            batch: None,
+            help: None,
+            rename: None,
+            pending_reports: Vec::new(),
+            operator_action: None,
+            pending_operator_entries: Vec::new(),
+            operator_entries: None,
+            state_handler,
+            state,
+            recovery: None,
+            duplicate_conflict: None,
+            claimed: session::is_claimed(),
+            last_activity: Instant::now(),
+            idle_timed_out: false,
+            in_use: InUseTracker::default(),
+            in_use_guidance: None,
+            claim_retry: None,
+            device_wait: None,
+            reconnect: None,
+            backend_generation: 0,
+            enroll_journal: None,
+            last_error: None,
+            error_details_open: false,
+            pam_status: None,
+            pam_can_enable: false,
+            pam_banner_dismissed: false,
         };
 
+        // Offer to retry an enrollment that was interrupted by a crash or exit
+        if let Some(marker) = app.state.interrupted_enrollment.clone() {
+            app.restore_interrupted_enrollment(marker);
+        }
+
         // Create a startup command that sets the window title.
         let command = app.update_title();
 
-        // Start async task to connect to DBus
-        let connect_task = Task::perform(
-            async move {
-                match zbus::Connection::system().await {
-                    Ok(conn) => Message::ConnectionReady(conn),
-                    Err(e) => Message::OperationError(AppError::ConnectDbus(e.to_string())),
-                }
-            },
-            cosmic::Action::App,
-        );
+        // Start async task to connect to DBus, unless the reader is simulated
+        let connect_task = if flags.demo {
+            app.set_backend(Arc::new(DemoBackend::new()))
+        } else {
+            Task::perform(
+                async move {
+                    match zbus::Connection::system().await {
+                        Ok(conn) => Message::ConnectionReady(conn),
+                        Err(e) => Message::OperationError(AppError::ConnectDbus(e.to_string())),
+                    }
+                },
+                cosmic::Action::App,
+            )
+        };
 
         let config_task = Task::perform(
             async move {
@@ -186,27 +595,112 @@ impl cosmic::Application for AppModel {
             cosmic::Action::App,
         );
 
-        (app, Task::batch(vec![command, connect_task, config_task]))
+        let fprintd_conf_task = Task::perform(
+            async move { Message::FprintdConfLoaded(read_fprintd_conf().await) },
+            cosmic::Action::App,
+        );
+
+        let open_task = flags.open.map_or_else(Task::none, |target| {
+            Task::done(cosmic::Action::App(Message::Open(target)))
+        });
+
+        (
+            app,
+            Task::batch(vec![
+                command,
+                connect_task,
+                config_task,
+                fprintd_conf_task,
+                pam_status_task(),
+                progress_bus_task(),
+                open_task,
+            ]),
+        )
     }
 
     /// Elements to pack at the start of the header bar.
     fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
+        let mut items = vec![
+            menu::Item::Button(fl!("device-info"), None, MenuAction::DeviceInfo),
+            menu::Item::Button(fl!("activity-log"), None, MenuAction::ActivityLog),
+        ];
+        if self.config.operator_mode {
+            items.push(menu::Item::Button(fl!("operator-log"), None, MenuAction::OperatorLog));
+        }
+        items.push(menu::Item::Button(fl!("debug-log-export"), None, MenuAction::ExportDebugLog));
+        items.push(menu::Item::Button(fl!("settings"), None, MenuAction::Settings));
+        items.push(menu::Item::Button(fl!("about"), None, MenuAction::About));
+
         let menu_bar = menu::bar(vec![menu::Tree::with_children(
             Element::from(menu::root(fl!("view"))),
-            menu::items(
-                &self.key_binds,
-                vec![menu::Item::Button(fl!("about"), None, MenuAction::About)],
-            ),
+            menu::items(&self.key_binds, items),
         )]);
 
         vec![menu_bar.into()]
     }
 
+    /// Elements to pack at the end of the header bar.
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        if self.device_path.is_none() {
+            return Vec::new();
+        }
+
+        let (icon_name, tooltip) = if self.claimed {
+            ("changes-prevent-symbolic", fl!("claim-held"))
+        } else {
+            ("changes-allow-symbolic", fl!("claim-free"))
+        };
+
+        vec![
+            widget::tooltip(
+                icon::from_name(icon_name).size(16).icon(),
+                text::body(tooltip),
+                widget::tooltip::Position::Bottom,
+            )
+            .into(),
+        ]
+    }
+
     /// Enables the COSMIC application to create a nav bar with this model.
     fn nav_model(&self) -> Option<&nav_bar::Model> {
         Some(&self.nav)
     }
 
+    /// Actions for a finger, offered when its nav item is right-clicked.
+    fn nav_context_menu(
+        &self,
+        id: nav_bar::Id,
+    ) -> Option<Vec<menu::Tree<cosmic::Action<Self::Message>>>> {
+        let page = self.nav.data::<Page>(id).copied()?;
+        let finger_id = page.as_finger_id()?;
+        let enrolled = self.enrolled_fingers.iter().any(|f| f == finger_id);
+        let ready = !self.busy && self.device_path.is_some() && self.selected_user.is_some();
+
+        let item = |label, action, enabled| {
+            if enabled {
+                menu::Item::Button(label, None, action)
+            } else {
+                menu::Item::ButtonDisabled(label, None, action)
+            }
+        };
+
+        Some(menu::items(
+            &HashMap::new(),
+            vec![
+                item(fl!("nav-menu-enroll"), NavMenuAction::Enroll(page), ready),
+                item(fl!("nav-menu-test"), NavMenuAction::Test(page), ready && enrolled),
+                item(
+                    fl!("nav-menu-delete"),
+                    NavMenuAction::Delete(page),
+                    ready && enrolled && self.can_delete(page),
+                ),
+                menu::Item::Divider,
+                item(fl!("nav-menu-rename"), NavMenuAction::Rename(page), true),
+                item(fl!("nav-menu-hide"), NavMenuAction::Hide(page), !enrolled),
+            ],
+        ))
+    }
+
     /// Display a context drawer if the context page is requested.
     fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
         if !self.core.window.show_context {
@@ -219,22 +713,102 @@ impl cosmic::Application for AppModel {
                 Message::ToggleContextPage(ContextPage::About),
             )
             .title(fl!("about")),
+            ContextPage::DeviceInfo => context_drawer::context_drawer(
+                self.device_info(),
+                Message::ToggleContextPage(ContextPage::DeviceInfo),
+            )
+            .title(fl!("device-info")),
+            ContextPage::Settings => context_drawer::context_drawer(
+                self.settings(),
+                Message::ToggleContextPage(ContextPage::Settings),
+            )
+            .title(fl!("settings")),
+            ContextPage::ActivityLog => context_drawer::context_drawer(
+                self.activity_log(),
+                Message::ToggleContextPage(ContextPage::ActivityLog),
+            )
+            .title(fl!("activity-log")),
+            ContextPage::OperatorLog => context_drawer::context_drawer(
+                self.operator_log(),
+                Message::ToggleContextPage(ContextPage::OperatorLog),
+            )
+            .title(fl!("operator-log")),
         })
     }
 
     /// Display a dialog in the center of the application window when `Some`.
     fn dialog(&self) -> Option<Element<'_, Self::Message>> {
-        if self.confirm_clear {
+        if let Some(confirmation) = &self.confirmation {
+            Some(self.view_confirmation(confirmation))
+        } else if let Some(marker) = &self.recovery {
+            let finger = finger_id_to_localized(&marker.finger);
+
+            let mut retry_btn = widget::button::suggested(fl!("recovery-retry"));
+            if !self.busy && self.device_path.is_some() {
+                retry_btn = retry_btn.on_press(Message::RecoveryRetry);
+            }
+
+            Some(
+                dialog::dialog()
+                    .title(fl!("recovery-title"))
+                    .body(fl!(
+                        "recovery-body",
+                        finger = finger,
+                        user = marker.username.as_str()
+                    ))
+                    .primary_action(retry_btn)
+                    .secondary_action(
+                        widget::button::standard(fl!("recovery-dismiss"))
+                            .on_press(Message::RecoveryDismiss),
+                    )
+                    .into(),
+            )
+        } else if let Some((page, name)) = &self.rename {
+            Some(
+                dialog::dialog()
+                    .title(fl!("rename-title", finger = page.localized_name()))
+                    .body(fl!("rename-body"))
+                    .control(
+                        widget::text_input(page.localized_name(), name.as_str())
+                            .on_input(Message::RenameInput)
+                            .on_submit(|_| Message::RenameSave),
+                    )
+                    .primary_action(
+                        widget::button::suggested(fl!("rename-save"))
+                            .on_press(Message::RenameSave),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::RenameCancel),
+                    )
+                    .into(),
+            )
+        } else if let Some(conflict) = &self.duplicate_conflict {
+            let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+            let finger = finger_id_to_localized(&conflict.finger);
+            let users = i18n::join_list(conflict.users.iter().map(ToString::to_string));
+
+            let switch_buttons = conflict.users.iter().fold(
+                widget::column().spacing(space_xxs),
+                |column, user| {
+                    column.push(
+                        widget::button::link(fl!("duplicate-switch", user = user.to_string()))
+                            .on_press(Message::UserSelected(user.clone())),
+                    )
+                },
+            );
+
             Some(
                 dialog::dialog()
-                    .title(fl!("clear-device"))
-                    .body(fl!("clear-device-confirm"))
+                    .title(fl!("duplicate-title"))
+                    .body(fl!("duplicate-body", finger = finger, users = users))
+                    .control(switch_buttons)
                     .primary_action(
-                        widget::button::destructive(fl!("clear-device"))
-                            .on_press(Message::ClearDevice),
+                        widget::button::suggested(fl!("duplicate-enroll-anyway"))
+                            .on_press(Message::EnrollAnyway),
                     )
                     .secondary_action(
-                        widget::button::standard(fl!("cancel")).on_press(Message::CancelClear),
+                        widget::button::standard(fl!("cancel"))
+                            .on_press(Message::DuplicateDismiss),
                     )
                     .into(),
             )
@@ -250,24 +824,87 @@ impl cosmic::Application for AppModel {
     fn view(&self) -> Element<'_, Self::Message> {
         let mut column = widget::column().push(self.view_header());
 
-        if let Some(picker) = self.view_user_picker() {
+        // The wizard replaces the pickers, the hands and the raw controls while it is shown
+        if self.enroll_wizard.is_none()
+            && let Some(picker) = self.view_pickers()
+        {
             column = column.push(picker);
         }
 
         column = column
-            .push(self.view_icon())
+            .push(
+                self.view_enroll_wizard()
+                    .unwrap_or_else(|| self.view_hands()),
+            )
             .push(self.view_status());
 
+        if let Some(banner) = self.view_error_banner() {
+            column = column.push(banner);
+        } else if self.permission_denied && !self.busy {
+            column = column.push(
+                widget::button::suggested(fl!("authenticate"))
+                    .on_press(Message::Authenticate)
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center),
+            );
+        }
+
+        if let Some(wait) = self.view_device_wait() {
+            column = column.push(wait);
+        } else if let Some(retry) = self.view_claim_retry() {
+            column = column.push(retry);
+        }
+
+        if let Some(guidance) = self.view_in_use_guidance() {
+            column = column.push(guidance);
+        }
+
+        if let Some(banner) = self.view_remote_notice() {
+            column = column.push(banner);
+        }
+
+        if let Some(banner) = self.view_pam_banner() {
+            column = column.push(banner);
+        }
+
+        if let Some(banner) = self.view_clear_resume() {
+            column = column.push(banner);
+        }
+
         if let Some(progress) = self.view_progress() {
             column = column.push(progress);
         }
 
-        column
-            .push(self.view_controls())
+        if let Some(cleanup) = self.view_storage_cleanup() {
+            column = column.push(cleanup);
+        }
+
+        if self.enroll_wizard.is_none() {
+            if let Some(batch) = self.view_batch_enroll() {
+                column = column.push(batch);
+            }
+            if let Some(wizard) = self.view_replace_wizard() {
+                column = column.push(wizard);
+            }
+            column = column.push(self.view_controls());
+        }
+
+        if let Some(footer) = self.view_last_change() {
+            column = column.push(footer);
+        }
+
+        let content = column
             .align_x(Horizontal::Center)
             .spacing(MAIN_SPACING)
-            .padding(MAIN_PADDING)
-            .into()
+            .padding(MAIN_PADDING);
+
+        // Dropping a user switches to their prints
+        widget::dnd_destination::dnd_destination_for_data(
+            content,
+            |user: Option<DroppedUser>, _action| Message::UserDropped(user.map(|user| user.0)),
+        )
+        .into()
     }
 
     /// Register subscriptions for this application.
@@ -278,6 +915,12 @@ impl cosmic::Application for AppModel {
     fn subscription(&self) -> Subscription<Self::Message> {
         struct MySubscription;
         struct EnrollmentSubscription;
+        struct VerificationSubscription;
+        struct ClaimSubscription;
+        struct ServiceSubscription;
+        struct UsersSubscription;
+        struct DevicesSubscription;
+        struct PowerSaverSubscription;
 
         let mut subscriptions = vec![
             // Create a subscription which emits updates through a channel.
@@ -287,6 +930,31 @@ impl cosmic::Application for AppModel {
                     futures_util::future::pending().await
                 }),
             ),
+            // Watch whether this app holds a claim on the reader.
+            Subscription::run_with_id(
+                std::any::TypeId::of::<ClaimSubscription>(),
+                cosmic::iced::stream::channel(4, move |mut output| async move {
+                    let mut claim = session::watch_claim();
+                    while claim.changed().await.is_ok() {
+                        let claimed = *claim.borrow_and_update();
+                        let _ = output.send(Message::ClaimChanged(claimed)).await;
+                    }
+                    futures_util::future::pending().await
+                }),
+            ),
+            // Slow down animations while the window is in the background.
+            cosmic::iced::event::listen_with(|event, _status, _id| match event {
+                cosmic::iced::Event::Window(cosmic::iced::window::Event::Focused) => {
+                    Some(Message::WindowFocused(true))
+                }
+                cosmic::iced::Event::Window(cosmic::iced::window::Event::Unfocused) => {
+                    Some(Message::WindowFocused(false))
+                }
+                cosmic::iced::Event::Window(cosmic::iced::window::Event::Resized(size)) => Some(
+                    Message::WindowResized(size.width.round() as u32, size.height.round() as u32),
+                ),
+                _ => None,
+            }),
             // Watch for application configuration changes.
             self.core()
                 .watch_config::<Config>(Self::APP_ID)
@@ -299,38 +967,138 @@ impl cosmic::Application for AppModel {
                 }),
         ];
 
+        // Pick up readers plugged in after startup
+        if self.backend.is_some() && self.devices.is_empty() {
+            subscriptions.push(
+                cosmic::iced::time::every(HOTPLUG_POLL_INTERVAL).map(|_| Message::RescanDevices),
+            );
+        }
+
+        // Browse earlier status messages with the arrow keys
+        if self.status_focused {
+            subscriptions.push(keyboard::on_key_press(|key, _modifiers| match key {
+                Key::Named(Named::ArrowUp) => Some(Message::StatusOlder),
+                Key::Named(Named::ArrowDown) => Some(Message::StatusNewer),
+                Key::Named(Named::Escape) => Some(Message::FocusStatus(false)),
+                _ => None,
+            }));
+        }
+
+        // Animate the active finger on the hand map
+        if self.replace.is_some()
+            && (self.enrollment.is_active() || self.verifying_finger.is_some())
+        {
+            subscriptions.push(
+                cosmic::iced::time::every(pulse_interval(self.window_focused, self.power_saver))
+                    .map(|_| Message::PulseTick),
+            );
+        }
+
+        // Keep checking whether a reader held by another program is free again
+        if self.claim_retry.is_some() {
+            subscriptions.push(
+                cosmic::iced::time::every(CLAIM_RETRY_TICK).map(|_| Message::ClaimRetryTick),
+            );
+        }
+
+        // Notice fprintd crashing or the bus connection dropping
+        if let Some(backend) = &self.backend {
+            subscriptions.push(Subscription::run_with_id(
+                (std::any::TypeId::of::<ServiceSubscription>(), self.backend_generation),
+                backend.watch_service(),
+            ));
+            // Follow readers plugged in or out while fprintd runs
+            subscriptions.push(Subscription::run_with_id(
+                (std::any::TypeId::of::<DevicesSubscription>(), self.backend_generation),
+                backend.watch_devices(),
+            ));
+            // Pick up accounts created or deleted while the app is open
+            subscriptions.push(Subscription::run_with_id(
+                (std::any::TypeId::of::<UsersSubscription>(), self.backend_generation),
+                backend.watch_users(),
+            ));
+            subscriptions.push(Subscription::run_with_id(
+                (std::any::TypeId::of::<PowerSaverSubscription>(), self.backend_generation),
+                backend.watch_power_saver(),
+            ));
+        }
+
+        if self.reconnect.is_some() {
+            subscriptions.push(
+                cosmic::iced::time::every(RECONNECT_TICK).map(|_| Message::ReconnectTick),
+            );
+        }
+
+        // Pick up prints enrolled or deleted with fprintd-enroll and friends
+        if self.backend.is_some() && self.device_path.is_some() && !self.busy {
+            subscriptions.push(
+                cosmic::iced::time::every(FINGERS_REFRESH_INTERVAL)
+                    .map(|_| Message::RefreshFingers),
+            );
+        }
+
+        // Give the next finger of a batch its turn after a pause
+        if self.batch.as_ref().is_some_and(BatchEnroll::is_waiting) {
+            subscriptions.push(
+                cosmic::iced::time::every(BATCH_PAUSE).map(|_| Message::BatchNext),
+            );
+        }
+
+        // Take the next scan of the self-test after a pause
+        if self.self_test.waiting {
+            subscriptions.push(
+                cosmic::iced::time::every(SELF_TEST_PAUSE).map(|_| Message::SelfTestNext),
+            );
+        }
+
+        // Release claims that stopped making progress
+        if self.claimed && (self.enrollment.is_active() || self.verifying_finger.is_some()) {
+            subscriptions.push(
+                cosmic::iced::time::every(IDLE_CHECK_INTERVAL).map(|_| Message::IdleTick),
+            );
+        }
+
         // Add enrollment subscription if enrolling
-        if let (Some(finger_name), Some(device_path), Some(connection), Some(user)) = (
-            &self.enrolling_finger,
+        if let (Some(finger_name), Some(device_path), Some(backend), Some(user)) = (
+            self.enrollment.finger(),
             &self.device_path,
-            &self.connection,
+            &self.backend,
             &self.selected_user,
         ) {
-            let finger_name = finger_name.clone();
-            let device_path = device_path.clone();
-            let connection = connection.clone();
-            let user = user.clone();
+            let enrollment = backend
+                .enroll(
+                    (**device_path).clone(),
+                    (**finger_name).clone(),
+                    (*user.username).clone(),
+                )
+                // Stay subscribed until the enrollment is stopped
+                .chain(futures_util::stream::pending());
 
             subscriptions.push(Subscription::run_with_id(
                 std::any::TypeId::of::<EnrollmentSubscription>(),
-                cosmic::iced::stream::channel(100, move |mut output| async move {
-                    // Implement enrollment stream here
-                    match enroll_fingerprint_process(
-                        connection,
-                        &device_path,
-                        &finger_name,
-                        &user.username,
-                        &mut output,
-                    )
-                    .await
-                    {
-                        Ok(_) => {}
-                        Err(e) => {
-                            let _ = output.send(Message::OperationError(AppError::from(e))).await;
-                        }
-                    }
-                    futures_util::future::pending().await
-                }),
+                enrollment,
+            ));
+        }
+
+        // Add verification subscription if verifying
+        if let (Some(finger_name), Some(device_path), Some(backend), Some(user)) = (
+            &self.verifying_finger,
+            &self.device_path,
+            &self.backend,
+            &self.selected_user,
+        ) {
+            let verification = backend
+                .verify(
+                    (**device_path).clone(),
+                    (**finger_name).clone(),
+                    (*user.username).clone(),
+                )
+                // Stay subscribed until the verification is stopped
+                .chain(futures_util::stream::pending());
+
+            subscriptions.push(Subscription::run_with_id(
+                std::any::TypeId::of::<VerificationSubscription>(),
+                verification,
             ));
         }
 
@@ -342,592 +1110,5983 @@ impl cosmic::Application for AppModel {
     /// Tasks may be returned for asynchronous execution of code in the background
     /// on the application's async runtime.
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
-        match message {
-            Message::ConnectionReady(conn) => self.on_connection_ready(conn),
+        let task = self.handle_message(message);
+        // Remember every status shown so it can be browsed later
+        self.status_history.push(&self.status);
+        self.update_nav_pages();
+        self.update_nav_badges();
+        self.update_nav_names();
+        self.save_nav_bar_state();
+        Task::batch(vec![task, self.report_task(), self.operator_log_task()])
+    }
 
-            Message::UsersFound(users) => self.on_users_found(users),
+    /// Shows the window when the app is launched again or summoned over DBus,
+    /// on the page and user asked for if any.
+    fn dbus_activation(
+        &mut self,
+        msg: cosmic::dbus_activation::Message,
+    ) -> Task<cosmic::Action<Self::Message>> {
+        let open = match msg.msg {
+            cosmic::dbus_activation::Details::ActivateAction { action, .. } => {
+                match OpenTarget::parse(&action) {
+                    Ok(target) => Task::done(cosmic::Action::App(Message::Open(target))),
+                    Err(e) => {
+                        tracing::warn!(%e, "Ignoring an activation");
+                        Task::none()
+                    }
+                }
+            }
+            _ => Task::none(),
+        };
 
-            Message::UserSelected(user) => self.on_user_selected(user),
+        // Raise the window of this instance instead of starting another one
+        if let Some(id) = self.core.main_window_id() {
+            return Task::batch(vec![
+                cosmic::iced::window::minimize(id, false),
+                cosmic::iced::window::gain_focus(id),
+                open,
+            ]);
+        }
 
-            Message::DeviceFound(path) => self.on_device_found(path),
+        // Started with --hidden, so there is no window yet
+        let (id, window) = cosmic::iced::window::open(cosmic::iced::window::Settings {
+            min_size: Some(cosmic::iced::Size::new(WINDOW_MIN_WIDTH, WINDOW_MIN_HEIGHT)),
+            decorations: false,
+            transparent: true,
+            platform_specific: cosmic::iced::window::settings::PlatformSpecific {
+                application_id: Self::APP_ID.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        self.core.set_main_window_id(Some(id));
+        Task::batch(vec![window.discard(), self.update_title(), open])
+    }
 
-            Message::EnrolledFingers(fingers) => {
-                self.enrolled_fingers = fingers;
-                Task::none()
-            }
+    /// Picks up a new accent or other change to the system theme.
+    ///
+    /// A forced dark or light scheme is loaded again as well, since it was
+    /// copied from the system theme when it was chosen.
+    fn system_theme_update(
+        &mut self,
+        _keys: &[&'static str],
+        _new_theme: &cosmic_theme::Theme,
+    ) -> Task<cosmic::Action<Self::Message>> {
+        cosmic::command::set_theme(self.config.app_theme.theme())
+    }
 
-            Message::OperationError(err) => {
-                self.status = err.localized_message();
-                self.busy = false;
-                self.enrolling_finger = None;
-                Task::none()
-            }
+    /// Follows the system between dark and light unless a scheme is forced.
+    fn system_theme_mode_update(
+        &mut self,
+        _keys: &[&'static str],
+        new_mode: &cosmic_theme::ThemeMode,
+    ) -> Task<cosmic::Action<Self::Message>> {
+        if self.config.app_theme != AppTheme::System {
+            return Task::none();
+        }
+        let theme = if new_mode.is_dark {
+            theme::system_dark()
+        } else {
+            theme::system_light()
+        };
+        cosmic::command::set_theme(theme)
+    }
 
-            Message::EnrollStart(total) => {
-                self.enroll_total_stages = total;
-                self.enroll_progress = 0;
-                self.status = fl!("enroll-starting");
+    /// Called when a nav item is selected.
+    fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
+        if self.busy || self.replace.is_some() || self.selection_locked() {
+            return Task::none();
+        }
+        // A section header opens the first page under it
+        if let Some(section) = self.nav.data::<NavSection>(id).copied() {
+            let first = self.nav.iter().find(|id| {
+                self.nav
+                    .data::<Page>(*id)
+                    .is_some_and(|page| NavSection::of(*page) == section)
+            });
+            return match first {
+                Some(first) => self.on_nav_select(first),
+                None => Task::none(),
+            };
+        }
+        self.confirmation = None;
+        self.cleanup = None;
+        // Activate the page in the model.
+        self.nav.activate(id);
+
+        if let Some(wizard) = &mut self.enroll_wizard
+            && let Some(page) = self.nav.data::<Page>(id).copied()
+        {
+            wizard.select_finger(page, &self.enrolled_fingers);
+        }
+
+        Task::batch(vec![self.update_title(), self.refresh_overview()])
+    }
+
+    /// Called when the window is closed. A running scan is stopped and the
+    /// reader released first, as fprintd could otherwise keep it claimed.
+    fn on_app_exit(&mut self) -> Option<Self::Message> {
+        (self.enrollment.is_active() || self.verifying_finger.is_some())
+            .then_some(Message::Exit)
+    }
+}
+
+impl AppModel {
+    fn handle_message(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::ConnectionReady(conn) => self.on_connection_ready(conn),
+
+            Message::ConnectionLost => {
+                tracing::warn!("Lost the connection to the system bus");
+                self.backend = None;
+                self.start_reconnecting();
                 Task::none()
             }
 
-            Message::EnrollStatus(status, done) => self.on_enroll_status(status, done),
+            Message::ServiceChanged(running) => self.on_service_changed(running),
 
-            Message::EnrollStop => self.on_enroll_stop(),
+            Message::ReconnectTick => self.on_reconnect_tick(),
 
-            Message::DeleteComplete => {
-                self.status = fl!("deleted");
-                self.busy = false;
-                if let Some(page) = self.nav.data::<Page>(self.nav.active()) {
-                    if let Some(finger_id) = page.as_finger_id() {
-                        self.enrolled_fingers.retain(|f| f != finger_id);
-                    } else {
-                        self.enrolled_fingers.clear();
-                    }
+            Message::ReconnectFailed(err) => {
+                tracing::debug!(%err, "Reconnecting failed");
+                if let Some(reconnect) = &mut self.reconnect {
+                    reconnect.failed(Instant::now());
                 }
                 Task::none()
             }
 
-            Message::Delete => self.on_delete(),
+            Message::UsersFound(users) => self.on_users_found(users),
 
-            Message::ClearDevice => self.on_clear_device(),
+            // Listing them again drops deleted users and keeps the selection valid
+            Message::UsersChanged => self.list_users_task(),
 
-            Message::CancelClear => {
-                self.confirm_clear = false;
-                Task::none()
+            Message::DevicesChanged => {
+                let Some(backend) = self.backend.clone() else {
+                    return Task::none();
+                };
+                // Unlike a rescan, report no readers too, so an unplugged one goes away
+                Task::perform(
+                    async move {
+                        match backend.find_devices().await {
+                            Ok(devices) => Some(Message::DevicesFound(devices)),
+                            Err(e) => {
+                                tracing::debug!(%e, "Failed to list changed devices");
+                                None
+                            }
+                        }
+                    },
+                    |message| message.map_or(cosmic::Action::None, cosmic::Action::App),
+                )
             }
 
-            Message::ClearComplete(res) => {
-                match res {
-                    Ok(_) => {
-                        self.status = fl!("device-cleared");
-                        self.enrolled_fingers.clear();
+            Message::UserSelected(user) => self.on_user_selected(user),
+
+            Message::Open(target) => self.on_open(target),
+
+            Message::UserDropped(username) => self.on_user_dropped(username),
+
+            Message::DevicesFound(devices) => {
+                // Finding the readers again completes a reconnection
+                self.reconnect = None;
+                self.on_devices_found(devices)
+            }
+
+            Message::Authenticate => self.authenticate(),
+
+            Message::AuthenticationComplete(res) => {
+                self.busy = false;
+                match res {
+                    Ok(true) => {
+                        self.authorized_for_others = true;
+                        self.permission_denied = false;
+                        self.last_error = None;
+                        self.status = fl!("authenticated");
                     }
-                    Err(e) => {
-                        self.status = e.localized_message();
+                    Ok(false) => {
+                        self.permission_denied = true;
+                        self.status = fl!("authentication-failed");
+                        return Task::none();
                     }
+                    // Without polkit, leave the checks to fprintd
+                    Err(e) => tracing::warn!(?e, "polkit authorization failed"),
+                }
+                Task::batch(vec![self.list_fingers_task(), self.refresh_overview()])
+            }
+
+            Message::ManageOthersChecked(allowed) => {
+                self.may_manage_others = allowed;
+                if !allowed && self.confirming_clear() {
+                    self.confirmation = None;
+                }
+                let previous = self.selected_user.clone();
+                self.update_shown_users();
+                if self.selected_user != previous {
+                    self.enrolled_fingers.clear();
+                    return self.list_fingers_task();
                 }
-                self.busy = false;
                 Task::none()
             }
 
-            Message::Register => self.on_register(),
+            Message::CapabilitiesProbed(capabilities) => {
+                self.capabilities = capabilities;
+                if !capabilities.delete_finger && self.confirming_clear() {
+                    self.confirmation = None;
+                }
+                Task::none()
+            }
 
-            Message::OpenRepositoryUrl => {
-                let _ = open::that_detached(REPOSITORY);
+            Message::OverviewLoaded(overview) => {
+                self.overview = Some(overview);
                 Task::none()
             }
 
-            Message::ToggleContextPage(context_page) => {
-                if self.context_page == context_page {
-                    // Close the context drawer if the toggled context page is the same.
-                    self.core.window.show_context = !self.core.window.show_context;
+            Message::SelectFinger(page) => {
+                let id = self.nav_id(page);
+                match id {
+                    Some(id) => self.on_nav_select(id),
+                    None => Task::none(),
+                }
+            }
+
+            Message::FocusStatus(focused) => {
+                self.status_focused = focused;
+                if !focused {
+                    self.status_history.reset();
+                }
+                Task::none()
+            }
+
+            Message::StatusOlder => {
+                self.status_history.older();
+                Task::none()
+            }
+
+            Message::StatusNewer => {
+                self.status_history.newer();
+                Task::none()
+            }
+
+            Message::PulseTick => {
+                self.pulse = !self.pulse;
+                Task::none()
+            }
+
+            Message::WindowFocused(focused) => {
+                self.window_focused = focused;
+                Task::none()
+            }
+
+            Message::WindowResized(width, height) => {
+                self.window_size = (width, height);
+                self.window_resizes += 1;
+                let resizes = self.window_resizes;
+                Task::perform(
+                    async move {
+                        tokio::time::sleep(WINDOW_SIZE_SAVE_DELAY).await;
+                        resizes
+                    },
+                    |resizes| cosmic::Action::App(Message::SaveWindowSize(resizes)),
+                )
+            }
+
+            Message::SaveWindowSize(resizes) => {
+                let (width, height) = self.window_size;
+                if resizes == self.window_resizes
+                    && (width, height) != (self.config.window_width, self.config.window_height)
+                {
+                    self.save_config(|config, handler| {
+                        config.set_window_width(handler, width)?;
+                        config.set_window_height(handler, height)
+                    });
+                }
+                Task::none()
+            }
+
+            Message::PowerSaverChanged(on) => {
+                self.power_saver = on;
+                Task::none()
+            }
+
+            Message::ProgressBusReady(connection) => {
+                self.progress_bus = Some(connection);
+                Task::none()
+            }
+
+            Message::SessionLocated(location) => {
+                if !location.is_local() {
+                    tracing::info!(?location, "Running away from the reader");
+                }
+                self.session_location = location;
+                Task::none()
+            }
+
+            Message::RescanDevices => {
+                let Some(backend) = self.backend.clone() else {
+                    return Task::none();
+                };
+                // Only report back once something shows up, so the current status stays put
+                Task::perform(
+                    async move {
+                        match backend.find_devices().await {
+                            Ok(devices) if !devices.is_empty() => Some(Message::DevicesFound(devices)),
+                            Ok(_) => None,
+                            Err(e) => {
+                                tracing::debug!(%e, "Failed to rescan devices");
+                                None
+                            }
+                        }
+                    },
+                    |message| message.map_or(cosmic::Action::None, cosmic::Action::App),
+                )
+            }
+
+            Message::DeviceSelected(device) => {
+                if self.busy || self.replace.is_some() || self.selection_locked() {
+                    return Task::none();
+                }
+                self.confirmation = None;
+                self.remember_device(&device);
+                self.select_device(device)
+            }
+
+            Message::RefreshFingers => self.on_refresh_fingers(),
+
+            Message::EnrolledFingers(username, fingers) => {
+                // Listed for a user that was switched away from meanwhile
+                if self
+                    .selected_user
+                    .as_ref()
+                    .is_none_or(|user| *user.username != username)
+                {
+                    tracing::debug!("Dropping the stale prints of {username}");
+                    return Task::none();
+                }
+                // Walk users without any prints through their first enrollment
+                if !std::mem::replace(&mut self.wizard_offered, true)
+                    && fingers.is_empty()
+                    && self.replace.is_none()
+                    && self.recovery.is_none()
+                    && self.enroll_wizard.is_none()
+                {
+                    self.enroll_wizard = Some(EnrollWizard::new(self.shows_user_picker()));
+                }
+                self.enrolled_fingers = fingers;
+                Task::none()
+            }
+
+            Message::OperationError(err) => {
+                self.finish_operator_action(Some(err.localized_message()));
+                // Failures are expected until fprintd is back
+                if let Some(reconnect) = &mut self.reconnect {
+                    tracing::debug!(?err, "Operation failed while reconnecting");
+                    reconnect.failed(Instant::now());
+                    self.abandon_operation();
+                    self.busy = true;
+                    return Task::none();
+                }
+                let lost_bus = matches!(err, AppError::ConnectDbus(_));
+
+                if err == AppError::AlreadyInUse {
+                    self.claim_retry.get_or_insert_with(|| ClaimRetry::new(Instant::now()));
+                }
+
+                // Repeatedly losing the reader points at another service polling it
+                let guidance_task = if err == AppError::AlreadyInUse
+                    && self.in_use.record(Instant::now())
+                {
+                    Task::perform(
+                        async move { Message::ConflictsFound(find_conflicting_services().await) },
+                        cosmic::Action::App,
+                    )
                 } else {
-                    // Open the context drawer to display the requested context page.
-                    self.context_page = context_page;
-                    self.core.window.show_context = true;
+                    Task::none()
+                };
+
+                let retry = if self.enrollment.is_active() {
+                    Message::Register
+                } else if self.verifying_finger.is_some() {
+                    Message::Verify
+                } else if self.pending_delete.is_some() {
+                    Message::Delete
+                } else if self.pending_cleanup.is_some() {
+                    Message::CleanupDelete
+                } else {
+                    // Reloads the readers and with them the enrolled prints
+                    Message::RescanDevices
+                };
+
+                if self.enrollment.is_active()
+                    && let Some(journal) = &mut self.enroll_journal
+                {
+                    journal.fail(&format!("{err:?}"));
+                }
+
+                // Wait for the reader instead of failing, unless there is nothing to run again
+                let wait = Duration::from_secs(self.config.busy_wait_secs);
+                if err == AppError::AlreadyInUse
+                    && !wait.is_zero()
+                    && !matches!(retry, Message::RescanDevices)
+                {
+                    self.abandon_operation();
+                    self.busy = true;
+                    self.status = fl!("device-wait");
+                    self.device_wait = Some((retry, Instant::now() + wait));
+                    return guidance_task;
+                }
+
+                self.permission_denied = err == AppError::PermissionDenied;
+                self.status = err.localized_message();
+                self.last_error = Some((err, retry));
+                self.error_details_open = false;
+                self.abandon_operation();
+                // Keep trying in the background in case the bus comes up later
+                if lost_bus {
+                    self.reconnect = Some(Reconnect::new(Instant::now()));
+                }
+                guidance_task
+            }
+
+            Message::ErrorAction(action) => self.on_error_action(action),
+
+            Message::StartDaemonComplete(res) => {
+                match res {
+                    Ok(()) => self.status = fl!("error-action-daemon-started"),
+                    Err(err) => {
+                        tracing::error!(%err, "failed to start fprintd");
+                        self.status = fl!("error-action-start-daemon-failed");
+                        return Task::none();
+                    }
                 }
+                Task::done(cosmic::Action::App(Message::RescanDevices))
+            }
+
+            Message::DismissError => {
+                self.last_error = None;
                 Task::none()
             }
 
-            Message::UpdateConfig(config) => {
-                self.config = config;
+            Message::ToggleErrorDetails => {
+                self.error_details_open = !self.error_details_open;
                 Task::none()
             }
 
-            Message::LaunchUrl(url) => {
-                match open::that_detached(&url) {
-                    Ok(()) => {}
+            Message::PamStatusLoaded(status, can_enable) => {
+                self.pam_status = status;
+                self.pam_can_enable = can_enable;
+                Task::none()
+            }
+
+            Message::EnablePam => {
+                self.status = fl!("pam-enabling");
+                Task::perform(
+                    async move { Message::EnablePamComplete(enable_fingerprint_auth().await) },
+                    cosmic::Action::App,
+                )
+            }
+
+            Message::EnablePamComplete(res) => {
+                match res {
+                    Ok(()) => self.status = fl!("pam-enabled"),
                     Err(err) => {
-                        eprintln!("failed to open {url:?}: {err}");
+                        tracing::error!(%err, "failed to enable fingerprint authentication");
+                        self.status = fl!("pam-enable-failed");
                     }
                 }
+                pam_status_task()
+            }
+
+            Message::DismissPamBanner => {
+                self.pam_banner_dismissed = true;
                 Task::none()
             }
-        }
-    }
 
-    /// Called when a nav item is selected.
-    fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
-        if self.busy {
-            return Task::none();
-        }
-        self.confirm_clear = false;
-        // Activate the page in the model.
-        self.nav.activate(id);
+            Message::ConflictsFound(services) => {
+                self.in_use_guidance = Some(services);
+                Task::none()
+            }
 
-        self.update_title()
-    }
-}
+            Message::DismissInUseGuidance => {
+                self.in_use_guidance = None;
+                self.in_use.reset();
+                Task::none()
+            }
 
-impl AppModel {
-    /// The about page for this app.
-    pub fn about(&self) -> Element<'_, Message> {
-        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+            Message::EnrollStart(total) => {
+                // Stopping was asked for before fprintd got going
+                if !self.enrollment.started() {
+                    return Task::none();
+                }
+                self.last_activity = Instant::now();
+                let total = total.or(self.device.as_ref().and_then(|device| device.stages));
+                self.enroll_total_stages = total;
+                self.enroll_progress = 0;
+                self.enroll_retry = None;
+                if let Some(journal) = &mut self.enroll_journal {
+                    journal.set_stages(total);
+                }
+                self.status = match self.scan_type() {
+                    ScanType::Press => fl!("enroll-starting"),
+                    ScanType::Swipe => fl!("enroll-starting-swipe"),
+                };
+                Task::none()
+            }
 
-        let icon = widget::svg(widget::svg::Handle::from_memory(APP_ICON));
+            Message::EnrollStatus(status, done) => {
+                let task = self.on_enroll_status(status.clone(), done);
+                // Sent once the passed scan is counted
+                Task::batch(vec![task, self.progress_signal_task(&status)])
+            }
 
-        let title = text::title3(fl!("app-title"));
+            Message::EnrollStop => self.on_enroll_stop(),
 
-        let hash = env!("VERGEN_GIT_SHA");
-        let short_hash: String = hash.chars().take(7).collect();
-        let date = env!("VERGEN_GIT_COMMIT_DATE");
+            Message::Exit => self.on_exit(),
 
-        let link = widget::button::link(REPOSITORY)
-            .on_press(Message::OpenRepositoryUrl)
-            .padding(0);
+            Message::ExitReady => cosmic::iced::exit(),
 
-        widget::column()
-            .push(icon)
-            .push(title)
-            .push(link)
-            .push(
-                widget::button::link(fl!(
-                    "git-description",
-                    hash = short_hash.as_str(),
-                    date = date
-                ))
-                .on_press(Message::LaunchUrl(format!("{REPOSITORY}/commits/{hash}")))
-                .padding(0),
-            )
-            .align_x(Alignment::Center)
-            .spacing(space_xxs)
-            .into()
-    }
+            Message::CopyActivityLog => {
+                cosmic::iced::clipboard::write(self.status_history.to_text())
+            }
 
-    fn list_fingers_task(&self) -> Task<cosmic::Action<Message>> {
-        if let (Some(proxy), Some(user)) =
-            (&self.device_proxy, &self.selected_user)
-        {
-            let proxy = proxy.clone();
-            let username = (*user.username).clone();
-            return Task::perform(
-                async move {
-                    match list_enrolled_fingers_dbus(&proxy, username).await {
-                        Ok(fingers) => Message::EnrolledFingers(fingers),
-                        Err(e) => Message::OperationError(
-                            AppError::from(e).with_context("Failed to list fingers"),
-                        ),
-                    }
-                },
+            Message::ExportDebugLog => Task::perform(
+                async { Message::DebugLogExported(debug_log::export().await) },
                 cosmic::Action::App,
-            );
-        }
-        Task::none()
-    }
+            ),
 
-    fn on_connection_ready(&mut self, conn: zbus::Connection) -> Task<cosmic::Action<Message>> {
-        self.connection = Some(conn.clone());
-        self.status = fl!("status-searching-device");
+            Message::DebugLogExported(res) => match res {
+                Ok(log) => {
+                    self.status = fl!("debug-log-copied");
+                    cosmic::iced::clipboard::write(log)
+                }
+                Err(why) => {
+                    tracing::error!(%why, "failed to export the debug log");
+                    self.status = if debug_log::is_enabled() {
+                        fl!("debug-log-export-failed", error = why)
+                    } else {
+                        fl!("debug-log-off")
+                    };
+                    Task::none()
+                }
+            },
 
-        let conn_clone = conn.clone();
-        let find_device_task = Task::perform(
-            async move {
-                match find_device(&conn_clone).await {
-                    Ok((path, proxy)) => Message::DeviceFound(Some((path, proxy))),
-                    Err(e) => {
-                        let error = AppError::from(e);
-                        if matches!(error, AppError::Unknown(_)) {
-                            Message::OperationError(AppError::DeviceNotFound)
-                        } else {
-                            Message::OperationError(error)
-                        }
-                    }
+            Message::ExportJournal => match &self.enroll_journal {
+                Some(journal) if journal.is_finished() => {
+                    let journal = journal.clone();
+                    Task::perform(
+                        async move { Message::JournalExported(journal::export(journal).await) },
+                        cosmic::Action::App,
+                    )
                 }
+                _ => Task::none(),
             },
-            cosmic::Action::App,
-        );
 
-        let conn_clone = conn.clone();
-        // Get users from AccountsService
-        let fetch_users_task = Task::perform(
-            async move {
-                let mut users = Vec::new();
-                if let Ok(accounts) = AccountsProxy::new(&conn_clone).await
-                && let Ok(user_paths) = accounts.list_cached_users().await {
-                    let fetched_users: Vec<_> = stream::iter(user_paths)
-                        .map(|path| {
-                            let conn = conn_clone.clone();
-                            async move {
-                                let builder = match UserProxy::builder(&conn).path(&path) {
-                                    Ok(builder) => builder,
-                                    Err(e) => {
-                                        tracing::error!(
-                                            %e,
-                                            "Failed to create UserProxy for path {path}"
-                                        );
-                                        return Err(e);
-                                    }
-                                };
-
-                                if let Ok(user_proxy) = builder.build().await {
-                                    if let (Ok(name), Ok(real_name)) =
-                                        (user_proxy.user_name().await, user_proxy.real_name().await)
-                                    {
-                                        Ok::<_, zbus::Error>(UserOption {
-                                            username: Arc::new(name),
-                                            realname: Arc::new(real_name),
-                                        })
-                                    } else {
-                                        Err(zbus::Error::Failure(
-                                            "Failed to fetch user name or real name".to_string(),
-                                        ))
-                                    }
-                                } else {
-                                    Err(zbus::Error::Failure(
-                                        "Failed to fetch user name or real name".to_string(),
-                                    ))
-                                }
-                            }
-                        })
-                        .buffered(USER_FETCH_CONCURRENCY)
-                        .filter_map(|res| async { res.ok() })
-                        .collect()
-                        .await;
-                    users.extend(fetched_users);
+            Message::JournalExported(res) => {
+                match res {
+                    Ok(path) => {
+                        self.status = fl!("journal-exported", path = path.display().to_string());
+                    }
+                    Err(err) => {
+                        tracing::error!(%err, "failed to export the enrollment journal");
+                        self.status = fl!("journal-export-failed");
+                    }
                 }
+                Task::none()
+            }
 
+            Message::VerifyStatus(status, done) => self.on_verify_status(status, done),
 
+            Message::Verify => self.on_verify(),
 
-                // Fallback to current user if list is empty
-                if users.is_empty() {
-                    if let Ok(Some(user)) = User::from_uid(Uid::current()) {
-                        users.push(UserOption {
-                            username: Arc::new(user.name),
-                            realname: Arc::new(user.gecos.to_string_lossy().into_owned()),
-                        });
-                    }
+            Message::Identify => self.on_identify(),
+
+            Message::VerifyFingerSelected(finger) => {
+                if let Some(selected) = &mut self.identifying
+                    && finger != "any"
+                {
+                    *selected = Some(finger);
                 }
-                Message::UsersFound(users)
-            },
-            cosmic::Action::App,
-        );
+                Task::none()
+            }
 
-        Task::batch(vec![find_device_task, fetch_users_task])
-    }
+            Message::VerifyStop => self.on_verify_stop(),
 
-    fn on_users_found(&mut self, users: Vec<UserOption>) -> Task<cosmic::Action<Message>> {
-        self.users = users;
-        // Ensure selected_user is valid
-        if let Some(selected) = &self.selected_user {
-            if !self.users.iter().any(|u| u.username == selected.username) {
-                if !self.users.is_empty() {
-                    self.selected_user = Some(self.users[0].clone());
+            Message::ReplaceStart => self.on_replace_start(),
+
+            Message::ReplaceFingerSelected(index) => {
+                if let Some(wizard) = &mut self.replace {
+                    wizard.select(index);
                 }
-            } else if let Some(updated_user) =
-                self.users
-                    .iter()
-                    .find(|u| u.username == selected.username)
-            {
-                // Update realname if found
-                self.selected_user = Some(updated_user.clone());
+                Task::none()
             }
-        } else if !self.users.is_empty() {
-            self.selected_user = Some(self.users[0].clone());
-        }
 
-        self.list_fingers_task()
-    }
+            Message::ReplaceContinue => self.on_replace_continue(),
 
-    fn on_user_selected(&mut self, user: UserOption) -> Task<cosmic::Action<Message>> {
-        if self.busy {
-            return Task::none();
-        }
-        self.confirm_clear = false;
-        self.selected_user = Some(user.clone());
-        self.enrolled_fingers.clear();
-        self.list_fingers_task()
-    }
+            Message::ReplaceVerify => self.on_replace_verify(),
 
-    fn on_device_found(
-        &mut self,
-        device_info: Option<(zbus::zvariant::OwnedObjectPath, DeviceProxy<'static>)>,
-    ) -> Task<cosmic::Action<Message>> {
-        if let Some((path, proxy)) = device_info {
-            self.device_path = Some(Arc::new(path));
-            self.device_proxy = Some(proxy);
-            self.status = fl!("status-device-found");
-            self.busy = false;
+            Message::ReplaceDeleteOld => {
+                // The old finger's page stays active for the whole wizard
+                self.replace = None;
+                self.on_delete()
+            }
 
-            if self.selected_user.is_some() {
-                self.list_fingers_task()
-            } else {
+            Message::ReplaceKeepOld => {
+                self.replace = None;
+                self.status = fl!("replace-done");
                 Task::none()
             }
-        } else {
-            self.device_path = None;
-            self.device_proxy = None;
-            self.status = fl!("status-no-device-found");
-            self.busy = true;
-            Task::none()
-        }
+
+            Message::CleanupPrints(prints) => {
+                if let Some(cleanup) = &mut self.cleanup {
+                    cleanup.set_prints(prints);
+                }
+                Task::none()
+            }
+
+            Message::CleanupToggle(index, selected) => {
+                if let Some(cleanup) = &mut self.cleanup
+                    && !cleanup.deleting
+                {
+                    cleanup.toggle(index, selected);
+                }
+                Task::none()
+            }
+
+            Message::CleanupDelete => self.on_cleanup_delete(),
+
+            Message::CleanupAuthorized(res) => self.on_cleanup_authorized(res),
+
+            Message::CleanupDeleted(deleted, err) => self.on_cleanup_deleted(deleted, err),
+
+            Message::NavMenu(action) => self.on_nav_menu(action),
+
+            Message::RenameInput(name) => {
+                if let Some((_, value)) = &mut self.rename {
+                    *value = name;
+                }
+                Task::none()
+            }
+
+            Message::RenameSave => {
+                if let Some((page, name)) = self.rename.take() {
+                    self.save_finger_name(page, name.trim());
+                }
+                self.update_title()
+            }
+
+            Message::RenameCancel => {
+                self.rename = None;
+                Task::none()
+            }
+
+            Message::ReportSent(res) => {
+                if let Err(why) = res {
+                    tracing::error!(%why, "failed to report change");
+                }
+                Task::none()
+            }
+
+            Message::OperatorLogWritten(res) => match res {
+                Ok(()) if self.showing_context(ContextPage::OperatorLog) => {
+                    self.operator_log_load_task()
+                }
+                Ok(()) => Task::none(),
+                Err(why) => {
+                    tracing::error!(%why, "failed to write the operator log");
+                    self.status = fl!("operator-log-write-failed", error = why);
+                    Task::none()
+                }
+            },
+
+            Message::OperatorLogLoaded(res) => {
+                self.operator_entries = Some(res);
+                Task::none()
+            }
+
+            Message::CleanupCancel => {
+                if self.cleanup.as_ref().is_some_and(|cleanup| !cleanup.deleting) {
+                    self.cleanup = None;
+                    self.status = fl!("cleanup-cancelled");
+                }
+                Task::none()
+            }
+
+            Message::ReplaceCancel => {
+                self.replace = None;
+                self.status = fl!("replace-cancelled");
+                Task::none()
+            }
+
+            Message::WizardStart => {
+                if !self.busy && self.replace.is_none() {
+                    self.confirmation = None;
+                    self.enroll_wizard = Some(EnrollWizard::new(self.shows_user_picker()));
+                }
+                Task::none()
+            }
+
+            Message::WizardNext => self.on_wizard_next(),
+
+            Message::WizardBack => {
+                if let Some(wizard) = &mut self.enroll_wizard {
+                    wizard.back();
+                }
+                Task::none()
+            }
+
+            Message::WizardVerify => self.on_wizard_verify(),
+
+            Message::WizardAnother => {
+                if let Some(wizard) = &mut self.enroll_wizard {
+                    wizard.restart();
+                }
+                Task::none()
+            }
+
+            Message::WizardClose => {
+                self.enroll_wizard = None;
+                Task::none()
+            }
+
+            Message::BatchOpen => {
+                if !self.busy && self.replace.is_none() && !self.enrollment.is_active() {
+                    self.confirmation = None;
+                    self.batch = Some(BatchEnroll::new(&self.enrolled_fingers));
+                }
+                Task::none()
+            }
+
+            Message::BatchToggle(index, selected) => {
+                if let Some(batch) = &mut self.batch {
+                    batch.toggle(index, selected);
+                }
+                Task::none()
+            }
+
+            Message::BatchStart | Message::BatchNext => self.on_batch_next(),
+
+            Message::BatchStop => self.on_batch_stop(),
+
+            Message::SelfTestStart => {
+                if self.busy || self.enrolled_fingers.is_empty() || self.device_path.is_none() {
+                    return Task::none();
+                }
+                self.self_test.start();
+                self.start_verification("any", fl!("self-test-prompt"));
+                Task::none()
+            }
+
+            Message::SelfTestNext => {
+                // The reader is released between scans
+                if self.busy || self.claimed || !self.self_test.next_scan() {
+                    return Task::none();
+                }
+                self.start_verification("any", fl!("self-test-prompt"));
+                Task::none()
+            }
+
+            Message::SelfTestStop => {
+                self.self_test.stop();
+                if self.verifying_finger.is_some() {
+                    return self.on_verify_stop();
+                }
+                Task::none()
+            }
+
+            Message::BatchClose => {
+                if !self.batch.as_ref().is_some_and(BatchEnroll::is_running) {
+                    self.batch = None;
+                }
+                Task::none()
+            }
+
+            Message::ToggleHelp(topic) => {
+                self.help = if self.help == Some(topic) {
+                    None
+                } else {
+                    Some(topic)
+                };
+                Task::none()
+            }
+
+            Message::CloseHelp => {
+                self.help = None;
+                Task::none()
+            }
+
+            Message::ToggleUserMenu => {
+                self.user_menu_open = !self.user_menu_open;
+                Task::none()
+            }
+
+            Message::CloseUserMenu => {
+                self.user_menu_open = false;
+                Task::none()
+            }
+
+            Message::RecoveryRetry => {
+                self.recovery = None;
+                self.on_register()
+            }
+
+            Message::DuplicateCheckComplete(finger, usernames) => {
+                self.busy = false;
+                let conflicts: Vec<UserOption> = self
+                    .users
+                    .iter()
+                    .filter(|u| usernames.iter().any(|name| *name == *u.username))
+                    .cloned()
+                    .collect();
+
+                if conflicts.is_empty() {
+                    self.start_enrollment(&finger);
+                } else {
+                    self.status = fl!("duplicate-title");
+                    self.duplicate_conflict = Some(DuplicateConflict {
+                        finger,
+                        users: conflicts,
+                    });
+                }
+                Task::none()
+            }
+
+            Message::EnrollAnyway => {
+                if let Some(conflict) = self.duplicate_conflict.take() {
+                    self.start_enrollment(&conflict.finger);
+                }
+                Task::none()
+            }
+
+            Message::IdleTick => self.on_idle_tick(),
+
+            Message::ClaimChanged(claimed) => {
+                self.claimed = claimed;
+                if !claimed {
+                    self.enrollment.released();
+                }
+                // Claiming worked, so the reader is no longer held elsewhere
+                if claimed {
+                    self.claim_retry = None;
+                }
+                Task::none()
+            }
+
+            Message::ClaimRetryTick => self.on_claim_retry_tick(),
+
+            Message::ClaimRetryNow => {
+                if let Some(retry) = &mut self.claim_retry {
+                    retry.retry_now(Instant::now());
+                }
+                self.on_claim_retry_tick()
+            }
+
+            Message::DeviceWaitCancel => {
+                if self.device_wait.take().is_some() {
+                    self.busy = false;
+                    self.status = fl!("device-wait-cancelled");
+                }
+                Task::none()
+            }
+
+            Message::ClaimRetryPause(paused) => {
+                if let Some(retry) = &mut self.claim_retry {
+                    retry.set_paused(paused);
+                }
+                Task::none()
+            }
+
+            Message::ClaimProbed(res) => {
+                match res {
+                    Ok(()) => {
+                        self.claim_retry = None;
+                        self.in_use.reset();
+                        self.in_use_guidance = None;
+                        self.status = fl!("reader-available");
+                        if let Some((operation, _)) = self.device_wait.take() {
+                            self.busy = false;
+                            return Task::done(cosmic::Action::App(operation));
+                        }
+                    }
+                    Err(e) => {
+                        if let Some(retry) = &mut self.claim_retry {
+                            retry.failed(Instant::now());
+                        }
+                        if e != AppError::AlreadyInUse {
+                            tracing::debug!(?e, "Claim retry failed");
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            Message::DuplicateDismiss => {
+                self.duplicate_conflict = None;
+                self.status = fl!("status-device-found");
+                Task::none()
+            }
+
+            Message::RecoveryDismiss => {
+                self.recovery = None;
+                self.set_interrupted_enrollment(None);
+                Task::none()
+            }
+
+            Message::FprintdConfLoaded(conf) => {
+                self.fprintd_conf = conf;
+                Task::none()
+            }
+
+            Message::DeleteComplete => {
+                self.status = fl!("deleted-in", secs = self.take_operation_secs());
+                self.busy = false;
+                self.finish_operator_action(None);
+                if let Some(page) = self.nav.data::<Page>(self.nav.active()).copied() {
+                    let finger = page.as_finger_id().map(str::to_string);
+                    self.record_selected_change(ChangeKind::Deleted, finger);
+                    if let Some(finger_id) = page.as_finger_id() {
+                        self.enrolled_fingers.retain(|f| f != finger_id);
+                    } else {
+                        self.enrolled_fingers.clear();
+                    }
+                }
+                Task::none()
+            }
+
+            Message::Delete => self.on_delete(),
+
+            Message::ClearDevice => self.on_clear_device(),
+
+            Message::ClearAuthorized(res) => self.on_clear_authorized(res),
+
+            Message::ClearPreview(preview) => {
+                if self.confirmation == Some(Confirmation::ClearDevice) {
+                    self.clear_preview = Some(preview);
+                }
+                Task::none()
+            }
+
+            Message::ConfirmAction => self.on_confirm_action(),
+
+            Message::AcknowledgeRemote(acknowledged) => {
+                self.remote_acknowledged = acknowledged;
+                Task::none()
+            }
+
+            Message::CancelConfirm => {
+                if self.confirmation.take() == Some(Confirmation::ClearDevice) {
+                    self.clear_preview = None;
+                    self.save_dual_boot_warning_choice();
+                }
+                Task::none()
+            }
+
+            Message::SuppressDualBootWarning(suppress) => {
+                self.suppress_dual_boot_warning = suppress;
+                Task::none()
+            }
+
+            Message::ClearUserDone(username) => {
+                let mut remaining = self.state.unfinished_clear.clone();
+                remaining.retain(|name| *name != username);
+                self.set_unfinished_clear(remaining);
+                self.record_last_change(&username, LastChange::now(ChangeKind::Deleted, None));
+                self.log_operator_action(OperatorAction::Clear, &username, None, None);
+
+                if self
+                    .selected_user
+                    .as_ref()
+                    .is_some_and(|user| *user.username == username)
+                {
+                    self.enrolled_fingers.clear();
+                }
+                Task::none()
+            }
+
+            Message::ClearCancel => {
+                if let Some(cancel) = &self.clear_cancel {
+                    cancel.store(true, Ordering::Relaxed);
+                    self.status = fl!("clear-cancelling");
+                }
+                Task::none()
+            }
+
+            Message::ClearComplete(res) => {
+                self.clear_cancel = None;
+                let remaining = self.state.unfinished_clear.len();
+                // Users that failed or that the clear didn't get to keep their prints
+                for username in self.state.unfinished_clear.clone() {
+                    let error = match &res {
+                        Ok(report) => report
+                            .failed
+                            .iter()
+                            .find(|(name, _)| *name == username)
+                            .map(|(_, e)| e.localized_message()),
+                        Err(e) => Some(e.localized_message()),
+                    };
+                    let error = error.unwrap_or_else(|| fl!("operator-log-stopped"));
+                    self.log_operator_action(OperatorAction::Clear, &username, None, Some(error));
+                }
+                match res {
+                    Ok(_) if remaining == 0 => {
+                        self.status = fl!("device-cleared-in", secs = self.take_operation_secs());
+                        self.enrolled_fingers.clear();
+                    }
+                    Ok(report) if !report.failed.is_empty() => {
+                        self.operation_started = None;
+                        let failures: Vec<String> = report
+                            .failed
+                            .iter()
+                            .map(|(username, e)| {
+                                fl!(
+                                    "clear-user-failed",
+                                    user = username.as_str(),
+                                    error = e.localized_title()
+                                )
+                            })
+                            .collect();
+                        self.status = fl!(
+                            "clear-partial",
+                            cleared = report.cleared.len(),
+                            total = report.cleared.len() + remaining,
+                            failures = i18n::join_list(failures)
+                        );
+                    }
+                    Ok(_) => {
+                        self.operation_started = None;
+                        self.status = fl!("clear-stopped", count = remaining);
+                    }
+                    Err(e) => {
+                        self.operation_started = None;
+                        self.status = e.localized_message();
+                    }
+                }
+                self.busy = false;
+                self.refresh_overview()
+            }
+
+            Message::ClearResume => {
+                if self.busy {
+                    return Task::none();
+                }
+                // Asked again, as the clear may have stopped in an earlier session
+                let users = self.state.unfinished_clear.clone();
+                self.authorize_clear(Confirmation::ClearResume { users })
+            }
+
+            Message::ClearDismissResume => {
+                self.set_unfinished_clear(Vec::new());
+                Task::none()
+            }
+
+            Message::Register => self.on_register(),
+
+            Message::OpenRepositoryUrl => {
+                let _ = open::that_detached(REPOSITORY);
+                Task::none()
+            }
+
+            Message::ToggleContextPage(context_page) => {
+                if self.context_page == context_page {
+                    // Close the context drawer if the toggled context page is the same.
+                    self.core.window.show_context = !self.core.window.show_context;
+                } else {
+                    // Open the context drawer to display the requested context page.
+                    self.context_page = context_page;
+                    self.core.window.show_context = true;
+                }
+                if self.showing_context(ContextPage::OperatorLog) {
+                    return self.operator_log_load_task();
+                }
+                Task::none()
+            }
+
+            Message::UpdateConfig(config) => {
+                let theme_changed = config.app_theme != self.config.app_theme;
+                self.config = config;
+                // The nav bar is put back as it was left once, not on every save
+                if !self.config_loaded {
+                    self.core.nav_bar_set_toggled(!self.config.nav_bar_collapsed);
+                }
+                self.config_loaded = true;
+                session::set_device_locking(self.config.lock_device);
+                debug_log::set_enabled(self.config.debug_log);
+                self.check_upgrade();
+                self.update_shown_users();
+
+                let theme_task = if theme_changed {
+                    cosmic::command::set_theme(self.config.app_theme.theme())
+                } else {
+                    Task::none()
+                };
+                Task::batch(vec![
+                    theme_task,
+                    self.apply_default_user(),
+                    self.apply_last_device(),
+                ])
+            }
+
+            Message::SetAppTheme(index) => {
+                let Some(app_theme) = AppTheme::all().get(index).copied() else {
+                    return Task::none();
+                };
+                self.save_config(|config, handler| config.set_app_theme(handler, app_theme));
+                cosmic::command::set_theme(self.config.app_theme.theme())
+            }
+
+            Message::SetDefaultUser(index) => {
+                // The first entry stands for the current user
+                let username = index
+                    .checked_sub(1)
+                    .and_then(|i| self.users.get(i))
+                    .map(|user| (*user.username).clone())
+                    .unwrap_or_default();
+                self.save_config(|config, handler| config.set_default_user(handler, username));
+                Task::none()
+            }
+
+            Message::SetShowAllUsers(show) => {
+                self.save_config(|config, handler| config.set_show_all_users(handler, show));
+                let previous = self.selected_user.clone();
+                self.update_shown_users();
+                if self.selected_user != previous {
+                    self.enrolled_fingers.clear();
+                    return self.list_fingers_task();
+                }
+                Task::none()
+            }
+
+            Message::SetShowSystemUsers(show) => {
+                self.save_config(|config, handler| config.set_show_system_users(handler, show));
+                let previous = self.selected_user.clone();
+                self.update_shown_users();
+                if self.selected_user != previous {
+                    self.enrolled_fingers.clear();
+                    return self.list_fingers_task();
+                }
+                Task::none()
+            }
+
+            Message::SetLockDevice(lock) => {
+                self.save_config(|config, handler| config.set_lock_device(handler, lock));
+                session::set_device_locking(self.config.lock_device);
+                Task::none()
+            }
+
+            Message::SetOperatorMode(enabled) => {
+                self.save_config(|config, handler| config.set_operator_mode(handler, enabled));
+                Task::none()
+            }
+
+            Message::SetNotifyInBackground(enabled) => {
+                self.save_config(|config, handler| {
+                    config.set_notify_in_background(handler, enabled)
+                });
+                Task::none()
+            }
+
+            Message::ShowAllFingers => {
+                self.save_hidden_fingers(Vec::new());
+                Task::none()
+            }
+
+            Message::SetDebugLog(enabled) => {
+                self.save_config(|config, handler| config.set_debug_log(handler, enabled));
+                debug_log::set_enabled(self.config.debug_log);
+                Task::none()
+            }
+
+            Message::SetIdleRelease(index) => {
+                let Some(secs) = IDLE_RELEASE_CHOICES.get(index).copied() else {
+                    return Task::none();
+                };
+                self.save_config(|config, handler| config.set_idle_release_secs(handler, secs));
+                Task::none()
+            }
+
+            Message::SetConfirmBeforeDelete(confirm) => {
+                self.save_config(|config, handler| {
+                    config.set_confirm_before_delete(handler, confirm)
+                });
+                Task::none()
+            }
+
+            Message::SetRequireVerifyBeforeDelete(require) => {
+                self.save_config(|config, handler| {
+                    config.set_require_verify_before_delete(handler, require)
+                });
+                Task::none()
+            }
+
+            Message::SetRequireAuthBeforeClear(require) => {
+                self.save_config(|config, handler| {
+                    config.set_require_auth_before_clear(handler, require)
+                });
+                Task::none()
+            }
+
+            Message::OpenAccountSettings => Task::perform(
+                async move {
+                    // Settings has no per-user deep link, so open the accounts page
+                    match tokio::process::Command::new(SETTINGS_COMMAND)
+                        .arg(SETTINGS_USERS_PAGE)
+                        .spawn()
+                    {
+                        Ok(mut child) => {
+                            let _ = child.wait().await;
+                            None
+                        }
+                        Err(e) => Some(e.to_string()),
+                    }
+                },
+                |res| match res {
+                    Some(err) => cosmic::Action::App(Message::AccountSettingsFailed(err)),
+                    None => cosmic::Action::None,
+                },
+            ),
+
+            Message::AccountSettingsFailed(err) => {
+                tracing::error!(%err, "failed to launch {SETTINGS_COMMAND}");
+                self.status = fl!("account-settings-failed");
+                Task::none()
+            }
+
+            Message::LaunchUrl(url) => {
+                match open::that_detached(&url) {
+                    Ok(()) => {}
+                    Err(err) => {
+                        eprintln!("failed to open {url:?}: {err}");
+                    }
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// The about page for this app.
+    pub fn about(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let icon = widget::svg(widget::svg::Handle::from_memory(APP_ICON));
+
+        let title = text::title3(fl!("app-title"));
+
+        let hash = env!("VERGEN_GIT_SHA");
+        let short_hash: String = hash.chars().take(7).collect();
+        let date = env!("VERGEN_GIT_COMMIT_DATE");
+
+        let link = widget::button::link(REPOSITORY)
+            .on_press(Message::OpenRepositoryUrl)
+            .padding(0);
+
+        let mut column = widget::column()
+            .push(icon)
+            .push(title)
+            .push(link)
+            .push(
+                widget::button::link(fl!(
+                    "git-description",
+                    hash = short_hash.as_str(),
+                    date = date
+                ))
+                .on_press(Message::LaunchUrl(format!("{REPOSITORY}/commits/{hash}")))
+                .padding(0),
+            );
+
+        if let Some(release) = changelog::release(VERSION) {
+            column = column.push(text::heading(fl!("whats-new", version = release.version)));
+            for note in release.notes {
+                column = column.push(text::body(fl!("whats-new-item", note = note)));
+            }
+        }
+
+        column
+            .align_x(Alignment::Center)
+            .spacing(space_xxs)
+            .into()
+    }
+
+    /// The device info page, showing the reader and fprintd storage settings.
+    pub fn device_info(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_m, space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let reader = widget::settings::section()
+            .title(fl!("device-info-reader"))
+            .add(widget::settings::item(
+                fl!("device-info-name"),
+                text::body(
+                    self.device_name()
+                        .map_or_else(|| fl!("device-info-unknown"), str::to_string),
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("device-info-storage"),
+                text::body(match self.caps().storage {
+                    TemplateStorage::Host => fl!("device-info-storage-host"),
+                    TemplateStorage::Device => fl!("device-info-storage-device"),
+                }),
+            ));
+
+        let mut daemon = widget::settings::section().title(fl!("device-info-fprintd-conf"));
+
+        if let Some(conf) = &self.fprintd_conf {
+            daemon = daemon
+                .add(widget::settings::item(
+                    fl!("device-info-storage-backend"),
+                    text::body(conf.storage_type()),
+                ))
+                .add(widget::settings::item(
+                    fl!("device-info-storage-path"),
+                    text::body(conf.storage_path()),
+                ));
+
+            for warning in conf.warnings() {
+                daemon = daemon.add(
+                    widget::settings::item_row(vec![
+                        icon::from_name("dialog-warning-symbolic").icon().into(),
+                        text::body(warning.localized_message()).into(),
+                    ])
+                    .spacing(space_xxs),
+                );
+            }
+        } else {
+            daemon = daemon.add(text::body(fl!(
+                "device-info-conf-unreadable",
+                path = FPRINTD_CONF_PATH
+            )));
+        }
+
+        let mut column = widget::column().push(reader).push(daemon);
+
+        if let Some(journal) = self.enroll_journal.as_ref().filter(|j| j.is_finished()) {
+            let finger = finger_id_to_localized(&journal.finger);
+            let duration = journal.duration_ms.unwrap_or_default() as f64 / 1000.0;
+            let duration = i18n::format_decimal(duration, 1);
+
+            let session = widget::settings::section()
+                .title(fl!("journal-title"))
+                .add(widget::settings::item(fl!("journal-finger"), text::body(finger)))
+                .add(widget::settings::item(
+                    fl!("journal-outcome"),
+                    text::body(journal.outcome.clone().unwrap_or_default()),
+                ))
+                .add(widget::settings::item(
+                    fl!("journal-duration"),
+                    text::body(fl!("journal-seconds", secs = duration)),
+                ))
+                .add(widget::settings::item(
+                    fl!("journal-retries"),
+                    text::body(journal.retries.to_string()),
+                ))
+                .add(
+                    widget::button::standard(fl!("journal-export"))
+                        .on_press(Message::ExportJournal),
+                );
+            column = column.push(session);
+        }
+
+        column.spacing(space_m).into()
+    }
+
+    /// The activity log page, listing the status messages shown so far with
+    /// the newest first.
+    pub fn activity_log(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_m, space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column().push(text::body(fl!("activity-log-description")));
+
+        if self.status_history.is_empty() {
+            column = column.push(text::body(fl!("activity-log-empty")));
+        } else {
+            let mut entries = widget::settings::section();
+            for (timestamp, status) in self.status_history.entries().rev() {
+                entries = entries.add(
+                    widget::settings::item_row(vec![
+                        text::caption(clock(timestamp)).into(),
+                        text::body(status).into(),
+                    ])
+                    .spacing(space_xxs),
+                );
+            }
+
+            column = column.push(entries).push(
+                widget::button::standard(fl!("activity-log-copy"))
+                    .on_press(Message::CopyActivityLog),
+            );
+        }
+
+        column.spacing(space_m).into()
+    }
+
+    /// The operator log page, listing the recorded actions with the newest first.
+    pub fn operator_log(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_m, space_xxxs, .. } = theme::active().cosmic().spacing;
+
+        let column = widget::column().push(text::body(fl!("operator-log-description")));
+
+        let column = match &self.operator_entries {
+            None => column.push(text::body(fl!("operator-log-loading"))),
+            Some(Err(why)) => column.push(text::body(fl!("operator-log-read-failed", error = why))),
+            Some(Ok(entries)) if entries.is_empty() => {
+                column.push(text::body(fl!("operator-log-empty")))
+            }
+            Some(Ok(entries)) => {
+                let mut section = widget::settings::section();
+                for entry in entries.iter().rev() {
+                    let when = date_time(entry.timestamp);
+                    let result = entry.localized_result();
+                    section = section.add(
+                        widget::column()
+                            .push(text::body(entry.localized_summary()))
+                            .push(text::caption(fl!(
+                                "operator-log-entry",
+                                when = when,
+                                result = result
+                            )))
+                            .spacing(space_xxxs),
+                    );
+                }
+                column.push(section)
+            }
+        };
+
+        column.spacing(space_m).into()
+    }
+
+    /// Opens the about drawer with the release notes once after an upgrade.
+    fn check_upgrade(&mut self) {
+        if self.config.last_seen_version == VERSION {
+            return;
+        }
+
+        // Nothing is new on the very first launch
+        if !self.config.last_seen_version.is_empty() {
+            self.context_page = ContextPage::About;
+            self.core.window.show_context = true;
+        }
+
+        if let Some(handler) = &self.config_handler
+            && let Err(why) = self
+                .config
+                .set_last_seen_version(handler, VERSION.to_string())
+        {
+            tracing::error!(%why, "failed to save config");
+        }
+    }
+
+    fn list_fingers_task(&mut self) -> Task<cosmic::Action<Message>> {
+        let mut task = Task::none();
+        if let (Some(backend), Some(path), Some(user)) =
+            (&self.backend, &self.device_path, &self.selected_user)
+        {
+            let username = (*user.username).clone();
+            let fingers = backend.list_enrolled_fingers((**path).clone(), username.clone());
+            task = Task::perform(
+                async move {
+                    match fingers.await {
+                        Ok(fingers) => Message::EnrolledFingers(username, fingers),
+                        Err(e) => Message::OperationError(
+                            AppError::from(e).with_context("Failed to list fingers"),
+                        ),
+                    }
+                },
+                cosmic::Action::App,
+            );
+        }
+        // The prints of a user no longer selected must not show up
+        fetches::replace(&mut self.fetches.fingers, task)
+    }
+
+    /// Reloads the prints in the background, keeping quiet about failures.
+    fn on_refresh_fingers(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.busy || self.confirmation.is_some() {
+            return Task::none();
+        }
+        let (Some(backend), Some(path), Some(user)) =
+            (&self.backend, &self.device_path, &self.selected_user)
+        else {
+            return Task::none();
+        };
+
+        let username = (*user.username).clone();
+        let fingers = backend.list_enrolled_fingers((**path).clone(), username.clone());
+        let fingers_task = Task::perform(
+            async move {
+                match fingers.await {
+                    Ok(fingers) => Some(Message::EnrolledFingers(username, fingers)),
+                    Err(e) => {
+                        tracing::debug!(%e, "Failed to refresh fingers");
+                        None
+                    }
+                }
+            },
+            |message| message.map_or(cosmic::Action::None, cosmic::Action::App),
+        );
+        let fingers_task = fetches::replace(&mut self.fetches.fingers, fingers_task);
+
+        // Keep the table in place instead of showing it loading again
+        if self.nav.data::<Page>(self.nav.active()) == Some(&Page::Overview) {
+            Task::batch(vec![fingers_task, self.overview_task()])
+        } else {
+            fingers_task
+        }
+    }
+
+    /// Gives up on the running operation, which can't finish anymore.
+    fn abandon_operation(&mut self) {
+        self.finish_operator_action(Some(fl!("operator-log-interrupted")));
+        if let Some(batch) = &mut self.batch
+            && batch.current.is_some()
+        {
+            batch.stopping = true;
+            batch.finish_current(BatchOutcome::Failed);
+        }
+        self.busy = false;
+        self.self_test.stop();
+        if self.enrollment.abandon().is_some() {
+            self.set_interrupted_enrollment(None);
+        }
+        self.verifying_finger = None;
+        self.pending_delete = None;
+        self.pending_cleanup = None;
+        self.identifying = None;
+        self.device_wait = None;
+        self.enroll_retry = None;
+        self.operation_started = None;
+        self.replace = None;
+        if let Some(cleanup) = &mut self.cleanup {
+            cleanup.deleting = false;
+        }
+        if let Some(wizard) = &mut self.enroll_wizard
+            && wizard.step == WizardStep::Enroll
+        {
+            wizard.back();
+        }
+        self.idle_timed_out = false;
+    }
+
+    /// Drops the running operation and keeps trying to reach fprintd again.
+    fn start_reconnecting(&mut self) {
+        if self.enrollment.is_active()
+            && let Some(journal) = &mut self.enroll_journal
+        {
+            journal.fail("service lost");
+        }
+        self.abandon_operation();
+        session::forget_claim();
+        self.reconnect.get_or_insert_with(|| Reconnect::new(Instant::now()));
+        self.status = fl!("reconnecting");
+        self.busy = true;
+    }
+
+    /// fprintd exits by itself when idle, so only going away while it holds the
+    /// reader for us means it crashed or was restarted.
+    fn on_service_changed(&mut self, running: bool) -> Task<cosmic::Action<Message>> {
+        if running {
+            return self.on_reconnect_tick();
+        }
+        if !self.claimed && !self.enrollment.is_active() && self.verifying_finger.is_none() {
+            return Task::none();
+        }
+        tracing::warn!("fprintd went away during an operation");
+        self.start_reconnecting();
+        Task::none()
+    }
+
+    fn on_reconnect_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(reconnect) = &mut self.reconnect else {
+            return Task::none();
+        };
+        if !reconnect.start_attempt(Instant::now()) {
+            return Task::none();
+        }
+
+        match &self.backend {
+            // The old connection is gone, so a new one has to be made
+            None => Task::perform(
+                async move {
+                    match zbus::Connection::system().await {
+                        Ok(conn) => Message::ConnectionReady(conn),
+                        Err(e) => Message::ReconnectFailed(e.to_string()),
+                    }
+                },
+                cosmic::Action::App,
+            ),
+            // Looking up the readers has DBus start fprintd again
+            Some(backend) => {
+                let devices = backend.find_devices();
+                Task::perform(
+                    async move {
+                        match devices.await {
+                            Ok(devices) => Message::DevicesFound(devices),
+                            Err(e) => Message::ReconnectFailed(e.to_string()),
+                        }
+                    },
+                    cosmic::Action::App,
+                )
+            }
+        }
+    }
+
+    fn on_connection_ready(&mut self, conn: zbus::Connection) -> Task<cosmic::Action<Message>> {
+        self.set_backend(Arc::new(ZbusBackend::new(conn)))
+    }
+
+    /// Starts working with `backend`, looking up its readers and users.
+    fn set_backend(&mut self, backend: Arc<dyn FprintBackend>) -> Task<cosmic::Action<Message>> {
+        // Whatever the old connection was fetching is of no use anymore
+        self.fetches.abort_all();
+        self.backend = Some(backend.clone());
+        self.backend_generation += 1;
+        self.status = fl!("status-searching-device");
+
+        let devices = backend.find_devices();
+        let state = backend.service_state();
+        let find_device_task = Task::perform(
+            async move {
+                match devices.await {
+                    Ok(devices) => Message::DevicesFound(devices),
+                    Err(e) => {
+                        let error = match (AppError::from(e), state.await) {
+                            (error, Ok(state)) => error.with_service_state(state),
+                            (AppError::Unknown(_), Err(_)) => AppError::DeviceNotFound,
+                            (error, Err(_)) => error,
+                        };
+                        Message::OperationError(error)
+                    }
+                }
+            },
+            cosmic::Action::App,
+        );
+
+        let location = backend.session_location();
+        let location_task = Task::perform(location, |location| {
+            cosmic::Action::App(Message::SessionLocated(location))
+        });
+
+        // Asked without prompting, so users who may not manage others never
+        // get a password dialog they can't pass
+        let manage_others = backend.may_manage_other_users();
+        let manage_others_task = Task::perform(manage_others, |allowed| {
+            let allowed = allowed.unwrap_or_else(|e| {
+                // Without polkit, leave the checks to fprintd
+                tracing::debug!(%e, "Failed to ask polkit about other users");
+                true
+            });
+            cosmic::Action::App(Message::ManageOthersChecked(allowed))
+        });
+
+        Task::batch(vec![
+            find_device_task,
+            self.list_users_task(),
+            location_task,
+            manage_others_task,
+        ])
+    }
+
+    /// Gets the users from AccountsService.
+    fn list_users_task(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(backend) = &self.backend else {
+            return Task::none();
+        };
+        let users = backend.list_users();
+        let task = Task::perform(
+            async move { Message::UsersFound(users.await) },
+            cosmic::Action::App,
+        );
+        fetches::replace(&mut self.fetches.users, task)
+    }
+
+    fn on_users_found(&mut self, users: Vec<UserOption>) -> Task<cosmic::Action<Message>> {
+        self.users = users;
+        self.default_user_names = std::iter::once(fl!("settings-default-user-current"))
+            .chain(self.users.iter().map(UserOption::to_string))
+            .collect();
+        self.update_shown_users();
+
+        let default_user_task = self.apply_default_user();
+        Task::batch(vec![default_user_task, self.list_fingers_task()])
+    }
+
+    /// Filters the picker to the current user unless all users should be shown,
+    /// and makes sure the selected user is one of them.
+    ///
+    /// System accounts are left out unless asked for, but the current user is
+    /// always kept. Other users are left out when polkit won't allow managing
+    /// their prints.
+    fn update_shown_users(&mut self) {
+        self.shown_users = self
+            .users
+            .iter()
+            .filter(|u| {
+                if self.is_current_user(&u.username) {
+                    return true;
+                }
+                self.may_manage_others
+                    && self.config.show_all_users
+                    && (self.config.show_system_users || !u.system)
+            })
+            .cloned()
+            .collect();
+
+        if self.shown_users.is_empty() {
+            return;
+        }
+
+        // Ensure selected_user is valid
+        if let Some(selected) = &self.selected_user {
+            if let Some(updated_user) = self
+                .shown_users
+                .iter()
+                .find(|u| u.username == selected.username)
+            {
+                // Update realname if found
+                self.selected_user = Some(updated_user.clone());
+            } else {
+                self.selected_user = Some(self.shown_users[0].clone());
+            }
+        } else {
+            self.selected_user = Some(self.shown_users[0].clone());
+        }
+    }
+
+    /// Selects the user asked for at startup, else the configured default
+    /// user, else the one used last, once both the users and the
+    /// configuration are known.
+    fn apply_default_user(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.default_user_applied || !self.config_loaded || self.users.is_empty() {
+            return Task::none();
+        }
+        self.default_user_applied = true;
+
+        if let Some(username) = self.open_user.take() {
+            return self.on_user_dropped(Some(username));
+        }
+        let username = if self.config.default_user.is_empty() {
+            &self.config.last_user
+        } else {
+            &self.config.default_user
+        };
+        // A pending recovery already chose its user
+        if username.is_empty() || self.recovery.is_some() || self.busy {
+            return Task::none();
+        }
+
+        // The account may have been deleted or hidden since
+        let Some(user) = self
+            .shown_users
+            .iter()
+            .find(|u| *u.username == *username)
+            .cloned()
+        else {
+            return Task::none();
+        };
+
+        if self.selected_user.as_ref() == Some(&user) {
+            return Task::none();
+        }
+        self.selected_user = Some(user);
+        self.enrolled_fingers.clear();
+        self.list_fingers_task()
+    }
+
+    /// Applies a change made in the settings drawer and persists it.
+    fn save_config(
+        &mut self,
+        set: impl FnOnce(&mut Config, &cosmic_config::Config) -> Result<bool, cosmic_config::Error>,
+    ) {
+        if let Some(handler) = &self.config_handler
+            && let Err(why) = set(&mut self.config, handler)
+        {
+            tracing::error!(%why, "failed to save config");
+        }
+    }
+
+    /// Saves whether the nav bar was hidden, which the header toggle changes
+    /// without a message of the app.
+    fn save_nav_bar_state(&mut self) {
+        let collapsed = !self.core.nav_bar_active();
+        // Saving before the config is read would overwrite the saved state
+        if self.config_loaded && collapsed != self.config.nav_bar_collapsed {
+            self.save_config(|config, handler| config.set_nav_bar_collapsed(handler, collapsed));
+        }
+    }
+
+    pub fn settings(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_m, .. } = theme::active().cosmic().spacing;
+
+        let theme_index = AppTheme::all()
+            .iter()
+            .position(|theme| *theme == self.config.app_theme);
+        let default_user_index = self
+            .users
+            .iter()
+            .position(|u| *u.username == self.config.default_user)
+            .map_or(0, |i| i + 1);
+        let hidden_count = self.config.hidden_fingers.len();
+        // Left unselected when set to something else in the config file
+        let idle_release_index = IDLE_RELEASE_CHOICES
+            .iter()
+            .position(|&secs| secs == self.config.idle_release_secs);
+
+        let general = widget::settings::section()
+            .title(fl!("settings-general"))
+            .add(widget::settings::item(
+                fl!("settings-theme"),
+                widget::dropdown(&self.theme_names, theme_index, Message::SetAppTheme),
+            ))
+            .add(widget::settings::item(
+                fl!("settings-default-user"),
+                widget::dropdown(
+                    &self.default_user_names,
+                    Some(default_user_index),
+                    Message::SetDefaultUser,
+                ),
+            ))
+            .add(
+                widget::settings::item::builder(fl!("settings-show-all-users"))
+                    .description(fl!("settings-show-all-users-description"))
+                    .toggler(self.config.show_all_users, Message::SetShowAllUsers),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-show-system-users"))
+                    .description(fl!("settings-show-system-users-description"))
+                    .toggler(self.config.show_system_users, Message::SetShowSystemUsers),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-lock-device"))
+                    .description(fl!("settings-lock-device-description"))
+                    .toggler(self.config.lock_device, Message::SetLockDevice),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-notify"))
+                    .description(fl!("settings-notify-description"))
+                    .toggler(
+                        self.config.notify_in_background,
+                        Message::SetNotifyInBackground,
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-hidden-fingers"))
+                    .description(fl!("settings-hidden-fingers-description", count = hidden_count))
+                    .control(
+                        widget::button::standard(fl!("settings-show-all-fingers"))
+                            .on_press_maybe((hidden_count > 0).then_some(Message::ShowAllFingers)),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-idle-release"))
+                    .description(fl!("settings-idle-release-description"))
+                    .control(widget::dropdown(
+                        &self.idle_release_names,
+                        idle_release_index,
+                        Message::SetIdleRelease,
+                    )),
+            );
+
+        let deleting = widget::settings::section()
+            .title(fl!("settings-deleting"))
+            .add(
+                widget::settings::item::builder(fl!("settings-confirm-before-delete"))
+                    .toggler(self.config.confirm_before_delete, Message::SetConfirmBeforeDelete),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-require-verify-before-delete"))
+                    .description(fl!("settings-require-verify-before-delete-description"))
+                    .toggler(
+                        self.config.require_verify_before_delete,
+                        Message::SetRequireVerifyBeforeDelete,
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-require-auth-before-clear"))
+                    .description(fl!("settings-require-auth-before-clear-description"))
+                    .toggler(
+                        self.config.require_auth_before_clear,
+                        Message::SetRequireAuthBeforeClear,
+                    ),
+            );
+
+        let operator_log_path = operator_log::log_path()
+            .map_or_else(|| fl!("log-no-path"), |path| path.display().to_string());
+        let administration = widget::settings::section()
+            .title(fl!("settings-administration"))
+            .add(
+                widget::settings::item::builder(fl!("settings-operator-mode"))
+                    .description(fl!(
+                        "settings-operator-mode-description",
+                        path = operator_log_path
+                    ))
+                    .toggler(self.config.operator_mode, Message::SetOperatorMode),
+            );
+
+        let debug_log_path = debug_log::log_path()
+            .map_or_else(|| fl!("log-no-path"), |path| path.display().to_string());
+        let troubleshooting = widget::settings::section()
+            .title(fl!("settings-troubleshooting"))
+            .add(
+                widget::settings::item::builder(fl!("settings-debug-log"))
+                    .description(fl!("settings-debug-log-description", path = debug_log_path))
+                    .toggler(self.config.debug_log, Message::SetDebugLog),
+            );
+
+        widget::column()
+            .push(general)
+            .push(deleting)
+            .push(administration)
+            .push(troubleshooting)
+            .spacing(space_m)
+            .into()
+    }
+
+    /// Shows the page and user of `target`. A user asked for at startup is
+    /// selected once the users are known.
+    fn on_open(&mut self, target: OpenTarget) -> Task<cosmic::Action<Message>> {
+        let mut tasks = Vec::new();
+        // The page first, as switching users may wait for polkit
+        if let Some(page) = target.page
+            && let Some(id) = self.nav_id(page)
+        {
+            tasks.push(self.on_nav_select(id));
+        }
+        if let Some(username) = target.user {
+            if self.default_user_applied {
+                tasks.push(self.on_user_dropped(Some(username)));
+            } else {
+                self.open_user = Some(username);
+            }
+        }
+        Task::batch(tasks)
+    }
+
+    /// Switches to a user dragged onto the window, if they can be chosen.
+    fn on_user_dropped(&mut self, username: Option<String>) -> Task<cosmic::Action<Message>> {
+        let Some(username) = username else {
+            self.status = fl!("user-drop-unreadable");
+            return Task::none();
+        };
+
+        if let Some(user) = self.shown_users.iter().find(|u| *u.username == username) {
+            return self.on_user_selected(user.clone());
+        }
+
+        self.status = if self.users.iter().any(|u| *u.username == username) {
+            fl!("user-drop-hidden", user = username)
+        } else {
+            fl!("user-drop-unknown", user = username)
+        };
+        Task::none()
+    }
+
+    /// Runs the step offered next to the last error, which it replaces.
+    fn on_error_action(&mut self, action: ErrorAction) -> Task<cosmic::Action<Message>> {
+        let Some((_, retry)) = self.last_error.take() else {
+            return Task::none();
+        };
+        if self.busy {
+            return Task::none();
+        }
+
+        match action {
+            ErrorAction::Retry => Task::done(cosmic::Action::App(retry)),
+            ErrorAction::Troubleshoot => Task::done(cosmic::Action::App(Message::LaunchUrl(
+                TROUBLESHOOTING_URL.to_string(),
+            ))),
+            ErrorAction::StartDaemon => {
+                let Some(backend) = &self.backend else {
+                    return Task::none();
+                };
+                self.status = fl!("error-action-starting-daemon");
+                let start = backend.start_service();
+                Task::perform(
+                    async move {
+                        Message::StartDaemonComplete(start.await.map_err(|e| e.to_string()))
+                    },
+                    cosmic::Action::App,
+                )
+            }
+            ErrorAction::RestartDaemon => {
+                self.status = fl!("error-action-starting-daemon");
+                Task::perform(
+                    async move { Message::StartDaemonComplete(restart_fprintd().await) },
+                    cosmic::Action::App,
+                )
+            }
+            ErrorAction::Authenticate => self.authenticate(),
+        }
+    }
+
+    fn on_user_selected(&mut self, user: UserOption) -> Task<cosmic::Action<Message>> {
+        self.user_menu_open = false;
+        if self.busy || self.replace.is_some() || self.selection_locked() {
+            return Task::none();
+        }
+        self.confirmation = None;
+        self.duplicate_conflict = None;
+        self.cleanup = None;
+        self.permission_denied = false;
+        self.last_error = None;
+        self.remember_user(&user);
+        self.selected_user = Some(user.clone());
+        self.enrolled_fingers.clear();
+
+        // fprintd refuses to touch the prints of other users without polkit's consent
+        if !self.authorized_for_others && !self.is_current_user(&user.username) {
+            return self.authenticate();
+        }
+        self.list_fingers_task()
+    }
+
+    /// Asks polkit for the right to manage the prints of other users.
+    fn authenticate(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(backend) = self.backend.clone() else {
+            return Task::none();
+        };
+
+        self.busy = true;
+        self.status = fl!("authenticating");
+        let authorized = backend.authorize_other_users();
+        Task::perform(
+            async move {
+                Message::AuthenticationComplete(authorized.await.map_err(AppError::from))
+            },
+            cosmic::Action::App,
+        )
+    }
+
+    fn on_devices_found(&mut self, devices: Vec<DeviceOption>) -> Task<cosmic::Action<Message>> {
+        self.devices = devices;
+
+        let removed = self
+            .device_path
+            .as_ref()
+            .is_some_and(|path| !self.devices.iter().any(|d| d.path == *path));
+        if removed {
+            self.forget_device();
+        }
+
+        // Keep the current device if it is still around, else prefer the one used last
+        self.last_device_applied |= self.config_loaded;
+        let device = self
+            .devices
+            .iter()
+            .find(|d| Some(&d.path) == self.device_path.as_ref())
+            .or_else(|| self.last_device())
+            .or_else(|| self.devices.first())
+            .cloned();
+
+        let task = if let Some(device) = device {
+            self.select_device(device)
+        } else {
+            self.device_path = None;
+            self.device = None;
+            self.status = fl!("status-no-device-found");
+            self.busy = true;
+            Task::none()
+        };
+        if removed {
+            self.status = fl!("device-removed");
+        }
+        task
+    }
+
+    /// Drops what was going on with a reader that was unplugged. fprintd took
+    /// its claim along with it.
+    fn forget_device(&mut self) {
+        tracing::info!("The selected reader was removed");
+        if self.busy {
+            self.abandon_operation();
+        }
+        session::forget_claim();
+        self.device_path = None;
+        self.device = None;
+        self.enrolled_fingers.clear();
+        self.cleanup = None;
+    }
+
+    /// The reader used last, if it is still plugged in.
+    fn last_device(&self) -> Option<&DeviceOption> {
+        if self.config.last_device.is_empty() {
+            return None;
+        }
+        self.devices
+            .iter()
+            .find(|d| d.path.as_str() == self.config.last_device)
+    }
+
+    /// Switches to the reader used last once the configuration is known, if
+    /// the readers were found before it.
+    fn apply_last_device(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.last_device_applied || !self.config_loaded || self.devices.is_empty() {
+            return Task::none();
+        }
+        self.last_device_applied = true;
+
+        if self.recovery.is_some()
+            || self.enrollment.is_active()
+            || self.verifying_finger.is_some()
+        {
+            return Task::none();
+        }
+        match self.last_device() {
+            Some(device) if Some(&device.path) != self.device_path.as_ref() => {
+                let device = device.clone();
+                self.select_device(device)
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Keeps the reader picked by hand to select it again on the next start.
+    fn remember_device(&mut self, device: &DeviceOption) {
+        let path = device.path.to_string();
+        if self.config.last_device == path {
+            return;
+        }
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_last_device(handler, path) {
+                tracing::error!(%why, "failed to save the last reader");
+            }
+        } else {
+            self.config.last_device = path;
+        }
+    }
+
+    /// Keeps the user picked by hand to select them again on the next start.
+    fn remember_user(&mut self, user: &UserOption) {
+        if *self.config.last_user == *user.username {
+            return;
+        }
+        let username = (*user.username).clone();
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_last_user(handler, username) {
+                tracing::error!(%why, "failed to save the last user");
+            }
+        } else {
+            self.config.last_user = username;
+        }
+    }
+
+    fn select_device(&mut self, device: DeviceOption) -> Task<cosmic::Action<Message>> {
+        self.device_path = Some(device.path.clone());
+        self.device = Some(device);
+        self.status = fl!("status-device-found");
+        self.busy = false;
+        self.enrolled_fingers.clear();
+        Task::batch(vec![self.list_fingers_task(), self.probe_capabilities()])
+    }
+
+    /// Asks fprintd which optional methods the selected reader has.
+    fn probe_capabilities(&mut self) -> Task<cosmic::Action<Message>> {
+        self.capabilities = Capabilities::default();
+        let (Some(backend), Some(path)) = (&self.backend, &self.device_path) else {
+            return Task::none();
+        };
+        let probe = backend.probe_capabilities((**path).clone());
+        Task::perform(probe, |capabilities| {
+            let capabilities = capabilities.unwrap_or_else(|e| {
+                // Leave the errors to the calls themselves
+                tracing::debug!(%e, "Failed to probe what fprintd offers");
+                Capabilities::default()
+            });
+            cosmic::Action::App(Message::CapabilitiesProbed(capabilities))
+        })
+    }
+
+    fn on_enroll_status(&mut self, status: String, done: bool) -> Task<cosmic::Action<Message>> {
+        self.last_activity = Instant::now();
+        if let Some(journal) = &mut self.enroll_journal {
+            journal.record(&status, done);
+        }
+        if status == "enroll-stage-passed" {
+            self.enroll_progress += 1;
+        }
+        self.enroll_retry = RetryReason::from_status(&status);
+        self.status = enroll_message(&status, self.status_context());
+
+        if done {
+            self.busy = false;
+            let finger = self.enrollment.finish(self.claimed);
+            self.operation_started = None;
+            self.set_interrupted_enrollment(None);
+            self.finish_batch_finger(&status);
+
+            let timed_out = std::mem::take(&mut self.idle_timed_out);
+            if timed_out {
+                self.status = fl!("idle-released");
+            }
+            let notification =
+                self.enroll_notification_task(&status, finger.as_deref(), timed_out);
+
+            let completed = status == "enroll-completed";
+            self.finish_operator_action((!completed).then(|| status.clone()));
+
+            if completed {
+                let finger = finger.map(|finger| (*finger).clone());
+                self.record_selected_change(ChangeKind::Enrolled, finger);
+                if let Some(wizard) = &mut self.replace
+                    && wizard.step == ReplaceStep::Enroll
+                {
+                    wizard.step = ReplaceStep::Verify;
+                }
+                if let Some(wizard) = &mut self.enroll_wizard
+                    && wizard.step == WizardStep::Enroll
+                {
+                    wizard.next();
+                }
+                return Task::batch(vec![notification, self.list_fingers_task()]);
+            }
+
+            // A failed or cancelled enrollment ends the replacement
+            self.replace = None;
+            if let Some(wizard) = &mut self.enroll_wizard
+                && wizard.step == WizardStep::Enroll
+            {
+                wizard.back();
+            }
+
+            if status == "enroll-data-full" {
+                return Task::batch(vec![notification, self.start_cleanup()]);
+            }
+            return notification;
+        }
+        Task::none()
+    }
+
+    /// Tells the desktop how an enrollment ended while the window was in the
+    /// background.
+    fn enroll_notification_task(
+        &self,
+        status: &str,
+        finger: Option<&String>,
+        timed_out: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        let (Some(notification), Some(connection)) = (
+            self.enroll_notification(status, finger, timed_out),
+            self.progress_bus.clone(),
+        ) else {
+            return Task::none();
+        };
+        let notify = notification::notify(connection, fl!("app-title"), Self::APP_ID, notification);
+        Task::perform(notify, |()| cosmic::Action::None)
+    }
+
+    /// What to notify about an enrollment of `finger` that ended with
+    /// `status`. Nothing while the window is in front, and nothing for an
+    /// enrollment stopped from the app rather than by the idle timeout.
+    fn enroll_notification(
+        &self,
+        status: &str,
+        finger: Option<&String>,
+        timed_out: bool,
+    ) -> Option<Notification> {
+        if self.window_focused || !self.config.notify_in_background {
+            return None;
+        }
+        if status == "enroll-cancelled" && !timed_out {
+            return None;
+        }
+        let finger = self.finger_name(Page::from_finger_id(finger?)?);
+        let summary = if status == "enroll-completed" {
+            fl!("notification-enrolled", finger = finger)
+        } else {
+            fl!("notification-enroll-failed", finger = finger)
+        };
+        Some(Notification {
+            summary,
+            body: self.status.clone(),
+        })
+    }
+
+    /// Lists the prints on the reader so some can be deleted to make room.
+    fn start_cleanup(&mut self) -> Task<cosmic::Action<Message>> {
+        self.cleanup = Some(StorageCleanup::default());
+        self.remote_acknowledged = false;
+        let (Some(path), Some(backend)) = (&self.device_path, &self.backend) else {
+            return Task::done(cosmic::Action::App(Message::CleanupPrints(Vec::new())));
+        };
+        // Prints of other users can only be listed once polkit agreed
+        let usernames = self
+            .users
+            .iter()
+            .filter(|user| self.authorized_for_others || self.is_current_user(&user.username))
+            .map(|user| (*user.username).clone())
+            .collect();
+        let prints = backend.list_fingers_by_user((**path).clone(), usernames);
+
+        Task::perform(
+            async move { Message::CleanupPrints(prints.await) },
+            cosmic::Action::App,
+        )
+    }
+
+    /// Deletes the prints picked in the cleanup, behind the same checks as
+    /// deleting from a finger's page.
+    fn on_cleanup_delete(&mut self) -> Task<cosmic::Action<Message>> {
+        // Away from the reader, the extra box has to be ticked first
+        if self.busy || (!self.session_location.is_local() && !self.remote_acknowledged) {
+            return Task::none();
+        }
+        let prints = self
+            .cleanup
+            .as_ref()
+            .map(StorageCleanup::selected)
+            .unwrap_or_default();
+        if prints.is_empty() {
+            return Task::none();
+        }
+        if !self.capabilities.delete_finger {
+            self.status = fl!("delete-unsupported");
+            return Task::none();
+        }
+
+        // fprintd refuses to touch the prints of other users without polkit's consent
+        if !self.authorized_for_others
+            && prints
+                .iter()
+                .any(|print| !self.is_current_user(&print.username))
+        {
+            return self.authorize_cleanup(None);
+        }
+
+        // The box ticked for a remote session carries over to the dialog
+        if self.config.confirm_before_delete {
+            self.confirmation = Some(Confirmation::Cleanup { prints });
+            return Task::none();
+        }
+        self.cleanup_confirmed(prints)
+    }
+
+    /// Deletes `prints` once the user agreed, scanning a finger first if
+    /// required. A scan only vouches for the selected user, so the prints of
+    /// anyone else take polkit's consent again instead.
+    fn cleanup_confirmed(&mut self, prints: Vec<StoredPrint>) -> Task<cosmic::Action<Message>> {
+        if !self.config.require_verify_before_delete {
+            return self.delete_cleanup(prints);
+        }
+
+        let selected = self.selected_user.as_ref().map(|user| user.username.clone());
+        if prints
+            .iter()
+            .all(|print| selected.as_deref() == Some(&print.username))
+        {
+            if self.device_path.is_some() {
+                self.pending_cleanup = Some(prints);
+                self.start_verification("any", fl!("delete-verify-prompt"));
+            }
+            return Task::none();
+        }
+        self.authorize_cleanup(Some(prints))
+    }
+
+    /// Asks polkit before the cleanup deletes prints of other users. Once it
+    /// agrees, `prints` are deleted, or the selection is checked again if
+    /// there are none yet.
+    fn authorize_cleanup(
+        &mut self,
+        prints: Option<Vec<StoredPrint>>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(backend) = self.backend.clone() else {
+            return Task::none();
+        };
+
+        self.pending_cleanup = prints;
+        self.busy = true;
+        self.status = fl!("authenticating");
+        let authorized = backend.authorize_other_users();
+        Task::perform(
+            async move { Message::CleanupAuthorized(authorized.await.map_err(AppError::from)) },
+            cosmic::Action::App,
+        )
+    }
+
+    fn on_cleanup_authorized(
+        &mut self,
+        res: Result<bool, AppError>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.busy = false;
+        let prints = self.pending_cleanup.take();
+        match res {
+            Ok(true) => {
+                self.authorized_for_others = true;
+                self.status = fl!("authenticated");
+                match prints {
+                    Some(prints) => self.delete_cleanup(prints),
+                    None => self.on_cleanup_delete(),
+                }
+            }
+            Ok(false) => {
+                self.status = fl!("authentication-failed");
+                Task::none()
+            }
+            // Unlike browsing other users, deleting can't be left to fprintd
+            Err(err) => Task::done(cosmic::Action::App(Message::OperationError(err))),
+        }
+    }
+
+    /// Deletes the cleanup's `prints`, one after another.
+    fn delete_cleanup(&mut self, prints: Vec<StoredPrint>) -> Task<cosmic::Action<Message>> {
+        let (Some(cleanup), Some(path), Some(backend)) =
+            (&mut self.cleanup, &self.device_path, &self.backend)
+        else {
+            return Task::none();
+        };
+
+        cleanup.deleting = true;
+        self.busy = true;
+        self.status = fl!("cleanup-deleting");
+        self.operation_started = Some(Instant::now());
+        let path = (**path).clone();
+        let backend = backend.clone();
+
+        Task::perform(
+            async move {
+                let mut deleted = Vec::new();
+                for print in prints {
+                    let delete = backend.delete_finger(
+                        path.clone(),
+                        print.finger.clone(),
+                        print.username.clone(),
+                    );
+                    if let Err(e) = delete.await {
+                        return Message::CleanupDeleted(deleted, Some(AppError::from(e)));
+                    }
+                    deleted.push(print);
+                }
+                Message::CleanupDeleted(deleted, None)
+            },
+            cosmic::Action::App,
+        )
+    }
+
+    /// Records what the cleanup deleted and enrolls the finger again.
+    fn on_cleanup_deleted(
+        &mut self,
+        deleted: Vec<StoredPrint>,
+        err: Option<AppError>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.busy = false;
+        self.operation_started = None;
+
+        let selected = self.selected_user.as_ref().map(|user| user.username.clone());
+        for print in &deleted {
+            let change = LastChange::now(ChangeKind::Deleted, Some(print.finger.clone()));
+            self.record_last_change(&print.username, change);
+            let finger = Some(print.finger.clone());
+            self.log_operator_action(OperatorAction::Delete, &print.username, finger, None);
+            if selected.as_deref() == Some(&print.username) {
+                self.enrolled_fingers.retain(|finger| *finger != print.finger);
+            }
+        }
+
+        if let Some(err) = err {
+            if let Some(cleanup) = &mut self.cleanup {
+                cleanup.remove(&deleted);
+                cleanup.deleting = false;
+            }
+            return Task::done(cosmic::Action::App(Message::OperationError(err)));
+        }
+
+        self.cleanup = None;
+        Task::done(cosmic::Action::App(Message::Register))
+    }
+
+    fn on_verify_status(&mut self, status: String, done: bool) -> Task<cosmic::Action<Message>> {
+        self.last_activity = Instant::now();
+        self.status = verify_message(&status, self.status_context());
+        self.self_test.record(&status, done);
+
+        if done {
+            self.busy = false;
+            let finger = self.verifying_finger.take();
+
+            if std::mem::take(&mut self.idle_timed_out) {
+                self.status = fl!("idle-released");
+            }
+
+            if let Some(selected) = self.identifying.take() {
+                return self.on_identified(&status, selected);
+            }
+
+            if let Some(page) = self.pending_delete.take() {
+                return match status.as_str() {
+                    "verify-match" => self.delete_page(page),
+                    "verify-cancelled" => Task::none(),
+                    _ => {
+                        self.status = fl!("delete-verify-failed");
+                        Task::none()
+                    }
+                };
+            }
+
+            if let Some(prints) = self.pending_cleanup.take() {
+                return match status.as_str() {
+                    "verify-match" => self.delete_cleanup(prints),
+                    "verify-cancelled" => Task::none(),
+                    _ => {
+                        self.status = fl!("delete-verify-failed");
+                        Task::none()
+                    }
+                };
+            }
+
+            if status == "verify-match" {
+                let finger = finger
+                    .filter(|finger| finger.as_str() != "any")
+                    .map(|finger| (*finger).clone());
+                self.record_selected_change(ChangeKind::Verified, finger);
+            }
+
+            if status == "verify-match"
+                && let Some(wizard) = &mut self.replace
+                && wizard.step == ReplaceStep::Verify
+            {
+                wizard.step = ReplaceStep::ConfirmDelete;
+            }
+
+            if status == "verify-match"
+                && let Some(wizard) = &mut self.enroll_wizard
+                && wizard.step == WizardStep::Verify
+            {
+                wizard.next();
+            }
+        }
+        Task::none()
+    }
+
+    /// Tries to claim the reader again once the backoff allows it.
+    fn on_claim_retry_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        let now = Instant::now();
+        if let Some((_, deadline)) = &self.device_wait
+            && now >= *deadline
+        {
+            return self.give_up_device_wait();
+        }
+        // Only the wait for the reader keeps probing while busy
+        if self.busy && self.device_wait.is_none() {
+            return Task::none();
+        }
+
+        if let Some(retry) = &mut self.claim_retry
+            && let (Some(backend), Some(path), Some(user)) =
+                (&self.backend, &self.device_path, &self.selected_user)
+            && retry.start_attempt(now)
+        {
+            let probe = backend.probe_claim((**path).clone(), (*user.username).clone());
+            return Task::perform(
+                async move { Message::ClaimProbed(probe.await.map_err(AppError::from)) },
+                cosmic::Action::App,
+            );
+        }
+        Task::none()
+    }
+
+    /// Fails the operation that waited for the reader the configured time.
+    fn give_up_device_wait(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some((operation, _)) = self.device_wait.take() else {
+            return Task::none();
+        };
+        self.busy = false;
+        self.status = AppError::AlreadyInUse.localized_message();
+        self.last_error = Some((AppError::AlreadyInUse, operation));
+        Task::none()
+    }
+
+    /// Stops an enrollment or verification that made no progress for the configured time.
+    fn on_idle_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        let timeout = Duration::from_secs(self.config.idle_release_secs);
+        if !self.claimed || timeout.is_zero() || self.last_activity.elapsed() < timeout {
+            return Task::none();
+        }
+
+        // Wait a full period again in case stopping takes a while
+        self.last_activity = Instant::now();
+
+        if self.enrollment.is_active() {
+            self.idle_timed_out = true;
+            self.on_enroll_stop()
+        } else if self.verifying_finger.is_some() {
+            self.idle_timed_out = true;
+            self.on_verify_stop()
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Stops the running scan and releases the reader before exiting, giving
+    /// up on fprintd after [`EXIT_RELEASE_TIMEOUT`].
+    fn on_exit(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.exiting {
+            return Task::none();
+        }
+        self.fetches.abort_all();
+        if let Some(cancel) = &self.clear_cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        let stop = match (&self.device_path, &self.backend) {
+            (Some(path), Some(backend)) if self.enrollment.is_active() => {
+                backend.enroll_stop((**path).clone())
+            }
+            (Some(path), Some(backend)) if self.verifying_finger.is_some() => {
+                backend.verify_stop((**path).clone())
+            }
+            _ => return cosmic::iced::exit(),
+        };
+
+        self.exiting = true;
+        self.busy = true;
+        self.status = fl!("exit-releasing");
+        Task::perform(
+            async move {
+                match tokio::time::timeout(EXIT_RELEASE_TIMEOUT, stop).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!(%e, "Failed to release the reader on exit"),
+                    Err(_) => tracing::warn!("fprintd did not release the reader in time"),
+                }
+                Message::ExitReady
+            },
+            cosmic::Action::App,
+        )
+    }
+
+    fn on_verify_stop(&self) -> Task<cosmic::Action<Message>> {
+        if let (Some(path), Some(backend)) = (&self.device_path, &self.backend) {
+            return Task::perform(
+                backend.verify_stop((**path).clone()),
+                |res| match res {
+                    Ok(_) => cosmic::Action::App(Message::VerifyStatus(
+                        "verify-cancelled".to_string(),
+                        true,
+                    )),
+                    Err(e) => cosmic::Action::App(Message::OperationError(AppError::from(e))),
+                },
+            );
+        }
+        Task::none()
+    }
+
+    fn on_replace_start(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(page) = self.nav.data::<Page>(self.nav.active()).copied()
+            && let Some(finger_id) = page.as_finger_id()
+            && self.enrolled_fingers.iter().any(|f| f == finger_id)
+        {
+            self.confirmation = None;
+            let wizard = ReplaceWizard::new(page, &self.enrolled_fingers);
+            if wizard.candidates.is_empty() {
+                self.status = fl!("replace-no-candidates");
+                return Task::none();
+            }
+            self.status = fl!("replace-choose", finger = page.localized_name());
+            self.replace = Some(wizard);
+        }
+        Task::none()
+    }
+
+    fn on_replace_continue(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(wizard) = &mut self.replace
+            && wizard.step == ReplaceStep::ChooseFinger
+            && let Some(new) = wizard.new
+            && let Some(finger_id) = new.as_finger_id()
+            && self.device_path.is_some()
+            && self.selected_user.is_some()
+        {
+            wizard.step = ReplaceStep::Enroll;
+            self.start_enrollment(finger_id);
+        }
+        Task::none()
+    }
+
+    fn on_replace_verify(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(wizard) = &self.replace
+            && wizard.step == ReplaceStep::Verify
+            && let Some(finger_id) = wizard.new.and_then(|p| p.as_finger_id())
+        {
+            let prompt = fl!(
+                "replace-verify-prompt",
+                finger = wizard.new.map(|p| p.localized_name()).unwrap_or_default()
+            );
+            self.start_verification(finger_id, prompt);
+        }
+        Task::none()
+    }
+
+    /// Whether the enrollment wizard is past choosing the user and finger, or
+    /// a batch enrollment or the self-test is running.
+    fn selection_locked(&self) -> bool {
+        self.enroll_wizard
+            .as_ref()
+            .is_some_and(EnrollWizard::locks_selection)
+            || self.batch.as_ref().is_some_and(BatchEnroll::is_running)
+            || self.self_test.running
+    }
+
+    /// Message of the buttons stopping the enrollment, `None` once stopping
+    /// was asked for.
+    fn stop_enrollment(&self) -> Option<Message> {
+        (self.enrollment.is_active() && !self.enrollment.is_cancelling())
+            .then_some(Message::EnrollStop)
+    }
+
+    fn on_wizard_next(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(wizard) = &mut self.enroll_wizard else {
+            return Task::none();
+        };
+
+        match wizard.step {
+            WizardStep::ChooseUser if self.selected_user.is_none() || self.busy => Task::none(),
+            WizardStep::ChooseFinger => {
+                // Preselect the finger shown in the navigation
+                if wizard.finger.is_none()
+                    && let Some(page) = self.nav.data::<Page>(self.nav.active()).copied()
+                {
+                    wizard.select_finger(page, &self.enrolled_fingers);
+                }
+                wizard.next();
+                Task::none()
+            }
+            WizardStep::Instructions => {
+                if self.busy || self.device_path.is_none() {
+                    return Task::none();
+                }
+                wizard.next();
+                // Enrolls the finger of the active page, which follows the wizard's choice
+                self.on_register()
+            }
+            _ => {
+                wizard.next();
+                Task::none()
+            }
+        }
+    }
+
+    /// Enrolls the next finger of the batch, once the reader is free again.
+    fn on_batch_next(&mut self) -> Task<cosmic::Action<Message>> {
+        // The last finger's claim may still be on its way back
+        if self.busy || self.claimed || self.enrollment.is_active() {
+            return Task::none();
+        }
+        let Some(batch) = &mut self.batch else {
+            return Task::none();
+        };
+        if !batch.started && batch.selected().next().is_none() {
+            return Task::none();
+        }
+        let Some(page) = batch.next_finger() else {
+            return Task::none();
+        };
+
+        if let Some(id) = self.nav_id(page) {
+            self.nav.activate(id);
+        }
+        if let Some(finger_id) = page.as_finger_id() {
+            self.start_enrollment(finger_id);
+        }
+        self.update_title()
+    }
+
+    /// Stops the batch, after cancelling the finger being enrolled.
+    fn on_batch_stop(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(batch) = &mut self.batch else {
+            return Task::none();
+        };
+        if batch.current.is_some() && self.enrollment.is_active() {
+            batch.stopping = true;
+            return self.on_enroll_stop();
+        }
+        batch.stop();
+        Task::none()
+    }
+
+    /// Records how the enrollment of the batch's current finger ended.
+    fn finish_batch_finger(&mut self, status: &str) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+        if batch.current.is_none() {
+            return;
+        }
+
+        let outcome = match status {
+            "enroll-completed" => BatchOutcome::Enrolled,
+            "enroll-cancelled" => BatchOutcome::Skipped,
+            _ => BatchOutcome::Failed,
+        };
+        // Nobody is at the reader anymore, or it has no room for more prints
+        if self.idle_timed_out || status == "enroll-data-full" {
+            batch.stopping = true;
+        }
+        batch.finish_current(outcome);
+    }
+
+    fn on_wizard_verify(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(wizard) = &self.enroll_wizard
+            && wizard.step == WizardStep::Verify
+            && let Some(page) = wizard.finger
+            && let Some(finger_id) = page.as_finger_id()
+            && !self.busy
+        {
+            let prompt = fl!("wizard-verify-prompt", finger = page.localized_name());
+            self.start_verification(finger_id, prompt);
+        }
+        Task::none()
+    }
+
+    fn on_verify(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(page) = self.nav.data::<Page>(self.nav.active()).copied()
+            && let Some(finger_id) = page.as_finger_id()
+            && self.enrolled_fingers.iter().any(|f| f == finger_id)
+            && self.device_path.is_some()
+            && self.selected_user.is_some()
+        {
+            let prompt = fl!("verify-prompt", finger = page.localized_name());
+            self.start_verification(finger_id, prompt);
+        }
+        Task::none()
+    }
+
+    /// Verifies against every print of the user, to tell which finger was
+    /// presented.
+    fn on_identify(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(user) = &self.selected_user
+            && !self.enrolled_fingers.is_empty()
+            && self.device_path.is_some()
+        {
+            let prompt = fl!("identify-prompt", user = user.to_string());
+            self.identifying = Some(None);
+            self.start_verification("any", prompt);
+        }
+        Task::none()
+    }
+
+    /// Reports the outcome of an identification, naming the finger when
+    /// fprintd said which one it compared against.
+    ///
+    /// Readers that identify among all prints at once only report `any`.
+    fn on_identified(
+        &mut self,
+        status: &str,
+        selected: Option<String>,
+    ) -> Task<cosmic::Action<Message>> {
+        if status != "verify-match" {
+            return Task::none();
+        }
+        let user = self
+            .selected_user
+            .as_ref()
+            .map(UserOption::to_string)
+            .unwrap_or_default();
+
+        self.status = match selected.as_deref().and_then(Page::from_finger_id) {
+            Some(page) => fl!("identify-match", finger = page.localized_name(), user = user),
+            None => fl!("identify-match-any", user = user),
+        };
+        self.record_selected_change(ChangeKind::Verified, selected);
+        Task::none()
+    }
+
+    /// Name of the selected device as reported by fprintd.
+    fn device_name(&self) -> Option<&str> {
+        self.device
+            .as_ref()
+            .map(|device| device.name.as_str())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// How fingers are presented to the selected device.
+    fn scan_type(&self) -> ScanType {
+        self.device
+            .as_ref()
+            .map_or_else(ScanType::default, |device| device.scan_type)
+    }
+
+    /// Whether the running fprintd can delete what `page` shows.
+    fn can_delete(&self, page: Page) -> bool {
+        if page.as_finger_id().is_some() {
+            self.capabilities.delete_finger
+        } else {
+            self.capabilities.delete_all_fingers
+        }
+    }
+
+    /// What the selected device can do.
+    fn caps(&self) -> DeviceCaps {
+        self.device
+            .as_ref()
+            .map_or_else(DeviceCaps::default, |device| device.caps)
+    }
+
+    /// What the status formatter is told about the running operation: the
+    /// reader's scan type and how long the operation has been going.
+    fn status_context(&self) -> StatusContext {
+        StatusContext {
+            scan_type: self.scan_type(),
+            secs: self
+                .operation_started
+                .map_or(0, |started| started.elapsed().as_secs()),
+        }
+    }
+
+    /// Seconds the finished operation took, with a decimal as deletes are quick.
+    fn take_operation_secs(&mut self) -> String {
+        let secs = self
+            .operation_started
+            .take()
+            .map_or(0.0, |started| started.elapsed().as_secs_f64());
+        i18n::format_decimal(secs, 1)
+    }
+
+    /// Starts the verification subscription for `finger_id`.
+    fn start_verification(&mut self, finger_id: &str, prompt: String) {
+        self.last_activity = Instant::now();
+        self.busy = true;
+        self.verifying_finger = Some(Arc::new(finger_id.to_string()));
+        self.status = prompt;
+    }
+
+    fn on_enroll_stop(&mut self) -> Task<cosmic::Action<Message>> {
+        if let (Some(path), Some(backend)) = (&self.device_path, &self.backend)
+            && self.enrollment.cancel()
+        {
+            return Task::perform(
+                backend.enroll_stop((**path).clone()),
+                |res| match res {
+                    Ok(_) => cosmic::Action::App(Message::EnrollStatus(
+                        "enroll-cancelled".to_string(),
+                        true,
+                    )),
+                    Err(e) => cosmic::Action::App(Message::OperationError(AppError::from(e))),
+                },
+            );
+        }
+        Task::none()
+    }
+
+    fn on_clear_device(&mut self) -> Task<cosmic::Action<Message>> {
+        self.authorize_clear(Confirmation::ClearDevice)
+    }
+
+    /// Asks about clearing the prints of whole users with `confirmation`,
+    /// asking polkit for administrator rights first when the settings
+    /// require it.
+    fn authorize_clear(&mut self, confirmation: Confirmation) -> Task<cosmic::Action<Message>> {
+        // Clearing deletes the prints of every user, which polkit won't allow
+        if !self.may_manage_others {
+            return Task::none();
+        }
+        // Users are cleared finger by finger
+        if !self.capabilities.delete_finger {
+            self.status = fl!("delete-unsupported");
+            return Task::none();
+        }
+        if !self.config.require_auth_before_clear {
+            return self.confirm_clear(confirmation);
+        }
+        let Some(backend) = self.backend.clone() else {
+            return Task::none();
+        };
+
+        self.pending_clear = Some(confirmation);
+        self.busy = true;
+        self.status = fl!("clear-authenticating");
+        let authorized = backend.authorize_other_users();
+        Task::perform(
+            async move { Message::ClearAuthorized(authorized.await.map_err(AppError::from)) },
+            cosmic::Action::App,
+        )
+    }
+
+    fn on_clear_authorized(
+        &mut self,
+        res: Result<bool, AppError>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.busy = false;
+        let confirmation = self.pending_clear.take();
+        match res {
+            Ok(true) => {
+                self.authorized_for_others = true;
+                self.status = fl!("authenticated");
+                confirmation.map_or_else(Task::none, |c| self.confirm_clear(c))
+            }
+            Ok(false) => {
+                self.status = fl!("clear-authentication-failed");
+                Task::none()
+            }
+            // Unlike browsing other users, the required check can't be left to fprintd
+            Err(err) => Task::done(cosmic::Action::App(Message::OperationError(err))),
+        }
+    }
+
+    /// Whether the dialog asks about clearing the prints of whole users.
+    fn confirming_clear(&self) -> bool {
+        self.confirmation
+            .as_ref()
+            .is_some_and(Confirmation::clears_users)
+    }
+
+    /// Asks to go ahead with the clear, showing who clearing the device affects.
+    fn confirm_clear(&mut self, confirmation: Confirmation) -> Task<cosmic::Action<Message>> {
+        let preview = confirmation == Confirmation::ClearDevice;
+        self.confirmation = Some(confirmation);
+        self.remote_acknowledged = false;
+        self.clear_preview = None;
+        if preview {
+            self.clear_preview_task()
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Goes ahead with the action the dialog asked about.
+    fn on_confirm_action(&mut self) -> Task<cosmic::Action<Message>> {
+        // Away from the reader, the dialog stays until the extra box is ticked
+        if !self.session_location.is_local() && !self.remote_acknowledged {
+            return Task::none();
+        }
+        match self.confirmation.take() {
+            Some(Confirmation::ClearDevice) => self.clear_device(),
+            Some(Confirmation::ClearResume { users }) => self.start_clear(users),
+            Some(Confirmation::Delete { page, .. }) => self.delete_confirmed(page),
+            Some(Confirmation::Cleanup { prints }) => self.cleanup_confirmed(prints),
+            None => Task::none(),
+        }
+    }
+
+    fn clear_device(&mut self) -> Task<cosmic::Action<Message>> {
+        self.save_dual_boot_warning_choice();
+        self.clear_preview = None;
+
+        let usernames: Vec<String> = self.users.iter().map(|u| (*u.username).clone()).collect();
+        self.start_clear(usernames)
+    }
+
+    /// Clears the prints of `usernames`, recording who is left so a clear that
+    /// stops midway can be resumed, even after a restart.
+    fn start_clear(&mut self, usernames: Vec<String>) -> Task<cosmic::Action<Message>> {
+        let (Some(path), Some(backend)) = (self.device_path.clone(), self.backend.clone()) else {
+            return Task::none();
+        };
+
+        self.status = fl!("clearing-device");
+        self.busy = true;
+        self.operation_started = Some(Instant::now());
+        self.set_unfinished_clear(usernames.clone());
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.clear_cancel = Some(cancel.clone());
+
+        Task::run(
+            backend.clear_users((*path).clone(), usernames, cancel),
+            cosmic::Action::App,
+        )
+    }
+
+    fn set_unfinished_clear(&mut self, remaining: Vec<String>) {
+        if self.state.unfinished_clear == remaining {
+            return;
+        }
+
+        if let Some(handler) = &self.state_handler {
+            if let Err(why) = self.state.set_unfinished_clear(handler, remaining) {
+                tracing::error!(%why, "failed to save clear progress");
+            }
+        } else {
+            self.state.unfinished_clear = remaining;
+        }
+    }
+
+    /// Remembers a successful change to the prints of the selected user.
+    fn record_selected_change(&mut self, kind: ChangeKind, finger: Option<String>) {
+        if let Some(user) = self.selected_user.clone() {
+            self.record_last_change(&user.username, LastChange::now(kind, finger));
+        }
+    }
+
+    fn record_last_change(&mut self, username: &str, change: LastChange) {
+        if self.report_hook().is_enabled() {
+            let device = self.device_name().map(str::to_string);
+            self.pending_reports.push(OperationReport::new(username, &change, device));
+        }
+
+        let mut changes = self.config.last_changes.clone();
+        changes.insert(username.to_string(), change);
+
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_last_changes(handler, changes) {
+                tracing::error!(%why, "failed to save last change");
+            }
+        } else {
+            self.config.last_changes = changes;
+        }
+    }
+
+    /// Where completed changes are reported, as set by the administrator.
+    fn report_hook(&self) -> ReportHook {
+        ReportHook {
+            path: self.config.report_path.clone(),
+            command: self.config.report_command.clone(),
+        }
+    }
+
+    /// Hands the changes made since the last update to the report hook.
+    fn report_task(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.pending_reports.is_empty() {
+            return Task::none();
+        }
+        let reports = std::mem::take(&mut self.pending_reports);
+        let send = self.report_hook().send(reports);
+
+        Task::perform(
+            async move { Message::ReportSent(send.await) },
+            cosmic::Action::App,
+        )
+    }
+
+    /// Starts recording an action on the prints of `user` in operator mode.
+    fn begin_operator_action(
+        &mut self,
+        action: OperatorAction,
+        user: &str,
+        finger: Option<String>,
+    ) {
+        if self.config.operator_mode {
+            self.operator_action = Some(OperatorEntry::start(action, user, finger));
+        }
+    }
+
+    /// Records how the action in progress ended, `None` when it went through.
+    fn finish_operator_action(&mut self, error: Option<String>) {
+        if let Some(entry) = self.operator_action.take() {
+            self.pending_operator_entries.push(entry.finish(error));
+        }
+    }
+
+    /// Records an action that ended right away in operator mode.
+    fn log_operator_action(
+        &mut self,
+        action: OperatorAction,
+        user: &str,
+        finger: Option<String>,
+        error: Option<String>,
+    ) {
+        if self.config.operator_mode {
+            let entry = OperatorEntry::start(action, user, finger).finish(error);
+            self.pending_operator_entries.push(entry);
+        }
+    }
+
+    /// Appends the actions finished since the last update to the operator log.
+    fn operator_log_task(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.pending_operator_entries.is_empty() {
+            return Task::none();
+        }
+        let entries = std::mem::take(&mut self.pending_operator_entries);
+        let Some(path) = operator_log::log_path() else {
+            self.status = fl!("log-no-path");
+            return Task::none();
+        };
+
+        Task::perform(
+            async move { Message::OperatorLogWritten(operator_log::append(&path, entries).await) },
+            cosmic::Action::App,
+        )
+    }
+
+    fn operator_log_load_task(&mut self) -> Task<cosmic::Action<Message>> {
+        self.operator_entries = None;
+        let Some(path) = operator_log::log_path() else {
+            return Task::done(cosmic::Action::App(Message::OperatorLogLoaded(Err(fl!(
+                "log-no-path"
+            )))));
+        };
+
+        Task::perform(
+            async move { Message::OperatorLogLoaded(operator_log::read(&path).await) },
+            cosmic::Action::App,
+        )
+    }
+
+    /// Whether the context drawer shows `page`.
+    fn showing_context(&self, page: ContextPage) -> bool {
+        self.core.window.show_context && self.context_page == page
+    }
+
+    /// Tells other components on the session bus how the enrollment goes.
+    fn progress_signal_task(&self, status: &str) -> Task<cosmic::Action<Message>> {
+        let Some(connection) = self.progress_bus.clone() else {
+            return Task::none();
+        };
+        let total = self.enroll_total_stages.unwrap_or(0);
+        let emit =
+            progress_signal::emit(connection, self.enroll_progress, total, status.to_string());
+        Task::perform(emit, |()| cosmic::Action::None)
+    }
+
+    /// Reloads the overview table if it is the page shown.
+    fn refresh_overview(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.nav.data::<Page>(self.nav.active()) != Some(&Page::Overview) {
+            return Task::none();
+        }
+        self.overview = None;
+        self.overview_task()
+    }
+
+    fn overview_task(&mut self) -> Task<cosmic::Action<Message>> {
+        let (Some(path), Some(backend)) = (&self.device_path, &self.backend) else {
+            return Task::done(cosmic::Action::App(Message::OverviewLoaded(Vec::new())));
+        };
+        let usernames = self
+            .overview_users()
+            .map(|user| (*user.username).clone())
+            .collect();
+        let overview = backend.list_fingers_by_user((**path).clone(), usernames);
+
+        let task = Task::perform(
+            async move { Message::OverviewLoaded(overview.await) },
+            cosmic::Action::App,
+        );
+        fetches::replace(&mut self.fetches.overview, task)
+    }
+
+    /// Whether `username` is the account running the app.
+    fn is_current_user(&self, username: &str) -> bool {
+        self.current_username.as_deref() == Some(username)
+    }
+
+    /// Listed users whose prints can be read without authenticating first.
+    fn overview_users(&self) -> impl Iterator<Item = &UserOption> {
+        self.shown_users
+            .iter()
+            .filter(|user| self.authorized_for_others || self.is_current_user(&user.username))
+    }
+
+    /// Lists what clearing the device would remove, for the confirm dialog.
+    fn clear_preview_task(&self) -> Task<cosmic::Action<Message>> {
+        let (Some(path), Some(backend)) = (&self.device_path, &self.backend) else {
+            return Task::done(cosmic::Action::App(Message::ClearPreview(Vec::new())));
+        };
+        let usernames: Vec<String> = self.users.iter().map(|u| (*u.username).clone()).collect();
+        let preview = backend.list_fingers_by_user((**path).clone(), usernames);
+
+        Task::perform(
+            async move { Message::ClearPreview(preview.await) },
+            cosmic::Action::App,
+        )
+    }
+
+    /// Asks to confirm a destructive action, naming who and what it affects.
+    fn view_confirmation(&self, confirmation: &Confirmation) -> Element<'_, Message> {
+        let remote = !self.session_location.is_local();
+        let mut confirm_btn = widget::button::destructive(confirmation.confirm_label());
+        if !remote || self.remote_acknowledged {
+            confirm_btn = confirm_btn.on_press(Message::ConfirmAction);
+        }
+
+        let dialog = dialog::dialog()
+            .title(confirmation.title())
+            .body(confirmation.body(self.caps().storage))
+            .primary_action(confirm_btn)
+            .secondary_action(
+                widget::button::standard(fl!("cancel")).on_press(Message::CancelConfirm),
+            );
+
+        if *confirmation != Confirmation::ClearDevice && !remote {
+            return dialog.into();
+        }
+
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+        let mut controls = widget::column().spacing(space_xs);
+
+        if *confirmation == Confirmation::ClearDevice {
+            controls = controls.push(self.view_clear_preview());
+
+            if self.caps().shared_with_other_systems && !self.config.suppress_dual_boot_warning {
+                controls = controls.push(
+                    widget::column()
+                        .push(text::body(fl!("dual-boot-warning")))
+                        .push(
+                            widget::checkbox(
+                                fl!("dual-boot-warning-suppress"),
+                                self.suppress_dual_boot_warning,
+                            )
+                            .on_toggle(Message::SuppressDualBootWarning),
+                        )
+                        .spacing(space_xs),
+                );
+            }
+        }
+
+        if remote {
+            controls = controls.push(
+                widget::checkbox(fl!("remote-session-confirm"), self.remote_acknowledged)
+                    .on_toggle(Message::AcknowledgeRemote),
+            );
+        }
+
+        dialog.control(controls).into()
+    }
+
+    /// Each user with the fingers that clearing the device removes.
+    fn view_clear_preview(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let Some(preview) = &self.clear_preview else {
+            return text::body(fl!("clear-preview-loading")).into();
+        };
+        if preview.is_empty() {
+            return text::body(fl!("clear-preview-empty")).into();
+        }
+
+        let summary = widget::column()
+            .push(text::body(clear_impact(preview)))
+            .spacing(space_xxs);
+        preview
+            .iter()
+            .fold(summary, |column, (username, fingers)| {
+                let names: Vec<String> =
+                    fingers.iter().map(|f| finger_id_to_localized(f)).collect();
+                column.push(
+                    widget::column()
+                        .push(text::heading(username.as_str()))
+                        .push(text::body(i18n::join_list(names))),
+                )
+            })
+            .into()
+    }
+
+    /// Persists a ticked "don't show again" box once the clear dialog closes.
+    fn save_dual_boot_warning_choice(&mut self) {
+        if !std::mem::take(&mut self.suppress_dual_boot_warning) {
+            return;
+        }
+
+        if let Some(handler) = &self.config_handler
+            && let Err(why) = self.config.set_suppress_dual_boot_warning(handler, true)
+        {
+            tracing::error!(%why, "failed to save config");
+        }
+    }
+
+    fn on_delete(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(page) = self.nav.data::<Page>(self.nav.active()).copied() else {
+            return Task::none();
+        };
+        // Nothing to delete from the overview itself
+        if page == Page::Overview {
+            return Task::none();
+        }
+        if !self.can_delete(page) {
+            self.status = fl!("delete-unsupported");
+            return Task::none();
+        }
+
+        // Deleting from a remote session is always confirmed
+        if (self.config.confirm_before_delete || !self.session_location.is_local())
+            && let Some(user) = self.selected_user.clone()
+        {
+            self.confirmation = Some(Confirmation::Delete { page, user });
+            self.remote_acknowledged = false;
+            return Task::none();
+        }
+        self.delete_confirmed(page)
+    }
+
+    /// Deletes `page` once the user agreed, scanning a finger first if required.
+    fn delete_confirmed(&mut self, page: Page) -> Task<cosmic::Action<Message>> {
+        if self.config.require_verify_before_delete {
+            if self.device_path.is_some() && self.selected_user.is_some() {
+                self.pending_delete = Some(page);
+                self.start_verification("any", fl!("delete-verify-prompt"));
+            }
+            return Task::none();
+        }
+
+        self.delete_page(page)
+    }
+
+    /// Deletes the finger shown on `page`, or every finger of the user.
+    fn delete_page(&mut self, page: Page) -> Task<cosmic::Action<Message>> {
+        if let (Some(path), Some(backend), Some(user)) = (
+            self.device_path.clone(),
+            self.backend.clone(),
+            self.selected_user.clone(),
+        ) {
+            self.status = fl!("deleting");
+            self.busy = true;
+            self.operation_started = Some(Instant::now());
+            let path = (*path).clone();
+            let username = (*user.username).clone();
+            let finger = page.as_finger_id().map(str::to_string);
+            self.begin_operator_action(OperatorAction::Delete, &username, finger);
+
+            if let Some(finger_name) = page.as_finger_id() {
+                let delete = backend.delete_finger(path, finger_name.to_string(), username);
+                return Task::perform(
+                    async move {
+                        match delete.await {
+                            Ok(_) => Message::DeleteComplete,
+                            Err(e) => Message::OperationError(AppError::from(e)),
+                        }
+                    },
+                    cosmic::Action::App,
+                );
+            } else {
+                let delete = backend.delete_fingers(path, username);
+                return Task::perform(
+                    async move {
+                        match delete.await {
+                            Ok(_) => Message::DeleteComplete,
+                            Err(e) => Message::OperationError(AppError::from(e)),
+                        }
+                    },
+                    cosmic::Action::App,
+                );
+            }
+        }
+        Task::none()
+    }
+
+    fn on_register(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(page) = self.nav.data::<Page>(self.nav.active())
+            && let Some(finger_id) = page.as_finger_id()
+            && self.device_path.is_some()
+            && let Some(user) = &self.selected_user
+        {
+            // Match-on-chip sensors may reject a finger already stored for someone else
+            if self.caps().rejects_duplicates
+                && let (Some(backend), Some(path)) = (&self.backend, &self.device_path)
+            {
+                let others: Vec<String> = self
+                    .users
+                    .iter()
+                    .filter(|u| u.username != user.username)
+                    .map(|u| (*u.username).clone())
+                    .collect();
+
+                if !others.is_empty() {
+                    self.busy = true;
+                    self.status = fl!("duplicate-checking");
+                    let finger = finger_id.to_string();
+                    let users =
+                        backend.find_users_with_finger((**path).clone(), others, finger.clone());
+                    return Task::perform(
+                        async move { Message::DuplicateCheckComplete(finger, users.await) },
+                        cosmic::Action::App,
+                    );
+                }
+            }
+
+            self.start_enrollment(finger_id);
+        }
+        Task::none()
+    }
+
+    /// Starts the enrollment subscription for `finger_id` and marks it as in progress
+    /// so an interrupted enrollment can be offered again on the next launch.
+    fn start_enrollment(&mut self, finger_id: &str) {
+        if !self.enrollment.start(finger_id) {
+            return;
+        }
+        self.last_activity = Instant::now();
+        self.operation_started = Some(Instant::now());
+        self.busy = true;
+        self.status = fl!("status-starting-enrollment");
+
+        let username = self
+            .selected_user
+            .as_ref()
+            .map_or_else(String::new, |user| (*user.username).clone());
+        let finger = Some(finger_id.to_string());
+        self.begin_operator_action(OperatorAction::Enroll, &username, finger);
+        self.enroll_journal = Some(EnrollJournal::start(
+            self.device_name().map(str::to_string),
+            self.scan_type(),
+            &username,
+            finger_id,
+        ));
+
+        let marker = self.selected_user.as_ref().map(|user| EnrollmentMarker {
+            username: (*user.username).clone(),
+            finger: finger_id.to_string(),
+        });
+        self.set_interrupted_enrollment(marker);
+    }
+
+    fn set_interrupted_enrollment(&mut self, marker: Option<EnrollmentMarker>) {
+        if self.state.interrupted_enrollment == marker {
+            return;
+        }
+
+        if let Some(handler) = &self.state_handler {
+            if let Err(why) = self.state.set_interrupted_enrollment(handler, marker) {
+                tracing::error!(%why, "failed to save enrollment state");
+            }
+        } else {
+            self.state.interrupted_enrollment = marker;
+        }
+    }
+
+    /// Preselects the user and finger page of an enrollment that never finished.
+    fn restore_interrupted_enrollment(&mut self, marker: EnrollmentMarker) {
+        if let Some(page) = Page::from_finger_id(&marker.finger)
+            && let Some(id) = self.nav_id(page)
+        {
+            self.nav.activate(id);
+        }
+
+        if self
+            .selected_user
+            .as_ref()
+            .is_none_or(|user| *user.username != marker.username)
+        {
+            self.selected_user = Some(UserOption {
+                username: Arc::new(marker.username.clone()),
+                realname: Arc::new(String::new()),
+                system: false,
+                avatar: None,
+            });
+        }
+
+        self.recovery = Some(marker);
+    }
+
+    /// Whether `page` has a print for the selected user, or needs attention.
+    fn finger_status(&self, page: Page) -> FingerStatus {
+        let Some(finger) = page.as_finger_id() else {
+            return FingerStatus::NotEnrolled;
+        };
+
+        let interrupted = self.state.interrupted_enrollment.as_ref().is_some_and(|marker| {
+            marker.finger == finger
+                && self
+                    .selected_user
+                    .as_ref()
+                    .is_some_and(|user| *user.username == marker.username)
+        });
+
+        if interrupted {
+            FingerStatus::Attention
+        } else if self.enrolled_fingers.iter().any(|f| f == finger) {
+            FingerStatus::Enrolled
+        } else {
+            FingerStatus::NotEnrolled
+        }
+    }
+
+    /// Updates the nav bar icons when the status of a finger changed.
+    ///
+    /// The icons differ in shape, not just color, like the glyphs on the hands.
+    fn update_nav_badges(&mut self) {
+        let badges: Vec<FingerStatus> =
+            Page::all().iter().map(|p| self.finger_status(*p)).collect();
+        if badges == self.nav_badges {
+            return;
+        }
+
+        let ids: Vec<nav_bar::Id> = self.nav.iter().collect();
+        for id in ids {
+            let Some(page) = self.nav.data::<Page>(id).copied() else {
+                continue;
+            };
+            let name = nav_icon(page, self.finger_status(page));
+            self.nav.icon_set(id, icon::from_name(name).icon());
+        }
+        self.nav_badges = badges;
+    }
+
+    /// Shows the names the selected user gave their fingers in the nav bar.
+    fn update_nav_names(&mut self) {
+        let ids: Vec<nav_bar::Id> = self.nav.iter().collect();
+        for id in ids {
+            let Some(page) = self.nav.data::<Page>(id).copied() else {
+                continue;
+            };
+            let name = self.finger_name(page);
+            if self.nav.text(id) != Some(name.as_str()) {
+                self.nav.text_set(id, name);
+            }
+        }
+    }
+
+    /// Lists the pages that aren't hidden in the nav bar, when they changed.
+    ///
+    /// A hidden finger is still listed while the selected user has a print of
+    /// it or while its page is open, so switching users never pulls the open
+    /// page away.
+    fn update_nav_pages(&mut self) {
+        let active = self.nav.data::<Page>(self.nav.active()).copied();
+        let pages: Vec<Page> = Page::all()
+            .iter()
+            .copied()
+            .filter(|page| {
+                let Some(finger_id) = page.as_finger_id() else {
+                    return true;
+                };
+                active == Some(*page)
+                    || !self.config.hidden_fingers.iter().any(|f| f == finger_id)
+                    || self.enrolled_fingers.iter().any(|f| f == finger_id)
+            })
+            .collect();
+        if pages == self.nav_pages {
+            return;
+        }
+
+        // The model can't hide items, so it is built again in page order
+        self.nav = build_nav(&pages, active);
+        self.nav_pages = pages;
+        // The new items need their icons and names set again
+        self.nav_badges.clear();
+    }
+
+    /// The nav item of `page`, listing the finger again first if it was
+    /// hidden, since going to its page means it is wanted after all.
+    fn nav_id(&mut self, page: Page) -> Option<nav_bar::Id> {
+        if let Some(finger_id) = page.as_finger_id()
+            && self.config.hidden_fingers.iter().any(|f| f == finger_id)
+        {
+            let mut hidden = self.config.hidden_fingers.clone();
+            hidden.retain(|f| f != finger_id);
+            self.save_hidden_fingers(hidden);
+            self.update_nav_pages();
+        }
+        self.nav.iter().find(|id| self.nav.data::<Page>(*id) == Some(&page))
+    }
+
+    /// Leaves `page` out of the nav bar, moving to the overview first when
+    /// it is open.
+    fn hide_finger(&mut self, page: Page) -> Task<cosmic::Action<Message>> {
+        let Some(finger_id) = page.as_finger_id() else {
+            return Task::none();
+        };
+        if self.enrolled_fingers.iter().any(|f| f == finger_id) {
+            return Task::none();
+        }
+
+        let mut tasks = Vec::new();
+        if self.nav.data::<Page>(self.nav.active()) == Some(&page) {
+            if let Some(id) = self.nav_id(Page::Overview) {
+                tasks.push(self.on_nav_select(id));
+            }
+            // Selection is locked while busy or in a wizard
+            if self.nav.data::<Page>(self.nav.active()) == Some(&page) {
+                return Task::batch(tasks);
+            }
+        }
+
+        let mut hidden = self.config.hidden_fingers.clone();
+        if !hidden.iter().any(|f| f == finger_id) {
+            hidden.push(finger_id.to_string());
+        }
+        self.save_hidden_fingers(hidden);
+        self.update_nav_pages();
+        Task::batch(tasks)
+    }
+
+    fn save_hidden_fingers(&mut self, hidden: Vec<String>) {
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_hidden_fingers(handler, hidden) {
+                tracing::error!(%why, "failed to save hidden fingers");
+            }
+        } else {
+            self.config.hidden_fingers = hidden;
+        }
+    }
+
+    /// Name of `page` for the selected user, falling back to the finger's own.
+    fn finger_name(&self, page: Page) -> String {
+        self.selected_user
+            .as_ref()
+            .zip(page.as_finger_id())
+            .and_then(|(user, finger)| self.finger_label(&user.username, finger))
+            .cloned()
+            .unwrap_or_else(|| page.localized_name())
+    }
+
+    /// Name `username` gave their `finger`, if any.
+    fn finger_label(&self, username: &str, finger: &str) -> Option<&String> {
+        self.config.finger_names.get(username)?.get(finger)
+    }
+
+    /// Names `page` for the selected user. An empty name restores the default.
+    fn save_finger_name(&mut self, page: Page, name: &str) {
+        let (Some(user), Some(finger)) = (&self.selected_user, page.as_finger_id()) else {
+            return;
+        };
+        let mut names = self.config.finger_names.clone();
+        let user_names = names.entry((*user.username).clone()).or_default();
+        if name.is_empty() || name == page.localized_name() {
+            user_names.remove(finger);
+        } else {
+            user_names.insert(finger.to_string(), name.to_string());
+        }
+        names.retain(|_, user_names| !user_names.is_empty());
+
+        if let Some(handler) = &self.config_handler {
+            if let Err(why) = self.config.set_finger_names(handler, names) {
+                tracing::error!(%why, "failed to save finger names");
+            }
+        } else {
+            self.config.finger_names = names;
+        }
+    }
+
+    /// Runs a nav context menu action on its finger, selecting the finger first.
+    fn on_nav_menu(&mut self, action: NavMenuAction) -> Task<cosmic::Action<Message>> {
+        let page = action.page();
+        if let NavMenuAction::Rename(_) = action {
+            self.rename = Some((page, self.finger_name(page)));
+            return Task::none();
+        }
+        if let NavMenuAction::Hide(_) = action {
+            return self.hide_finger(page);
+        }
+
+        let Some(id) = self.nav_id(page) else {
+            return Task::none();
+        };
+        let select = if self.nav.active() == id {
+            Task::none()
+        } else {
+            self.on_nav_select(id)
+        };
+        // Selection is locked while busy or in a wizard
+        if self.nav.active() != id {
+            return select;
+        }
+
+        let run = match action {
+            NavMenuAction::Enroll(_) => self.on_register(),
+            NavMenuAction::Test(_) => self.on_verify(),
+            NavMenuAction::Delete(_) => self.on_delete(),
+            NavMenuAction::Rename(_) | NavMenuAction::Hide(_) => Task::none(),
+        };
+        Task::batch(vec![select, run])
+    }
+
+    /// Updates the header and window titles.
+    pub fn update_title(&mut self) -> Task<cosmic::Action<Message>> {
+        let window_title = match self.nav.text(self.nav.active()) {
+            Some(page) => fl!("window-title-page", app = fl!("app-title"), page = page),
+            None => fl!("app-title"),
+        };
+
+        if let Some(id) = self.core.main_window_id() {
+            self.set_window_title(window_title, id)
+        } else {
+            Task::none()
+        }
+    }
+
+    fn view_header(&self) -> Element<'_, Message> {
+        text::title1(fl!("fprint"))
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center)
+            .into()
+    }
+
+    /// Whether users can be picked, which they can't when the current user
+    /// is the only one whose prints may be managed.
+    fn shows_user_picker(&self) -> bool {
+        self.may_manage_others && !self.shown_users.is_empty()
+    }
+
+    /// The user picker, plus a device picker when more than one reader is present.
+    fn view_pickers(&self) -> Option<Element<'_, Message>> {
+        let show_devices = self.devices.len() > 1;
+        let show_users = self.shows_user_picker();
+        if !show_users && !show_devices {
+            return None;
+        }
+
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+        let mut row = widget::row().spacing(space_s).align_y(Vertical::Center);
+
+        if show_users {
+            row = row.push(
+                widget::row()
+                    .push(self.view_user_picker())
+                    .push(
+                        widget::button::icon(icon::from_name("system-users-symbolic"))
+                            .extra_small()
+                            .tooltip(fl!("account-settings"))
+                            .on_press_maybe(
+                                self.selected_user
+                                    .as_ref()
+                                    .map(|_| Message::OpenAccountSettings),
+                            ),
+                    )
+                    .push(self.view_help(HelpTopic::UserPicker))
+                    .align_y(Vertical::Center),
+            );
+        }
+
+        if show_devices {
+            let selected_device = self
+                .devices
+                .iter()
+                .find(|d| Some(&d.path) == self.device_path.as_ref())
+                .cloned();
+
+            row = row.push(
+                pick_list(
+                    self.devices.as_slice(),
+                    selected_device,
+                    Message::DeviceSelected,
+                )
+                .width(Length::Fixed(PICKER_WIDTH)),
+            );
+        }
+
+        Some(
+            row.apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Picks one of the shown users, listing them with their pictures like
+    /// the accounts page of COSMIC Settings.
+    fn view_user_picker(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let selected = self
+            .selected_user
+            .as_ref()
+            .map_or_else(|| widget::Space::with_width(Length::Fill).into(), user_row);
+        let button = widget::button::custom(
+            widget::row()
+                .push(selected)
+                .push(icon::from_name("pan-down-symbolic").size(16).icon())
+                .spacing(space_xxs)
+                .align_y(Vertical::Center),
+        )
+        .class(theme::Button::Standard)
+        .width(Length::Fixed(PICKER_WIDTH))
+        .on_press(Message::ToggleUserMenu);
+
+        let mut popover = widget::popover(button).on_close(Message::CloseUserMenu);
+
+        if self.user_menu_open {
+            let users = self.shown_users.iter().fold(widget::column(), |column, user| {
+                column.push(
+                    widget::button::custom(user_row(user))
+                        .class(theme::Button::MenuItem)
+                        .selected(self.selected_user.as_ref() == Some(user))
+                        .width(Length::Fill)
+                        .on_press(Message::UserSelected(user.clone())),
+                )
+            });
+            popover = popover.popup(
+                widget::scrollable(users)
+                    .apply(widget::container)
+                    .width(Length::Fixed(PICKER_WIDTH))
+                    .max_height(USER_MENU_MAX_HEIGHT)
+                    .padding(space_xxs)
+                    .class(theme::Container::Dialog),
+            );
+        }
+
+        popover.into()
+    }
+
+    /// A small help button that opens an explanatory popover for `topic`.
+    fn view_help(&self, topic: HelpTopic) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+
+        let button = widget::button::icon(icon::from_name("help-about-symbolic"))
+            .extra_small()
+            .tooltip(fl!("help"))
+            .on_press(Message::ToggleHelp(topic));
+
+        let mut popover = widget::popover(button).on_close(Message::CloseHelp);
+
+        if self.help == Some(topic) {
+            popover = popover.popup(
+                text::body(topic.localized_text())
+                    .width(Length::Fixed(HELP_POPUP_WIDTH))
+                    .apply(widget::container)
+                    .padding(space_s)
+                    .class(theme::Container::Dialog),
+            );
+        }
+
+        popover.into()
+    }
+
+    /// The hand diagram for choosing a finger, or the app icon while there's no reader.
+    fn view_hands(&self) -> Element<'_, Message> {
+        if self.device_path.is_none() {
+            // The artwork is drawn in one color, tinted with the current accent
+            return widget::svg(widget::svg::Handle::from_memory(FPRINT_ICON))
+                .class(theme::Svg::custom(|theme| widget::svg::Style {
+                    color: Some(theme.cosmic().accent_color().into()),
+                }))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
+        let selected = self.nav.data::<Page>(self.nav.active()).copied();
+        if selected == Some(Page::Overview) {
+            return self.view_overview();
+        }
+        if selected == Some(Page::Test) {
+            return self.view_self_test();
+        }
+
+        hand_diagram::view(selected, |page| self.finger_status(page), Message::SelectFinger)
+        .apply(widget::container)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Horizontal::Center)
+        .align_y(Vertical::Center)
+        .into()
+    }
+
+    /// The self-test page: how many scans matched the prints of the user
+    /// since it was started, with the button to start or stop it.
+    fn view_self_test(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+        let test = &self.self_test;
+
+        let mut content = widget::column()
+            .push(text::title4(fl!("page-test")))
+            .push(text::body(fl!("self-test-body")))
+            .spacing(space_s)
+            .align_x(Alignment::Center);
+
+        if self.enrolled_fingers.is_empty() {
+            content = content.push(text::body(fl!("self-test-no-prints")));
+        }
+
+        let (matches, no_matches, retries) = (test.matches, test.no_matches, test.retries);
+        content = content.push(text::body(fl!(
+            "self-test-tally",
+            matches = matches,
+            no_matches = no_matches,
+            retries = retries
+        )));
+        if let Some(rate) = test.match_rate() {
+            content = content.push(text::heading(fl!("self-test-rate", rate = rate)));
+        }
+        if let Some(last) = test.last {
+            content = content.push(text::caption(match last {
+                ScanResult::Match => fl!("self-test-last-match"),
+                ScanResult::NoMatch => fl!("self-test-last-no-match"),
+                ScanResult::Retry => fl!("self-test-last-retry"),
+            }));
+        }
+
+        let button = if test.running {
+            widget::button::destructive(fl!("self-test-stop")).on_press(Message::SelfTestStop)
+        } else {
+            let can_start =
+                !self.busy && self.device_path.is_some() && !self.enrolled_fingers.is_empty();
+            widget::button::suggested(fl!("self-test-start"))
+                .on_press_maybe(can_start.then_some(Message::SelfTestStart))
+        };
+
+        content
+            .push(button)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center)
+            .into()
+    }
+
+    /// A table of the listed users against their fingers, marking enrolled ones.
+    ///
+    /// Clicking a user switches to their prints.
+    fn view_overview(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let Some(overview) = &self.overview else {
+            return text::body(fl!("overview-loading"))
+                .apply(widget::container)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(Horizontal::Center)
+                .align_y(Vertical::Center)
+                .into();
+        };
+
+        let fingers: Vec<Page> = Page::all()
+            .iter()
+            .copied()
+            .filter(|page| page.as_finger_id().is_some())
+            .collect();
+
+        let header = fingers.iter().fold(
+            widget::row()
+                .push(widget::Space::new(Length::Fixed(OVERVIEW_USER_WIDTH), Length::Shrink))
+                .spacing(space_xxs),
+            |row, page| {
+                row.push(
+                    text::caption(page.localized_name())
+                        .apply(widget::container)
+                        .width(OVERVIEW_CELL_WIDTH)
+                        .align_x(Horizontal::Center),
+                )
+            },
+        );
+
+        let rows = self.overview_users().fold(
+            widget::column().push(header).spacing(space_xxs),
+            |column, user| {
+                let enrolled = overview
+                    .iter()
+                    .find(|(username, _)| *username == *user.username)
+                    .map_or(&[][..], |(_, fingers)| fingers.as_slice());
+
+                let name = widget::button::link(user.to_string())
+                    .on_press(Message::UserSelected(user.clone()))
+                    .width(OVERVIEW_USER_WIDTH);
+
+                let row = fingers.iter().fold(
+                    widget::row().push(name).spacing(space_xxs),
+                    |row, page| {
+                        let status = if page
+                            .as_finger_id()
+                            .is_some_and(|id| enrolled.iter().any(|f| f == id))
+                        {
+                            FingerStatus::Enrolled
+                        } else {
+                            FingerStatus::NotEnrolled
+                        };
+                        let cell = text::body(status.glyph())
+                            .apply(widget::container)
+                            .width(OVERVIEW_CELL_WIDTH)
+                            .align_x(Horizontal::Center);
+                        // Show what the user named the finger on hover
+                        let label = page
+                            .as_finger_id()
+                            .and_then(|finger| self.finger_label(&user.username, finger));
+                        match label {
+                            Some(label) => row.push(widget::tooltip(
+                                cell,
+                                text::body(label.as_str()),
+                                widget::tooltip::Position::Top,
+                            )),
+                            None => row.push(cell),
+                        }
+                    },
+                );
+                column.push(row.align_y(Vertical::Center))
+            },
+        );
+
+        let mut content = widget::column()
+            .push(rows)
+            .spacing(space_s);
+
+        // Other users' prints are only readable once polkit agreed
+        if self.overview_users().count() < self.shown_users.len() {
+            content = content.push(
+                widget::row()
+                    .push(text::body(fl!("overview-others-hidden")))
+                    .push(
+                        widget::button::standard(fl!("authenticate"))
+                            .on_press(Message::Authenticate),
+                    )
+                    .spacing(space_xxs)
+                    .align_y(Vertical::Center),
+            );
+        }
+
+        // The table is wider than a narrow window
+        widget::scrollable(content)
+            .direction(Direction::Both {
+                vertical: Scrollbar::default(),
+                horizontal: Scrollbar::default(),
+            })
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// The live status, or an earlier one while browsing the history.
+    ///
+    /// Clicking the status lets the arrow keys browse the history.
+    fn view_status(&self) -> Element<'_, Message> {
+        let (status, caption) = match self.status_history.browsed() {
+            Some((status, count)) => (status, Some(fl!("status-history-position", count = count))),
+            None => (
+                self.status.as_str(),
+                self.status_focused.then(|| fl!("status-history-hint")),
+            ),
+        };
+
+        let mut status_row = widget::row()
+            .spacing(theme::active().cosmic().spacing.space_xs)
+            .align_y(Vertical::Center);
+
+        // Show how to present the finger while the reader waits for it
+        if self.enrollment.is_active() && self.status_history.browsed().is_none() {
+            status_row = status_row.push(icon::from_name(scan_icon(self.scan_type())).icon());
+        }
+
+        let mut column = widget::column()
+            .push(status_row.push(widget::text(status).size(STATUS_TEXT_SIZE)))
+            .align_x(Alignment::Center);
+
+        if let Some(caption) = caption {
+            column = column.push(text::caption(caption));
+        }
+
+        widget::mouse_area(column)
+            .on_press(Message::FocusStatus(!self.status_focused))
+            .apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .into()
+    }
+
+    /// Points out that the reader is managed from a session away from it.
+    fn view_remote_notice(&self) -> Option<Element<'_, Message>> {
+        let notice = self.session_location.localized_notice()?;
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        Some(
+            widget::row()
+                .push(icon::from_name("dialog-warning-symbolic").icon())
+                .push(text::body(notice))
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Points out when enrolled fingers won't be used for logging in or sudo.
+    fn view_pam_banner(&self) -> Option<Element<'_, Message>> {
+        let status = self.pam_status.filter(|status| !status.fully_enabled())?;
+        if self.pam_banner_dismissed {
+            return None;
+        }
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let message = match (status.login, status.sudo) {
+            (false, false) => fl!("pam-disabled"),
+            (false, true) => fl!("pam-login-disabled"),
+            _ => fl!("pam-sudo-disabled"),
+        };
+
+        let mut row = widget::row()
+            .push(icon::from_name("dialog-information-symbolic").icon())
+            .push(text::body(message));
+
+        if self.pam_can_enable {
+            row = row.push(
+                widget::button::suggested(fl!("pam-enable")).on_press(Message::EnablePam),
+            );
+        }
+
+        Some(
+            row.push(widget::button::text(fl!("dismiss")).on_press(Message::DismissPamBanner))
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Offers to finish a device clear that stopped before every user was cleared.
+    fn view_clear_resume(&self) -> Option<Element<'_, Message>> {
+        if self.state.unfinished_clear.is_empty() || self.clear_cancel.is_some() {
+            return None;
+        }
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let mut resume_btn = widget::button::suggested(fl!("clear-resume"));
+        if !self.busy && self.device_path.is_some() {
+            resume_btn = resume_btn.on_press(Message::ClearResume);
+        }
+
+        Some(
+            widget::row()
+                .push(icon::from_name("dialog-warning-symbolic").icon())
+                .push(text::body(fl!(
+                    "clear-unfinished",
+                    users = i18n::join_list(&self.state.unfinished_clear)
+                )))
+                .push(resume_btn)
+                .push(
+                    widget::button::text(fl!("dismiss")).on_press(Message::ClearDismissResume),
+                )
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Says an operation waits for the reader to be free, and lets it be cancelled.
+    fn view_device_wait(&self) -> Option<Element<'_, Message>> {
+        let (_, deadline) = self.device_wait.as_ref()?;
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let secs = deadline.saturating_duration_since(Instant::now()).as_secs().max(1);
+        Some(
+            widget::row()
+                .push(icon::from_name("dialog-information-symbolic").icon())
+                .push(text::body(fl!("device-wait-remaining", secs = secs)))
+                .push(widget::button::standard(fl!("cancel")).on_press(Message::DeviceWaitCancel))
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// How long the reader has been held by another program, with controls for retrying.
+    fn view_claim_retry(&self) -> Option<Element<'_, Message>> {
+        let retry = self.claim_retry.as_ref()?;
+        let now = Instant::now();
+        if !retry.is_shown(now) {
+            return None;
+        }
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let unavailable = retry.unavailable_for(now).as_secs();
+        let busy = if unavailable < 60 {
+            fl!("reader-busy-seconds", secs = unavailable)
+        } else {
+            fl!("reader-busy-minutes", mins = unavailable / 60)
+        };
+        let check = if retry.is_attempting() {
+            Some(fl!("reader-busy-checking"))
+        } else {
+            retry
+                .next_attempt_in(now)
+                .map(|next| fl!("reader-busy-next-check", secs = next.as_secs().max(1)))
+        };
+        let message = match check {
+            Some(check) => fl!("reader-busy-with-check", busy = busy, check = check),
+            None => busy,
+        };
+
+        let pause_btn = if retry.is_paused() {
+            widget::button::text(fl!("reader-busy-resume"))
+        } else {
+            widget::button::text(fl!("reader-busy-pause"))
+        }
+        .on_press(Message::ClaimRetryPause(!retry.is_paused()));
+
+        let mut retry_btn = widget::button::standard(fl!("reader-busy-retry"));
+        if !self.busy && !retry.is_attempting() {
+            retry_btn = retry_btn.on_press(Message::ClaimRetryNow);
+        }
+
+        Some(
+            widget::row()
+                .push(icon::from_name("dialog-warning-symbolic").icon())
+                .push(text::body(message))
+                .push(retry_btn)
+                .push(pause_btn)
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    fn view_in_use_guidance(&self) -> Option<Element<'_, Message>> {
+        let services = self.in_use_guidance.as_ref()?;
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let message = if services.is_empty() {
+            fl!("in-use-guidance")
+        } else {
+            fl!("in-use-guidance-services", services = i18n::join_list(services))
+        };
+
+        Some(
+            widget::row()
+                .push(icon::from_name("dialog-warning-symbolic").icon())
+                .push(
+                    widget::column()
+                        .push(text::heading(AppError::AlreadyInUse.localized_title()))
+                        .push(text::body(message)),
+                )
+                .push(
+                    widget::button::text(fl!("dismiss"))
+                        .on_press(Message::DismissInUseGuidance),
+                )
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// The last error with a button for the step most likely to get past it.
+    fn view_error_banner(&self) -> Option<Element<'_, Message>> {
+        let (error, _) = self.last_error.as_ref()?;
+        // The claim retry banner already covers a busy reader
+        if *error == AppError::AlreadyInUse && self.claim_retry.is_some() {
+            return None;
+        }
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column()
+            .push(text::heading(error.localized_title()))
+            .push(text::body(error.localized_detail()));
+        // What fprintd replied, for bug reports and searching the web
+        if let Some(dbus_error) = error.dbus_error() {
+            let label = if self.error_details_open {
+                fl!("error-details-hide")
+            } else {
+                fl!("error-details")
+            };
+            column = column.push(widget::button::text(label).on_press(Message::ToggleErrorDetails));
+            if self.error_details_open {
+                column = column.push(text::caption(dbus_error.to_string()));
+            }
+        }
+
+        let mut row = widget::row()
+            .push(icon::from_name("dialog-error-symbolic").icon())
+            .push(column);
+
+        if let Some(action) = error.suggested_action() {
+            let mut action_btn = widget::button::suggested(action.localized_label());
+            if !self.busy {
+                action_btn = action_btn.on_press(Message::ErrorAction(action));
+            }
+            row = row.push(action_btn);
+        }
+
+        Some(
+            row.push(widget::button::text(fl!("dismiss")).on_press(Message::DismissError))
+                .spacing(space_xs)
+                .align_y(Vertical::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    fn view_progress(&self) -> Option<Element<'_, Message>> {
+        self.enrollment.finger()?;
+
+        self.enroll_total_stages.map(|total| {
+            widget::row()
+                .push(stage_progress::view(self.enroll_progress, total, self.enroll_retry))
+                .push(self.view_help(HelpTopic::Progress))
+                .align_y(Vertical::Center)
+                .into()
+        })
+    }
+
+    fn view_replace_wizard(&self) -> Option<Element<'_, Message>> {
+        let wizard = self.replace.as_ref()?;
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let old = wizard.old.localized_name();
+        let new = wizard.new.map(|p| p.localized_name()).unwrap_or_default();
+        let cancel_btn = widget::button::standard(fl!("cancel"));
+
+        let content: Element<'_, Message> = match wizard.step {
+            ReplaceStep::ChooseFinger => {
+                let mut continue_btn = widget::button::suggested(fl!("replace-continue"));
+                if wizard.new.is_some() {
+                    continue_btn = continue_btn.on_press(Message::ReplaceContinue);
+                }
+
+                widget::row()
+                    .push(widget::dropdown(
+                        &wizard.candidate_names,
+                        wizard.selected_index(),
+                        Message::ReplaceFingerSelected,
+                    ))
+                    .push(continue_btn)
+                    .push(cancel_btn.on_press(Message::ReplaceCancel))
+                    .spacing(space_xs)
+                    .align_y(Vertical::Center)
+                    .into()
+            }
+            ReplaceStep::Enroll => {
+                text::body(fl!("replace-enrolling", new = new, old = old)).into()
+            }
+            ReplaceStep::Verify => {
+                if self.verifying_finger.is_some() {
+                    cancel_btn.on_press(Message::VerifyStop).into()
+                } else {
+                    widget::row()
+                        .push(
+                            widget::button::suggested(fl!("replace-verify"))
+                                .on_press(Message::ReplaceVerify),
+                        )
+                        .push(cancel_btn.on_press(Message::ReplaceCancel))
+                        .spacing(space_xs)
+                        .into()
+                }
+            }
+            ReplaceStep::ConfirmDelete => widget::column()
+                .push(text::body(match self.caps().storage {
+                    TemplateStorage::Host => fl!("replace-confirm-delete", new = new, old = old),
+                    TemplateStorage::Device => {
+                        fl!("replace-confirm-delete-on-chip", new = new, old = old)
+                    }
+                }))
+                .push(
+                    widget::row()
+                        .push(
+                            widget::button::destructive(fl!("replace-delete-old"))
+                                .on_press(Message::ReplaceDeleteOld),
+                        )
+                        .push(
+                            widget::button::standard(fl!("replace-keep-old"))
+                                .on_press(Message::ReplaceKeepOld),
+                        )
+                        .spacing(space_xs),
+                )
+                .align_x(Alignment::Center)
+                .spacing(space_xs)
+                .into(),
+        };
+
+        Some(
+            widget::column()
+                .push(hand_map::view(|page| wizard.finger_mark(page), self.pulse))
+                .push(content)
+                .spacing(space_xs)
+                .align_x(Alignment::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Lists the fingers to tick for a batch enrollment, then how each went.
+    fn view_batch_enroll(&self) -> Option<Element<'_, Message>> {
+        let batch = self.batch.as_ref()?;
+        let cosmic_theme::Spacing { space_xxs, space_xs, .. } =
+            theme::active().cosmic().spacing;
+
+        let fingers = batch
+            .fingers
+            .iter()
+            .enumerate()
+            .filter(|(_, finger)| !batch.started || finger.selected)
+            .fold(widget::column().spacing(space_xxs), |column, (index, finger)| {
+                let name = self.finger_name(finger.page);
+                if !batch.started {
+                    return column.push(
+                        widget::checkbox(name, finger.selected)
+                            .on_toggle(move |selected| Message::BatchToggle(index, selected)),
+                    );
+                }
+                let state = match finger.outcome {
+                    Some(BatchOutcome::Enrolled) => fl!("batch-enrolled"),
+                    Some(BatchOutcome::Skipped) => fl!("batch-skipped"),
+                    Some(BatchOutcome::Failed) => fl!("batch-failed"),
+                    None if batch.current == Some(index) => fl!("batch-enrolling"),
+                    None => fl!("batch-waiting"),
+                };
+                column.push(
+                    widget::row()
+                        .push(text::body(name).width(Length::Fill))
+                        .push(text::body(state))
+                        .spacing(space_xs),
+                )
+            });
+
+        let (done, total) = batch.progress();
+        let mut content = widget::column().push(text::heading(fl!("batch-title")));
+        let buttons = if !batch.started {
+            content = content.push(text::body(fl!("batch-body")));
+            let can_start = !self.busy && total > 0;
+            widget::row()
+                .push(
+                    widget::button::suggested(fl!("batch-start"))
+                        .on_press_maybe(can_start.then_some(Message::BatchStart)),
+                )
+                .push(widget::button::standard(fl!("cancel")).on_press(Message::BatchClose))
+        } else if batch.is_done() {
+            let enrolled = batch.count(BatchOutcome::Enrolled);
+            content = content.push(text::body(fl!(
+                "batch-done",
+                enrolled = enrolled,
+                total = total
+            )));
+            widget::row().push(
+                widget::button::standard(fl!("batch-close")).on_press(Message::BatchClose),
+            )
+        } else {
+            content = content.push(text::body(fl!("batch-progress", done = done, total = total)));
+            let skip = self.stop_enrollment().filter(|_| batch.current.is_some());
+            widget::row()
+                .push(widget::button::standard(fl!("batch-skip")).on_press_maybe(skip))
+                .push(
+                    widget::button::destructive(fl!("batch-stop"))
+                        .on_press_maybe((!batch.stopping).then_some(Message::BatchStop)),
+                )
+        };
+
+        Some(
+            content
+                .push(fingers)
+                .push(buttons.spacing(space_xs))
+                .spacing(space_xs)
+                .width(Length::Fixed(BATCH_WIDTH))
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    /// Offers to delete prints after the reader ran out of room for another one.
+    fn view_storage_cleanup(&self) -> Option<Element<'_, Message>> {
+        let cleanup = self.cleanup.as_ref()?;
+        let cosmic_theme::Spacing { space_xxs, space_xs, .. } =
+            theme::active().cosmic().spacing;
+
+        let prints: Element<'_, Message> = match &cleanup.prints {
+            None => text::body(fl!("cleanup-loading")).into(),
+            Some(prints) if prints.is_empty() => text::body(fl!("cleanup-empty")).into(),
+            Some(prints) => prints
+                .iter()
+                .enumerate()
+                .fold(widget::column().spacing(space_xxs), |column, (index, print)| {
+                    let label = fl!(
+                        "cleanup-print",
+                        finger = print.localized_finger(),
+                        user = print.username.as_str()
+                    );
+                    let mut checkbox = widget::checkbox(label, print.selected);
+                    if !cleanup.deleting {
+                        checkbox = checkbox
+                            .on_toggle(move |selected| Message::CleanupToggle(index, selected));
+                    }
+                    column.push(checkbox)
+                })
+                .into(),
+        };
+
+        let remote = !self.session_location.is_local();
+        let can_delete = !cleanup.deleting
+            && !self.busy
+            && !cleanup.selected().is_empty()
+            && (!remote || self.remote_acknowledged);
+        let mut cancel_btn = widget::button::standard(fl!("cancel"));
+        if !cleanup.deleting {
+            cancel_btn = cancel_btn.on_press(Message::CleanupCancel);
+        }
+
+        let mut column = widget::column()
+            .push(text::heading(fl!("cleanup-title")))
+            .push(text::body(fl!("cleanup-body")))
+            .push(prints);
+
+        if remote {
+            let mut acknowledge =
+                widget::checkbox(fl!("remote-session-confirm"), self.remote_acknowledged);
+            if !cleanup.deleting {
+                acknowledge = acknowledge.on_toggle(Message::AcknowledgeRemote);
+            }
+            column = column.push(acknowledge);
+        }
+
+        Some(
+            column
+                .push(
+                    widget::row()
+                        .push(
+                            widget::button::destructive(fl!("cleanup-delete"))
+                                .on_press_maybe(can_delete.then_some(Message::CleanupDelete)),
+                        )
+                        .push(cancel_btn)
+                        .spacing(space_xs),
+                )
+                .spacing(space_xs)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    fn view_enroll_wizard(&self) -> Option<Element<'_, Message>> {
+        let wizard = self.enroll_wizard.as_ref()?;
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        let finger = wizard.finger.map(|p| p.localized_name()).unwrap_or_default();
+        let (step, steps) = wizard.position();
+        let back_btn = widget::button::standard(fl!("wizard-back")).on_press(Message::WizardBack);
+        let next_btn = widget::button::suggested(fl!("wizard-next"));
+        let cancel_btn = widget::button::standard(fl!("cancel")).on_press(Message::WizardClose);
+        let mut buttons = widget::row().spacing(space_xs);
+
+        let content: Element<'_, Message> = match wizard.step {
+            WizardStep::ChooseUser => {
+                buttons = buttons.push(cancel_btn).push(next_btn.on_press_maybe(
+                    (self.selected_user.is_some() && !self.busy).then_some(Message::WizardNext),
+                ));
+
+                widget::column()
+                    .push(text::body(fl!("wizard-choose-user")))
+                    .push(self.view_user_picker())
+                    .spacing(space_xs)
+                    .align_x(Alignment::Center)
+                    .into()
+            }
+            WizardStep::ChooseFinger => {
+                if step == 1 {
+                    buttons = buttons.push(cancel_btn);
+                } else {
+                    buttons = buttons.push(back_btn);
+                }
+                buttons = buttons.push(
+                    next_btn.on_press_maybe(wizard.finger.map(|_| Message::WizardNext)),
+                );
+
+                widget::column()
+                    .push(text::body(fl!("wizard-choose-finger")))
+                    .push(hand_diagram::view(
+                        wizard.finger,
+                        |page| self.finger_status(page),
+                        Message::SelectFinger,
+                    ))
+                    .spacing(space_xs)
+                    .align_x(Alignment::Center)
+                    .into()
+            }
+            WizardStep::Instructions => {
+                let start_btn = widget::button::suggested(fl!("wizard-start-enroll"));
+                buttons = buttons
+                    .push(back_btn)
+                    .push(start_btn.on_press_maybe(
+                        (!self.busy && self.device_path.is_some()).then_some(Message::WizardNext),
+                    ));
+
+                text::body(match self.scan_type() {
+                    ScanType::Press => fl!("wizard-instructions-press", finger = finger),
+                    ScanType::Swipe => fl!("wizard-instructions-swipe", finger = finger),
+                })
+                .into()
+            }
+            WizardStep::Enroll => {
+                if self.enrollment.is_active() {
+                    let cancel = widget::button::standard(fl!("cancel"))
+                        .on_press_maybe(self.stop_enrollment());
+                    buttons = buttons.push(cancel);
+                } else if !self.busy {
+                    // The enrollment failed or never started
+                    buttons = buttons.push(cancel_btn).push(
+                        widget::button::suggested(fl!("wizard-try-again"))
+                            .on_press(Message::WizardBack),
+                    );
+                }
+
+                text::body(fl!("wizard-enrolling", finger = finger)).into()
+            }
+            WizardStep::Verify => {
+                if self.verifying_finger.is_some() {
+                    buttons = buttons.push(
+                        widget::button::standard(fl!("cancel")).on_press(Message::VerifyStop),
+                    );
+                } else {
+                    buttons = buttons
+                        .push(
+                            widget::button::standard(fl!("wizard-skip"))
+                                .on_press(Message::WizardNext),
+                        )
+                        .push(
+                            widget::button::suggested(fl!("verify"))
+                                .on_press(Message::WizardVerify),
+                        );
+                }
+
+                text::body(fl!("wizard-verify", finger = finger)).into()
+            }
+            WizardStep::Done => {
+                buttons = buttons
+                    .push(
+                        widget::button::standard(fl!("wizard-another"))
+                            .on_press(Message::WizardAnother),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("wizard-finish"))
+                            .on_press(Message::WizardClose),
+                    );
+
+                text::body(fl!("wizard-done", finger = finger)).into()
+            }
+        };
+
+        Some(
+            widget::column()
+                .push(text::title4(fl!("wizard-title")))
+                .push(text::caption(fl!("wizard-step", step = step, steps = steps)))
+                .push(content)
+                .push(buttons)
+                .spacing(MAIN_SPACING)
+                .align_x(Alignment::Center)
+                .apply(widget::container)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(Horizontal::Center)
+                .align_y(Vertical::Center)
+                .into(),
+        )
+    }
+
+    /// Footer line with the last change made to the prints of the selected user.
+    fn view_last_change(&self) -> Option<Element<'_, Message>> {
+        let user = self.selected_user.as_ref()?;
+        let change = self.config.last_changes.get(user.username.as_str())?;
+
+        Some(
+            text::caption(change.localized_summary(last_change::unix_now()))
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+                .into(),
+        )
+    }
+
+    fn view_controls(&self) -> Element<'_, Message> {
+        let buttons_enabled = !self.busy
+            && self.device_path.is_some()
+            && !self.enrollment.is_active()
+            && self.replace.is_none()
+            && self.batch.is_none()
+            && !self.self_test.running;
+
+        let current_page = self.nav.data::<Page>(self.nav.active());
+        let current_finger = current_page.and_then(|p| p.as_finger_id());
+        let is_enrolled = match (current_finger, current_page) {
+            (Some(f), _) => self.enrolled_fingers.iter().any(|ef| ef == f),
+            (None, Some(Page::DeleteAllUsersPrints)) => !self.enrolled_fingers.is_empty(),
+            (None, _) => false,
+        };
+
+        let register_btn = widget::button::text(fl!("register"));
+        let delete_btn = widget::button::text(fl!("delete"));
+        let clear_btn = widget::button::text(fl!("clear-device"));
+        let replace_btn = widget::button::text(fl!("replace"));
+        let verify_btn = widget::button::text(fl!("verify"));
+        let identify_btn = widget::button::text(fl!("identify"));
+        let wizard_btn = widget::button::text(fl!("wizard-open"));
+        let batch_btn = widget::button::text(fl!("batch-open"));
+
+        let register_btn = if buttons_enabled && current_finger.is_some() {
+            register_btn.on_press(Message::Register)
+        } else {
+            register_btn
+        };
+
+        let can_delete = current_page.is_some_and(|page| self.can_delete(*page));
+        let delete_btn = if buttons_enabled && is_enrolled && can_delete {
+            delete_btn.on_press(Message::Delete)
+        } else {
+            delete_btn
+        };
+
+        let clear_btn = if buttons_enabled
+            && self.may_manage_others
+            && self.capabilities.delete_finger
+        {
+            clear_btn.on_press(Message::ClearDevice)
+        } else {
+            clear_btn
+        };
+
+        let replace_btn = if buttons_enabled && current_finger.is_some() && is_enrolled {
+            replace_btn.on_press(Message::ReplaceStart)
+        } else {
+            replace_btn
+        };
+
+        let verify_btn = if buttons_enabled && current_finger.is_some() && is_enrolled {
+            verify_btn.on_press(Message::Verify)
+        } else {
+            verify_btn
+        };
+
+        let identify_btn = if buttons_enabled && !self.enrolled_fingers.is_empty() {
+            identify_btn.on_press(Message::Identify)
+        } else {
+            identify_btn
+        };
+
+        let wizard_btn = if buttons_enabled {
+            wizard_btn.on_press(Message::WizardStart)
+        } else {
+            wizard_btn
+        };
+
+        let batch_btn = if buttons_enabled {
+            batch_btn.on_press(Message::BatchOpen)
+        } else {
+            batch_btn
+        };
+
+        let mut row = widget::row()
+            .push(register_btn)
+            .push(verify_btn)
+            .push(identify_btn)
+            .push(delete_btn)
+            .push(replace_btn)
+            .push(clear_btn)
+            .push(wizard_btn)
+            .push(batch_btn)
+            .push(self.view_help(HelpTopic::ClearDevice));
+
+        // The batch panel shows its own buttons to skip and stop
+        if self.enrollment.is_active() && self.batch.is_none() {
+            row = row.push(
+                widget::button::text(fl!("cancel")).on_press_maybe(self.stop_enrollment()),
+            );
+        } else if self.clear_cancel.is_some() {
+            row = row.push(widget::button::text(fl!("cancel")).on_press(Message::ClearCancel));
+        } else if self.verifying_finger.is_some()
+            && self.replace.is_none()
+            && !self.self_test.running
+        {
+            // The replace wizard and the self-test show their own cancel button
+            row = row.push(widget::button::text(fl!("cancel")).on_press(Message::VerifyStop));
+        }
+
+        row.apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center)
+            .padding(MAIN_PADDING)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::mock::MockBackend;
+    use fake_accounts::{FakeAccounts, FakeUser};
+    use cosmic::widget::menu::action::MenuAction as _;
+    use fprint_client::fprint::ClearReport;
+    use futures_util::stream::BoxStream;
+
+    /// An app talking to `backend`, with nothing read from or written to disk.
+    fn test_app(backend: &MockBackend, username: &str) -> AppModel {
+        let (mut app, _) = AppModel::init(cosmic::Core::default(), Flags::default());
+        app.config_handler = None;
+        app.state_handler = None;
+        app.state = State::default();
+        app.recovery = None;
+        app.config.confirm_before_delete = false;
+        app.selected_user = Some(UserOption {
+            username: Arc::new(username.to_string()),
+            realname: Arc::new(String::new()),
+            system: false,
+            avatar: None,
+        });
+        let _ = app.set_backend(Arc::new(backend.clone()));
+        let _ = app.update(Message::DevicesFound(vec![MockBackend::device()]));
+        app
+    }
+
+    fn select_page(app: &mut AppModel, page: Page) {
+        let id = app
+            .nav
+            .iter()
+            .find(|id| app.nav.data::<Page>(*id) == Some(&page))
+            .unwrap();
+        app.nav.activate(id);
+    }
+
+    /// Feeds everything the backend reports to the app, like a subscription would.
+    async fn deliver(app: &mut AppModel, messages: BoxStream<'static, Message>) {
+        for message in messages.collect::<Vec<_>>().await {
+            let _ = app.update(message);
+        }
+    }
+
+    #[test]
+    fn test_devices_found_selects_reader() {
+        let app = test_app(&MockBackend::new(&["alice"]), "alice");
+
+        assert_eq!(app.device_path, Some(MockBackend::device().path));
+        assert_eq!(app.device_name(), Some("Mock Reader"));
+        assert!(!app.busy);
+    }
+
+    #[tokio::test]
+    async fn test_enroll() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Register);
+        assert_eq!(app.enrollment.finger().map(|f| f.as_str()), Some("left-index-finger"));
+
+        let path = (*MockBackend::device().path).clone();
+        let enrollment =
+            backend.enroll(path, "left-index-finger".to_string(), "alice".to_string());
+        deliver(&mut app, enrollment).await;
+
+        assert!(!app.enrollment.is_active());
+        assert!(!app.busy);
+        assert_eq!(app.enroll_progress, 2);
+        assert_eq!(backend.prints("alice"), vec!["left-index-finger".to_string()]);
+
+        let journal = app.enroll_journal.as_ref().unwrap();
+        assert_eq!(journal.outcome.as_deref(), Some("enroll-completed"));
+        assert_eq!(journal.stages, Some(3));
+
+        let change = &app.config.last_changes["alice"];
+        assert_eq!(change.kind, ChangeKind::Enrolled);
+        assert_eq!(change.finger.as_deref(), Some("left-index-finger"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_enroll() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        let _ = app.update(Message::BatchOpen);
+        let fingers = &app.batch.as_ref().unwrap().fingers;
+        let position = |page| fingers.iter().position(|f| f.page == page).unwrap();
+        let picked = [
+            position(Page::LeftThumb),
+            position(Page::RightIndex),
+            position(Page::RightMiddle),
+        ];
+        for finger in picked {
+            let _ = app.update(Message::BatchToggle(finger, true));
+        }
+
+        let _ = app.update(Message::BatchStart);
+        let finger = "right-index-finger".to_string();
+        assert_eq!(app.enrollment.finger().map(|f| f.as_str()), Some(finger.as_str()));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::RightIndex));
+        let path = (*MockBackend::device().path).clone();
+        let enrollment = backend.enroll(path, finger.clone(), "alice".to_string());
+        deliver(&mut app, enrollment).await;
+        assert!(app.batch.as_ref().unwrap().is_waiting());
+
+        // Skipping a finger moves on to the next one
+        let _ = app.update(Message::BatchNext);
+        assert_eq!(
+            app.enrollment.finger().map(|f| f.as_str()),
+            Some("right-middle-finger")
+        );
+        let _ = app.update(Message::EnrollStatus("enroll-cancelled".to_string(), true));
+        let _ = app.update(Message::BatchNext);
+        assert_eq!(app.enrollment.finger().map(|f| f.as_str()), Some("left-thumb"));
+
+        // Stopping cancels the finger being enrolled and ends the batch
+        let _ = app.update(Message::BatchStop);
+        let _ = app.update(Message::EnrollStatus("enroll-cancelled".to_string(), true));
+        let batch = app.batch.as_ref().unwrap();
+        assert!(batch.is_done());
+        assert_eq!(batch.count(BatchOutcome::Enrolled), 1);
+        assert_eq!(batch.count(BatchOutcome::Skipped), 2);
+        assert_eq!(backend.prints("alice"), vec![finger]);
+
+        let _ = app.update(Message::BatchClose);
+        assert!(app.batch.is_none());
+    }
+
+    #[test]
+    fn test_unplugged_reader_ends_enrollment() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::Register);
+        assert!(app.enrollment.is_active());
+
+        let _ = app.update(Message::DevicesFound(Vec::new()));
+        assert!(!app.enrollment.is_active());
+        assert!(app.device_path.is_none());
+        assert!(!session::is_claimed());
+        assert_eq!(app.status, fl!("device-removed"));
+
+        // Plugging it back in picks it up again
+        let _ = app.update(Message::DevicesFound(vec![MockBackend::device()]));
+        assert_eq!(app.device_path, Some(MockBackend::device().path));
+        assert!(!app.busy);
+    }
+
+    #[test]
+    fn test_exit_releases_reader_while_enrolling() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+        assert!(app.on_app_exit().is_none());
+
+        let _ = app.update(Message::Register);
+        assert!(matches!(app.on_app_exit(), Some(Message::Exit)));
+        let _ = app.update(Message::Exit);
+        assert!(app.exiting);
+        assert!(app.busy);
+        assert_eq!(app.status, fl!("exit-releasing"));
+    }
+
+    #[test]
+    fn test_enroll_retry_marks_stage() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::EnrollStart(Some(3)));
+
+        let _ = app.update(Message::EnrollStatus("enroll-swipe-too-short".to_string(), false));
+        assert_eq!(app.enroll_retry, Some(RetryReason::TooShort));
+        assert_eq!(app.enroll_progress, 0);
+
+        let _ = app.update(Message::EnrollStatus("enroll-stage-passed".to_string(), false));
+        assert_eq!(app.enroll_retry, None);
+        assert_eq!(app.enroll_progress, 1);
+    }
+
+    #[test]
+    fn test_enroll_notification() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        app.config.notify_in_background = true;
+        let finger = "right-index-finger".to_string();
+        let notify = |app: &AppModel, status: &str, timed_out| {
+            app.enroll_notification(status, Some(&finger), timed_out)
+        };
+
+        // Not while the window is in front
+        assert_eq!(notify(&app, "enroll-completed", false), None);
+
+        let _ = app.update(Message::WindowFocused(false));
+        let notification = notify(&app, "enroll-completed", false).unwrap();
+        let name = Page::RightIndex.localized_name();
+        assert_eq!(notification.summary, fl!("notification-enrolled", finger = name));
+        assert!(notify(&app, "enroll-failed", false).is_some());
+
+        // Stopped from the app, unless by the idle timeout
+        assert_eq!(notify(&app, "enroll-cancelled", false), None);
+        assert!(notify(&app, "enroll-cancelled", true).is_some());
+
+        app.config.notify_in_background = false;
+        assert_eq!(notify(&app, "enroll-completed", false), None);
+    }
+
+    #[test]
+    fn test_self_test_loops() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        app.enrolled_fingers = vec!["right-index-finger".to_string()];
+        select_page(&mut app, Page::Test);
+
+        let _ = app.update(Message::SelfTestStart);
+        assert_eq!(app.verifying_finger.as_deref().map(String::as_str), Some("any"));
+        let _ = app.update(Message::VerifyStatus("verify-match".to_string(), true));
+        assert!(app.verifying_finger.is_none());
+        assert!(app.self_test.waiting);
+        assert!(app.selection_locked());
+
+        // Scans go on after the pause
+        let _ = app.update(Message::SelfTestNext);
+        assert!(app.verifying_finger.is_some());
+        let _ = app.update(Message::VerifyStatus("verify-finger-not-centered".to_string(), false));
+        let _ = app.update(Message::VerifyStatus("verify-no-match".to_string(), true));
+        assert_eq!(app.self_test.match_rate(), Some(50));
+        assert_eq!(app.self_test.retries, 1);
+
+        let _ = app.update(Message::SelfTestNext);
+        let _ = app.update(Message::SelfTestStop);
+        let _ = app.update(Message::VerifyStatus("verify-cancelled".to_string(), true));
+        assert!(!app.self_test.running);
+        assert!(!app.self_test.waiting);
+        assert_eq!(app.self_test.attempts(), 2);
+        assert!(!app.busy);
+    }
+
+    #[test]
+    fn test_enrollment_stops_once() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::Register);
+        assert!(app.stop_enrollment().is_some());
+
+        let _ = app.update(Message::EnrollStop);
+        assert!(app.enrollment.is_cancelling());
+        assert!(app.stop_enrollment().is_none());
+        // fprintd confirming the start late doesn't undo stopping
+        let _ = app.update(Message::EnrollStart(Some(3)));
+        assert!(app.enrollment.is_cancelling());
+
+        let _ = app.update(Message::ClaimChanged(true));
+        let _ = app.update(Message::EnrollStatus("enroll-cancelled".to_string(), true));
+        assert_eq!(app.enrollment, Enrollment::Releasing);
+        assert!(!app.busy);
+        let _ = app.update(Message::ClaimChanged(false));
+        assert_eq!(app.enrollment, Enrollment::Idle);
+    }
+
+    #[test]
+    fn test_remote_session_needs_acknowledgement() {
+        let backend = MockBackend::new(&["alice"]);
+        backend.add_print("alice", "left-index-finger");
+        let mut app = test_app(&backend, "alice");
+        let _ = app.update(Message::SessionLocated(SessionLocation::Remote { host: None }));
+        select_page(&mut app, Page::LeftIndex);
+
+        // Asked even though confirmations are turned off
+        let _ = app.update(Message::Delete);
+        assert!(app.confirmation.is_some());
+
+        let _ = app.update(Message::ConfirmAction);
+        assert!(app.confirmation.is_some());
+        assert_eq!(backend.prints("alice").len(), 1);
+
+        let _ = app.update(Message::AcknowledgeRemote(true));
+        let _ = app.update(Message::ConfirmAction);
+        assert_eq!(app.confirmation, None);
+        assert!(backend.prints("alice").is_empty());
+
+        // The cleanup of a full reader waits for the same box
+        app.current_username = Some("alice".to_string());
+        let _ = app.update(Message::DeleteComplete);
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::EnrollStatus("enroll-data-full".to_string(), true));
+        assert!(!app.remote_acknowledged);
+        let _ = app.update(Message::CleanupPrints(vec![(
+            "alice".to_string(),
+            vec!["right-thumb".to_string()],
+        )]));
+        let _ = app.update(Message::CleanupToggle(0, true));
+        let _ = app.update(Message::CleanupDelete);
+        assert!(!app.cleanup.as_ref().unwrap().deleting);
+
+        let _ = app.update(Message::AcknowledgeRemote(true));
+        let _ = app.update(Message::CleanupDelete);
+        assert!(app.cleanup.as_ref().unwrap().deleting);
+    }
+
+    #[test]
+    fn test_busy_reader_is_waited_for() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::Register);
+
+        let _ = app.update(Message::OperationError(AppError::AlreadyInUse));
+        assert!(matches!(app.device_wait, Some((Message::Register, _))));
+        assert!(app.last_error.is_none());
+        assert!(app.busy);
+
+        // The enrollment starts over once the reader is free
+        let _ = app.update(Message::ClaimProbed(Ok(())));
+        assert!(app.device_wait.is_none());
+        assert!(!app.busy);
+
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::OperationError(AppError::AlreadyInUse));
+        let _ = app.update(Message::DeviceWaitCancel);
+        assert!(app.device_wait.is_none());
+        assert!(!app.busy);
+    }
+
+    #[test]
+    fn test_busy_reader_wait_times_out() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::OperationError(AppError::AlreadyInUse));
+
+        if let Some((_, deadline)) = &mut app.device_wait {
+            *deadline = Instant::now();
+        }
+        let _ = app.update(Message::ClaimRetryTick);
+        assert!(app.device_wait.is_none());
+        assert!(matches!(app.last_error, Some((AppError::AlreadyInUse, Message::Register))));
+
+        // Without a wait the error is shown right away
+        app.config.busy_wait_secs = 0;
+        app.last_error = None;
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::OperationError(AppError::AlreadyInUse));
+        assert!(app.device_wait.is_none());
+        assert!(app.last_error.is_some());
+    }
+
+    #[test]
+    fn test_storage_full_offers_cleanup() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        app.current_username = Some("alice".to_string());
+        app.enrolled_fingers = vec!["right-thumb".to_string()];
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::EnrollStatus("enroll-data-full".to_string(), true));
+        assert_eq!(app.cleanup, Some(StorageCleanup::default()));
+
+        let _ = app.update(Message::CleanupPrints(vec![(
+            "alice".to_string(),
+            vec!["right-thumb".to_string()],
+        )]));
+        let _ = app.update(Message::CleanupToggle(0, true));
+        let _ = app.update(Message::CleanupDelete);
+        assert!(app.busy);
+        assert!(app.cleanup.as_ref().unwrap().deleting);
+
+        let deleted = app.cleanup.as_ref().unwrap().selected();
+        let _ = app.update(Message::CleanupDeleted(deleted, None));
+        assert!(app.cleanup.is_none());
+        assert!(app.enrolled_fingers.is_empty());
+        assert_eq!(app.config.last_changes["alice"].kind, ChangeKind::Deleted);
+    }
+
+    #[test]
+    fn test_cleanup_guarded_like_delete() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        backend.add_print("alice", "right-thumb");
+        backend.add_print("bob", "left-index-finger");
+        let mut app = test_app(&backend, "alice");
+        app.current_username = Some("alice".to_string());
+        app.config.require_verify_before_delete = true;
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::EnrollStatus("enroll-data-full".to_string(), true));
+        let _ = app.update(Message::CleanupPrints(vec![
+            ("alice".to_string(), vec!["right-thumb".to_string()]),
+            ("bob".to_string(), vec!["left-index-finger".to_string()]),
+        ]));
+
+        // Alice's own print waits for her finger, which doesn't match
+        let _ = app.update(Message::CleanupToggle(0, true));
+        let _ = app.update(Message::CleanupDelete);
+        assert!(app.pending_cleanup.is_some());
+        assert!(!app.cleanup.as_ref().unwrap().deleting);
+        let _ = app.update(Message::VerifyStatus("verify-no-match".to_string(), true));
+        assert_eq!(app.pending_cleanup, None);
+        assert_eq!(app.status, fl!("delete-verify-failed"));
+        assert_eq!(backend.prints("alice"), vec!["right-thumb".to_string()]);
+
+        // Bob's print needs polkit, which refuses
+        let _ = app.update(Message::CleanupToggle(0, false));
+        let _ = app.update(Message::CleanupToggle(1, true));
+        let _ = app.update(Message::CleanupDelete);
+        assert_eq!(app.status, fl!("authenticating"));
+        let _ = app.update(Message::CleanupAuthorized(Ok(false)));
+        assert!(!app.cleanup.as_ref().unwrap().deleting);
+        assert_eq!(backend.prints("bob"), vec!["left-index-finger".to_string()]);
+
+        // Asked to confirm first when the settings say so
+        app.config.require_verify_before_delete = false;
+        app.config.confirm_before_delete = true;
+        let _ = app.update(Message::CleanupAuthorized(Ok(true)));
+        assert!(matches!(
+            app.confirmation,
+            Some(Confirmation::Cleanup { .. })
+        ));
+        assert!(!app.cleanup.as_ref().unwrap().deleting);
+    }
+
+    #[test]
+    fn test_nav_menu_enrolls_without_selecting_first() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::Overview);
+
+        let _ = app.update(Message::NavMenu(NavMenuAction::Enroll(Page::LeftThumb)));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::LeftThumb));
+        assert_eq!(app.enrollment.finger().map(|f| f.as_str()), Some("left-thumb"));
+    }
+
+    #[test]
+    fn test_nav_section_header_opens_first_page() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        let header = |app: &AppModel, section: NavSection| {
+            app.nav
+                .iter()
+                .find(|id| app.nav.data::<NavSection>(*id) == Some(&section))
+                .unwrap()
+        };
+        assert_eq!(app.nav.iter().count(), Page::all().len() + NavSection::all().len());
+
+        let _ = app.on_nav_select(header(&app, NavSection::LeftHand));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::LeftThumb));
+
+        // With the thumb hidden, the next finger of the hand is first
+        app.config.hidden_fingers = vec!["right-thumb".to_string()];
+        app.update_nav_pages();
+        let _ = app.on_nav_select(header(&app, NavSection::RightHand));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::RightIndex));
+    }
+
+    #[test]
+    fn test_nav_menu_hides_unused_finger() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        app.enrolled_fingers = vec!["left-thumb".to_string()];
+        select_page(&mut app, Page::LeftPinky);
+        let listed = |app: &AppModel, page: Page| {
+            app.nav.iter().any(|id| app.nav.data::<Page>(id) == Some(&page))
+        };
+
+        // Hiding the open page moves to the overview
+        let _ = app.update(Message::NavMenu(NavMenuAction::Hide(Page::LeftPinky)));
+        assert_eq!(app.config.hidden_fingers, ["left-little-finger"]);
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::Overview));
+        assert!(!listed(&app, Page::LeftPinky));
+        assert_eq!(app.nav_pages.len(), Page::all().len() - 1);
+
+        // Enrolled fingers stay listed
+        let _ = app.update(Message::NavMenu(NavMenuAction::Hide(Page::LeftThumb)));
+        assert!(listed(&app, Page::LeftThumb));
+        app.config.hidden_fingers.push("left-thumb".to_string());
+        app.update_nav_pages();
+        assert!(listed(&app, Page::LeftThumb));
+
+        // Going to a hidden finger from elsewhere lists it again
+        let _ = app.update(Message::SelectFinger(Page::LeftPinky));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::LeftPinky));
+        assert_eq!(app.config.hidden_fingers, ["left-thumb"]);
+
+        let _ = app.update(Message::ShowAllFingers);
+        assert!(app.config.hidden_fingers.is_empty());
+    }
+
+    #[test]
+    fn test_nav_menu_rename() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        let id = app
+            .nav
+            .iter()
+            .find(|id| app.nav.data::<Page>(*id) == Some(&Page::LeftIndex))
+            .unwrap();
+
+        let _ = app.update(Message::NavMenu(NavMenuAction::Rename(Page::LeftIndex)));
+        let _ = app.update(Message::RenameInput(" Work ".to_string()));
+        let _ = app.update(Message::RenameSave);
+        assert!(app.rename.is_none());
+        assert_eq!(app.config.finger_names["alice"]["left-index-finger"], "Work");
+        assert_eq!(app.nav.text(id), Some("Work"));
+        // Shown on the overview too, for that user only
+        let label = app.finger_label("alice", "left-index-finger");
+        assert_eq!(label.map(String::as_str), Some("Work"));
+        assert!(app.finger_label("bob", "left-index-finger").is_none());
+
+        // An empty name restores the default one
+        let _ = app.update(Message::NavMenu(NavMenuAction::Rename(Page::LeftIndex)));
+        let _ = app.update(Message::RenameInput(String::new()));
+        let _ = app.update(Message::RenameSave);
+        assert!(app.config.finger_names.is_empty());
+        assert_eq!(app.nav.text(id), Some(Page::LeftIndex.localized_name().as_str()));
+    }
+
+    #[test]
+    fn test_changes_are_reported() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.report_path = "/dev/null".to_string();
+        app.enrolled_fingers = vec!["left-index-finger".to_string()];
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.handle_message(Message::DeleteComplete);
+        let report = &app.pending_reports[0];
+        assert_eq!(report.operation, "deleted");
+        assert_eq!(report.username, "alice");
+        assert_eq!(report.finger.as_deref(), Some("left-index-finger"));
+        assert_eq!(report.device.as_deref(), Some("Mock Reader"));
+
+        // Reports are sent once the update is handled
+        let _ = app.update(Message::RenameCancel);
+        assert!(app.pending_reports.is_empty());
+    }
+
+    #[test]
+    fn test_operator_mode_logs_actions() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+
+        // Nothing is recorded until operator mode is on
+        let _ = app.handle_message(Message::Register);
+        let _ = app.handle_message(Message::EnrollStatus("enroll-completed".to_string(), true));
+        assert!(app.pending_operator_entries.is_empty());
+
+        app.config.operator_mode = true;
+        let _ = app.handle_message(Message::Register);
+        let _ = app.handle_message(Message::EnrollStatus("enroll-duplicate".to_string(), true));
+        let _ = app.handle_message(Message::Delete);
+        let _ = app.handle_message(Message::OperationError(AppError::PermissionDenied));
+
+        let entries = &app.pending_operator_entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, OperatorAction::Enroll);
+        assert_eq!(entries[0].user, "alice");
+        assert_eq!(entries[0].finger.as_deref(), Some("left-index-finger"));
+        assert_eq!(entries[0].result, "enroll-duplicate");
+        assert_eq!(entries[1].action, OperatorAction::Delete);
+        assert_eq!(entries[1].result, AppError::PermissionDenied.localized_message());
+
+        let _ = app.handle_message(Message::ClearUserDone("alice".to_string()));
+        assert!(app.pending_operator_entries[2].succeeded());
+
+        // Handed to the log once the update is handled
+        let _ = app.update(Message::RenameCancel);
+        assert!(app.pending_operator_entries.is_empty());
+    }
+
+    #[test]
+    fn test_error_offers_retry() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::OperationError(AppError::Timeout));
+        assert!(matches!(
+            app.last_error,
+            Some((AppError::Timeout, Message::Register))
+        ));
+
+        let _ = app.update(Message::ErrorAction(ErrorAction::Retry));
+        assert!(app.last_error.is_none());
+
+        let _ = app.update(Message::OperationError(AppError::DeviceNotFound));
+        let _ = app.update(Message::DismissError);
+        assert!(app.last_error.is_none());
+    }
+
+    #[test]
+    fn test_error_details_collapse_for_new_error() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        let internal = |name: &str| {
+            AppError::Dbus(
+                Box::new(AppError::Internal),
+                error::DbusError {
+                    name: name.to_string(),
+                    message: None,
+                },
+            )
+        };
+
+        let err = internal("net.reactivated.Fprint.Error.Internal");
+        let _ = app.update(Message::OperationError(err));
+        assert!(!app.error_details_open);
+        let _ = app.update(Message::ToggleErrorDetails);
+        assert!(app.error_details_open);
+        let (last, _) = app.last_error.as_ref().unwrap();
+        assert_eq!(*last, AppError::Internal);
+        assert!(last.dbus_error().is_some());
+
+        let _ = app.update(Message::OperationError(AppError::Timeout));
+        assert!(!app.error_details_open);
+    }
+
+    #[test]
+    fn test_service_lost_during_enrollment() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        select_page(&mut app, Page::LeftIndex);
+
+        // fprintd exiting while idle is routine
+        let _ = app.update(Message::ServiceChanged(false));
+        assert!(app.reconnect.is_none());
+
+        let _ = app.update(Message::Register);
+        let _ = app.update(Message::ServiceChanged(false));
+        assert!(!app.enrollment.is_active());
+        assert!(app.reconnect.is_some());
+        assert!(app.busy);
+
+        let _ = app.update(Message::DevicesFound(vec![MockBackend::device()]));
+        assert!(app.reconnect.is_none());
+        assert!(!app.busy);
+    }
+
+    #[test]
+    fn test_system_users_hidden() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let _ = app.update(Message::UsersFound(
+            [("alice", false), ("bob", false), ("gdm", true)]
+                .map(|(name, system)| UserOption {
+                    username: Arc::new(name.to_string()),
+                    realname: Arc::new(String::new()),
+                    system,
+                    avatar: None,
+                })
+                .to_vec(),
+        ));
+
+        let shown = |app: &AppModel| -> Vec<String> {
+            app.shown_users.iter().map(|u| u.username.to_string()).collect()
+        };
+        assert_eq!(shown(&app), ["alice", "bob"]);
+
+        // Without a config handler the setting is changed directly
+        app.config.show_system_users = true;
+        app.update_shown_users();
+        assert_eq!(shown(&app), ["alice", "bob", "gdm"]);
+    }
+
+    #[tokio::test]
+    async fn test_identify_names_finger() {
+        let backend = MockBackend::new(&["alice"]);
+        backend.add_print("alice", "left-thumb");
+        let mut app = test_app(&backend, "alice");
+        app.enrolled_fingers = backend.prints("alice");
+
+        let _ = app.update(Message::Identify);
+        assert_eq!(app.verifying_finger.as_deref().map(String::as_str), Some("any"));
+
+        let path = (*MockBackend::device().path).clone();
+        deliver(&mut app, backend.verify(path, "any".to_string(), "alice".to_string())).await;
+
+        assert_eq!(app.identifying, None);
+        assert!(app.status.contains(&Page::LeftThumb.localized_name()));
+    }
+
+    #[test]
+    fn test_pulse_slows_down() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        assert_eq!(pulse_interval(app.window_focused, app.power_saver), PULSE_INTERVAL);
+
+        let _ = app.update(Message::WindowFocused(false));
+        assert_eq!(
+            pulse_interval(app.window_focused, app.power_saver),
+            PULSE_INTERVAL_LOW_POWER
+        );
+
+        let _ = app.update(Message::WindowFocused(true));
+        let _ = app.update(Message::PowerSaverChanged(true));
+        assert_eq!(
+            pulse_interval(app.window_focused, app.power_saver),
+            PULSE_INTERVAL_LOW_POWER
+        );
+    }
+
+    #[test]
+    fn test_switching_user_aborts_stale_listing() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let bob = UserOption {
+            username: Arc::new("bob".to_string()),
+            realname: Arc::new(String::new()),
+            system: false,
+            avatar: None,
+        };
+        let alice = UserOption {
+            username: Arc::new("alice".to_string()),
+            ..bob.clone()
+        };
+        let _ = app.update(Message::UsersFound(vec![alice, bob.clone()]));
+        let listing = app.fetches.fingers.clone().unwrap();
+        assert!(!listing.is_aborted());
+
+        let _ = app.update(Message::UserSelected(bob));
+        assert!(listing.is_aborted());
+        let listing = app.fetches.fingers.clone().unwrap();
+        assert!(!listing.is_aborted());
+
+        // A new connection drops whatever the old one was listing
+        let _ = app.set_backend(Arc::new(backend.clone()));
+        assert!(listing.is_aborted());
+    }
+
+    #[test]
+    fn test_stale_prints_dropped() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+
+        // Bob's listing arrives after switching to alice
+        let fingers = vec!["left-thumb".to_string()];
+        let _ = app.update(Message::EnrolledFingers("bob".to_string(), fingers.clone()));
+        assert!(app.enrolled_fingers.is_empty());
+
+        let _ = app.update(Message::EnrolledFingers("alice".to_string(), fingers.clone()));
+        assert_eq!(app.enrolled_fingers, fingers);
+    }
+
+    #[test]
+    fn test_deleted_user_leaves_picker() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let users = |names: &[&str]| -> Vec<UserOption> {
+            names
+                .iter()
+                .map(|name| UserOption {
+                    username: Arc::new(name.to_string()),
+                    realname: Arc::new(String::new()),
+                    system: false,
+                    avatar: None,
+                })
+                .collect()
+        };
+        let _ = app.update(Message::UsersFound(users(&["alice", "bob"])));
+        let _ = app.update(Message::UserSelected(users(&["bob"]).remove(0)));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("bob"));
+
+        // Listed again after AccountsService deleted bob
+        let _ = app.update(Message::UsersFound(users(&["alice"])));
+        assert_eq!(app.shown_users.len(), 1);
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("alice"));
     }
 
-    fn on_enroll_status(&mut self, status: String, done: bool) -> Task<cosmic::Action<Message>> {
-        let status_msg = match status.as_str() {
-            "enroll-stage-passed" => {
-                self.enroll_progress += 1;
-                fl!("enroll-stage-passed")
-            }
-            "enroll-retry-scan" => fl!("enroll-retry-scan"),
-            "enroll-swipe-too-short" => fl!("enroll-swipe-too-short"),
-            "enroll-finger-not-centered" => fl!("enroll-finger-not-centered"),
-            "enroll-remove-and-retry" => fl!("enroll-remove-and-retry"),
-            "enroll-unknown-error" => fl!("enroll-unknown-error"),
-            "enroll-completed" => fl!("enroll-completed"),
-            "enroll-failed" => fl!("enroll-failed"),
-            "enroll-disconnected" => fl!("enroll-disconnected"),
-            "enroll-data-full" => fl!("enroll-data-full"),
-            "enroll-too-fast" => fl!("enroll-too-fast"),
-            "enroll-duplicate" => fl!("enroll-duplicate"),
-            "enroll-cancelled" => fl!("enroll-cancelled"),
-            _ => status.clone(),
-        };
-        self.status = status_msg;
+    #[test]
+    fn test_restores_last_user_and_device() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let users: Vec<UserOption> = ["alice", "bob"]
+            .map(|name| UserOption {
+                username: Arc::new(name.to_string()),
+                realname: Arc::new(String::new()),
+                system: false,
+                avatar: None,
+            })
+            .to_vec();
+        let second = DeviceOption {
+            path: Arc::new(
+                zbus::zvariant::OwnedObjectPath::try_from("/net/reactivated/Fprint/Device/1")
+                    .unwrap(),
+            ),
+            name: Arc::new("Second Reader".to_string()),
+            ..MockBackend::device()
+        };
+        let devices = vec![MockBackend::device(), second.clone()];
 
-        if done {
-            self.busy = false;
-            self.enrolling_finger = None;
+        let _ = app.update(Message::DevicesFound(devices.clone()));
+        let _ = app.update(Message::DeviceSelected(second.clone()));
+        let _ = app.update(Message::UsersFound(users.clone()));
+        let _ = app.update(Message::UserSelected(users[1].clone()));
+        assert_eq!(app.config.last_user, "bob");
+        assert_eq!(app.config.last_device, second.path.as_str());
 
-            if status == "enroll-completed" {
-                return self.list_fingers_task();
-            }
-        }
-        Task::none()
-    }
+        // Both are picked again on the next start, once the config is read
+        let config = app.config.clone();
+        let mut app = test_app(&backend, "alice");
+        let _ = app.update(Message::DevicesFound(devices));
+        assert_eq!(app.device_path, Some(MockBackend::device().path));
+        let _ = app.update(Message::UpdateConfig(config));
+        assert_eq!(app.device_path, Some(second.path));
+        let _ = app.update(Message::UsersFound(users));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("bob"));
 
-    fn on_enroll_stop(&self) -> Task<cosmic::Action<Message>> {
-        if let (Some(path), Some(conn)) = (self.device_path.clone(), self.connection.clone()) {
-            let path = (*path).clone();
-            return Task::perform(
-                async move {
-                    let device = DeviceProxy::builder(&conn).path(path)?.build().await?;
-                    let _ = device.enroll_stop().await;
-                    device.release().await?;
-                    Ok::<(), zbus::Error>(())
-                },
-                |res| match res {
-                    Ok(_) => cosmic::Action::App(Message::EnrollStatus(
-                        "enroll-cancelled".to_string(),
-                        true,
-                    )),
-                    Err(e) => cosmic::Action::App(Message::OperationError(AppError::from(e))),
-                },
-            );
-        }
-        Task::none()
+        // A user that no longer exists is not restored
+        let mut app = test_app(&backend, "alice");
+        app.config.last_user = "carol".to_string();
+        app.config_loaded = true;
+        let _ = app.update(Message::UsersFound(vec![UserOption {
+            username: Arc::new("alice".to_string()),
+            realname: Arc::new(String::new()),
+            system: false,
+            avatar: None,
+        }]));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("alice"));
     }
 
-    fn on_clear_device(&mut self) -> Task<cosmic::Action<Message>> {
-        if !self.confirm_clear {
-            self.confirm_clear = true;
-            return Task::none();
-        }
+    #[tokio::test]
+    async fn test_users_from_accounts_service() {
+        let service = FakeUser {
+            system_account: Some(true),
+            ..FakeUser::new(990, "printer")
+        };
+        let users = vec![FakeUser::new(1000, "alice"), service, FakeUser::new(1001, "bob")];
+        let (_accounts, connection) = FakeAccounts::serve(users).await;
 
-        if let (Some(path), Some(conn)) = (self.device_path.clone(), self.connection.clone()) {
-            self.status = fl!("clearing-device");
-            self.busy = true;
-            self.confirm_clear = false;
-            let path = (*path).clone();
-            let usernames: Vec<String> = self.users.iter().map(|u| (*u.username).clone()).collect();
-            return Task::perform(
-                async move {
-                    match clear_all_fingers_dbus(&conn, path, usernames).await {
-                        Ok(_) => Message::ClearComplete(Ok(())),
-                        Err(e) => Message::ClearComplete(Err(AppError::from(e))),
-                    }
-                },
-                cosmic::Action::App,
-            );
-        }
-        Task::none()
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        app.config.show_all_users = true;
+        let users = ZbusBackend::new(connection).list_users().await;
+        let _ = app.update(Message::UsersFound(users));
+
+        assert_eq!(app.users.len(), 3);
+        let shown: Vec<&str> = app.shown_users.iter().map(|u| u.username.as_str()).collect();
+        assert_eq!(shown, ["alice", "bob"]);
     }
 
-    fn on_delete(&mut self) -> Task<cosmic::Action<Message>> {
-        if let Some(page) = self.nav.data::<Page>(self.nav.active())
-            && let (Some(path), Some(conn), Some(user)) = (
-                self.device_path.clone(),
-                self.connection.clone(),
-                self.selected_user.clone(),
-            )
-        {
-            self.status = fl!("deleting");
-            self.busy = true;
-            let path = (*path).clone();
-            let username = (*user.username).clone();
+    #[test]
+    fn test_user_menu_closes_on_select() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        let user = app.selected_user.clone().unwrap();
 
-            if let Some(finger_name) = page.as_finger_id() {
-                let finger_name = finger_name.to_string();
-                return Task::perform(
-                    async move {
-                        match delete_fingerprint_dbus(&conn, path, finger_name, username).await {
-                            Ok(_) => Message::DeleteComplete,
-                            Err(e) => Message::OperationError(AppError::from(e)),
-                        }
-                    },
-                    cosmic::Action::App,
-                );
-            } else {
-                return Task::perform(
-                    async move {
-                        match delete_fingers(&conn, path, username).await {
-                            Ok(_) => Message::DeleteComplete,
-                            Err(e) => Message::OperationError(AppError::from(e)),
-                        }
-                    },
-                    cosmic::Action::App,
-                );
-            }
-        }
-        Task::none()
-    }
+        let _ = app.update(Message::ToggleUserMenu);
+        assert!(app.user_menu_open);
+        let _ = app.update(Message::UserSelected(user));
+        assert!(!app.user_menu_open);
 
-    fn on_register(&mut self) -> Task<cosmic::Action<Message>> {
-        if let Some(page) = self.nav.data::<Page>(self.nav.active())
-            && let Some(finger_id) = page.as_finger_id()
-            && self.device_path.is_some()
-            && self.selected_user.is_some()
-        {
-            self.busy = true;
-            self.enrolling_finger = Some(Arc::new(finger_id.to_string()));
-            self.status = fl!("status-starting-enrollment");
-        }
-        Task::none()
+        let _ = app.update(Message::ToggleUserMenu);
+        let _ = app.update(Message::CloseUserMenu);
+        assert!(!app.user_menu_open);
     }
 
-    /// Updates the header and window titles.
-    pub fn update_title(&mut self) -> Task<cosmic::Action<Message>> {
-        let mut window_title = fl!("app-title");
+    #[test]
+    fn test_open_page_and_user() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let users: Vec<UserOption> = ["alice", "bob"]
+            .map(|name| UserOption {
+                username: Arc::new(name.to_string()),
+                realname: Arc::new(String::new()),
+                system: false,
+                avatar: None,
+            })
+            .to_vec();
 
-        if let Some(page) = self.nav.text(self.nav.active()) {
-            window_title.push_str(" — ");
-            window_title.push_str(page);
-        }
+        // Asked at startup, before the users are known
+        let target = OpenTarget::parse("open?page=left-thumb&user=bob").unwrap();
+        let _ = app.update(Message::Open(target));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::LeftThumb));
+        let _ = app.update(Message::UsersFound(users));
+        let _ = app.update(Message::UpdateConfig(app.config.clone()));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("bob"));
+        let _ = app.update(Message::AuthenticationComplete(Ok(true)));
 
-        if let Some(id) = self.core.main_window_id() {
-            self.set_window_title(window_title, id)
-        } else {
-            Task::none()
-        }
+        // Asked while running
+        let target = OpenTarget::parse("open?page=overview&user=alice").unwrap();
+        let _ = app.update(Message::Open(target));
+        assert_eq!(app.nav.data::<Page>(app.nav.active()), Some(&Page::Overview));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("alice"));
     }
 
-    fn view_header(&self) -> Element<'_, Message> {
-        text::title1(fl!("fprint"))
-            .apply(widget::container)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .align_x(Horizontal::Center)
-            .align_y(Vertical::Center)
-            .into()
-    }
+    #[test]
+    fn test_user_dropped() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let _ = app.update(Message::UsersFound(
+            ["alice", "bob"]
+                .map(|name| UserOption {
+                    username: Arc::new(name.to_string()),
+                    realname: Arc::new(String::new()),
+                    system: false,
+                    avatar: None,
+                })
+                .to_vec(),
+        ));
 
-    fn view_user_picker(&self) -> Option<Element<'_, Message>> {
-        if self.users.is_empty() {
-            return None;
-        }
+        let _ = app.update(Message::UserDropped(Some("bob".to_string())));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("bob"));
 
-        Some(
-            pick_list(
-                self.users.as_slice(),
-                self.selected_user.clone(),
-                Message::UserSelected,
-            )
-            .width(Length::Fixed(200.0))
-            .apply(widget::container)
-            .width(Length::Fill)
-            .align_x(Horizontal::Center)
-            .into(),
-        )
+        let _ = app.update(Message::UserDropped(Some("zed".to_string())));
+        assert_eq!(app.status, fl!("user-drop-unknown", user = "zed"));
+        assert_eq!(app.selected_user.as_ref().map(|u| u.username.as_str()), Some("bob"));
     }
 
-    fn view_icon(&self) -> Element<'_, Message> {
-        widget::svg(widget::svg::Handle::from_memory(FPRINT_ICON))
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+    #[test]
+    fn test_delete_unsupported_by_fprintd() {
+        let backend = MockBackend::new(&["alice"]);
+        backend.add_print("alice", "left-index-finger");
+        let mut app = test_app(&backend, "alice");
+        app.config.confirm_before_delete = true;
+        select_page(&mut app, Page::LeftIndex);
+        let _ = app.update(Message::CapabilitiesProbed(Capabilities {
+            delete_finger: false,
+            delete_all_fingers: true,
+        }));
+
+        let _ = app.update(Message::Delete);
+        assert_eq!(app.confirmation, None);
+        assert_eq!(app.status, fl!("delete-unsupported"));
+        let _ = app.update(Message::ClearDevice);
+        assert_eq!(app.confirmation, None);
+        assert_eq!(backend.prints("alice").len(), 1);
+
+        // Every print of the user can still go at once
+        select_page(&mut app, Page::DeleteAllUsersPrints);
+        let _ = app.update(Message::Delete);
+        assert!(matches!(
+            app.confirmation,
+            Some(Confirmation::Delete { page: Page::DeleteAllUsersPrints, .. })
+        ));
     }
 
-    fn view_status(&self) -> Element<'_, Message> {
-        widget::text(&self.status)
-            .size(STATUS_TEXT_SIZE)
-            .apply(widget::container)
-            .width(Length::Fill)
-            .align_x(Horizontal::Center)
-            .into()
+    #[test]
+    fn test_delete_asks_first() {
+        let backend = MockBackend::new(&["alice"]);
+        backend.add_print("alice", "left-index-finger");
+        let mut app = test_app(&backend, "alice");
+        app.config.confirm_before_delete = true;
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Delete);
+        assert!(matches!(
+            app.confirmation,
+            Some(Confirmation::Delete { page: Page::LeftIndex, .. })
+        ));
+
+        let _ = app.update(Message::CancelConfirm);
+        assert_eq!(app.confirmation, None);
+        assert_eq!(backend.prints("alice").len(), 1);
+
+        let _ = app.update(Message::Delete);
+        let _ = app.update(Message::ConfirmAction);
+        assert_eq!(app.confirmation, None);
+        assert!(backend.prints("alice").is_empty());
     }
 
-    fn view_progress(&self) -> Option<Element<'_, Message>> {
-        self.enrolling_finger.as_ref()?;
+    #[test]
+    fn test_clear_requires_authentication() {
+        let backend = MockBackend::new(&["alice"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.require_auth_before_clear = true;
 
-        self.enroll_total_stages.map(|total| {
-            widget::progress_bar(0.0..=(total as f32), self.enroll_progress as f32)
-                .height(PROGRESS_BAR_HEIGHT)
-                .into()
-        })
+        let _ = app.update(Message::ClearDevice);
+        assert!(app.busy);
+        assert_eq!(app.confirmation, None);
+
+        let _ = app.update(Message::ClearAuthorized(Ok(false)));
+        assert_eq!(app.status, fl!("clear-authentication-failed"));
+        assert_eq!(app.confirmation, None);
+
+        let _ = app.update(Message::ClearDevice);
+        let _ = app.update(Message::ClearAuthorized(Ok(true)));
+        assert!(!app.busy);
+        assert_eq!(app.confirmation, Some(Confirmation::ClearDevice));
     }
 
-    fn view_controls(&self) -> Element<'_, Message> {
-        let buttons_enabled =
-            !self.busy && self.device_path.is_some() && self.enrolling_finger.is_none();
+    #[test]
+    fn test_clear_reports_failed_users() {
+        let backend = MockBackend::new(&["alice", "bob", "carol"]);
+        let mut app = test_app(&backend, "alice");
+        app.set_unfinished_clear(vec!["bob".to_string()]);
 
-        let current_page = self.nav.data::<Page>(self.nav.active());
-        let current_finger = current_page.and_then(|p| p.as_finger_id());
-        let is_enrolled = if let Some(f) = current_finger {
-            self.enrolled_fingers.iter().any(|ef| ef == f)
-        } else {
-            !self.enrolled_fingers.is_empty()
+        let report = ClearReport {
+            cleared: vec!["alice".to_string(), "carol".to_string()],
+            failed: vec![("bob".to_string(), AppError::PermissionDenied)],
         };
+        let _ = app.update(Message::ClearComplete(Ok(report)));
+        let failures = fl!(
+            "clear-user-failed",
+            user = "bob",
+            error = AppError::PermissionDenied.localized_title()
+        );
+        assert_eq!(
+            app.status,
+            fl!("clear-partial", cleared = 2, total = 3, failures = failures)
+        );
+        // Bob is offered to be cleared again
+        assert_eq!(app.state.unfinished_clear, ["bob"]);
+        assert!(!app.busy);
+    }
 
-        let register_btn = widget::button::text(fl!("register"));
-        let delete_btn = widget::button::text(fl!("delete"));
-        let clear_btn = widget::button::text(fl!("clear-device"));
+    #[test]
+    fn test_clear_resume_confirmed() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.set_unfinished_clear(vec!["bob".to_string()]);
+        let _ = app.update(Message::SessionLocated(SessionLocation::Remote {
+            host: None,
+        }));
 
-        let register_btn = if buttons_enabled && current_finger.is_some() {
-            register_btn.on_press(Message::Register)
-        } else {
-            register_btn
-        };
+        let _ = app.update(Message::ClearResume);
+        assert_eq!(
+            app.confirmation,
+            Some(Confirmation::ClearResume {
+                users: vec!["bob".to_string()]
+            })
+        );
+        let _ = app.update(Message::ConfirmAction);
+        assert!(app.clear_cancel.is_none());
 
-        let delete_btn = if buttons_enabled && is_enrolled {
-            delete_btn.on_press(Message::Delete)
-        } else {
-            delete_btn
-        };
+        let _ = app.update(Message::AcknowledgeRemote(true));
+        let _ = app.update(Message::ConfirmAction);
+        assert_eq!(app.confirmation, None);
+        assert!(app.clear_cancel.is_some());
+    }
 
-        let clear_btn = if !self.busy && self.device_path.is_some() && self.enrolling_finger.is_none()
-        {
-            clear_btn.on_press(Message::ClearDevice)
-        } else {
-            clear_btn
-        };
+    #[test]
+    fn test_clear_resume_requires_auth() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.require_auth_before_clear = true;
+        app.set_unfinished_clear(vec!["bob".to_string()]);
 
-        let mut cancel_btn = widget::button::text(fl!("cancel"));
-        if self.enrolling_finger.is_some() {
-            cancel_btn = cancel_btn.on_press(Message::EnrollStop);
-        }
+        // Waits for ClearAuthorized instead of clearing
+        let _ = app.update(Message::ClearResume);
+        assert!(app.busy);
+        assert_eq!(app.status, fl!("clear-authenticating"));
+        assert_eq!(app.confirmation, None);
+        assert!(app.clear_cancel.is_none());
 
-        let mut row = widget::row()
-            .push(register_btn)
-            .push(delete_btn)
-            .push(clear_btn);
+        let _ = app.update(Message::ClearAuthorized(Ok(true)));
+        assert!(matches!(
+            app.confirmation,
+            Some(Confirmation::ClearResume { .. })
+        ));
+        assert!(app.clear_cancel.is_none());
+    }
 
-        if self.enrolling_finger.is_some() {
-            row = row.push(cancel_btn);
-        }
+    #[test]
+    fn test_other_users_forbidden() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        let mut app = test_app(&backend, "alice");
+        app.config.show_all_users = true;
+        let current = User::from_uid(Uid::current()).unwrap().unwrap().name;
+        let users = [current.as_str(), "alice"]
+            .map(|name| UserOption {
+                username: Arc::new(name.to_string()),
+                realname: Arc::new(String::new()),
+                system: false,
+                avatar: None,
+            })
+            .to_vec();
+        let _ = app.update(Message::UsersFound(users));
+        assert!(app.shows_user_picker());
 
-        row.apply(widget::container)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .align_x(Horizontal::Center)
-            .align_y(Vertical::Center)
-            .padding(MAIN_PADDING)
-            .into()
+        let _ = app.update(Message::ManageOthersChecked(false));
+        // Only the current user is left, and can't be switched away from
+        assert_eq!(
+            app.selected_user.as_ref().map(|u| u.username.as_str()),
+            Some(current.as_str())
+        );
+        assert_eq!(app.shown_users.len(), 1);
+        assert!(!app.shows_user_picker());
+
+        let _ = app.update(Message::UserDropped(Some("alice".to_string())));
+        assert_eq!(app.status, fl!("user-drop-hidden", user = "alice"));
+
+        let _ = app.update(Message::ClearDevice);
+        assert!(!app.busy);
+        assert_eq!(app.confirmation, None);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmic::widget::menu::action::MenuAction as _;
+    #[tokio::test]
+    async fn test_delete_requires_matching_finger() {
+        let backend = MockBackend::new(&["alice", "bob"]);
+        backend.add_print("bob", "left-index-finger");
+        let mut app = test_app(&backend, "alice");
+        app.config.require_verify_before_delete = true;
+        select_page(&mut app, Page::LeftIndex);
+
+        let _ = app.update(Message::Delete);
+        assert_eq!(app.pending_delete, Some(Page::LeftIndex));
+
+        // Alice has no print to verify with
+        let path = (*MockBackend::device().path).clone();
+        deliver(&mut app, backend.verify(path, "any".to_string(), "alice".to_string())).await;
+
+        assert_eq!(app.pending_delete, None);
+        assert_eq!(app.status, fl!("delete-verify-failed"));
+        assert_eq!(backend.prints("bob"), vec!["left-index-finger".to_string()]);
+    }
 
     #[test]
     fn test_app_error_localization() {
@@ -988,12 +7147,189 @@ mod tests {
             action.message(),
             Message::ToggleContextPage(ContextPage::About)
         ));
+
+        let action = MenuAction::DeviceInfo;
+        assert!(matches!(
+            action.message(),
+            Message::ToggleContextPage(ContextPage::DeviceInfo)
+        ));
+
+        let action = MenuAction::Settings;
+        assert!(matches!(
+            action.message(),
+            Message::ToggleContextPage(ContextPage::Settings)
+        ));
+
+        let action = MenuAction::ActivityLog;
+        assert!(matches!(
+            action.message(),
+            Message::ToggleContextPage(ContextPage::ActivityLog)
+        ));
+    }
+
+    #[test]
+    fn test_activity_log_keeps_statuses() {
+        let mut app = test_app(&MockBackend::new(&["alice"]), "alice");
+        let _ = app.update(Message::ToggleContextPage(ContextPage::ActivityLog));
+        assert!(app.core.window.show_context);
+
+        let _ = app.update(Message::OperationError(AppError::PermissionDenied));
+        app.status = fl!("enroll-completed");
+        let _ = app.update(Message::DismissError);
+
+        let log = app.status_history.to_text();
+        assert!(log.contains(&AppError::PermissionDenied.localized_message()));
+        assert!(log.ends_with(&format!("{}\n", fl!("enroll-completed"))));
+    }
+}
+
+/// A nav bar listing `pages` under the header of their section, with
+/// `active` open.
+fn build_nav(pages: &[Page], active: Option<Page>) -> nav_bar::Model {
+    let mut nav = nav_bar::Model::default();
+    let mut section = None;
+    for page in pages {
+        let page_section = NavSection::of(*page);
+        if section != Some(page_section) {
+            nav.insert()
+                .text(page_section.localized_name())
+                .data::<NavSection>(page_section)
+                .divider_above(section.is_some());
+            section = Some(page_section);
+        }
+        nav.insert()
+            .text(page.localized_name())
+            .data::<Page>(*page)
+            .icon(icon::from_name(nav_icon(*page, FingerStatus::NotEnrolled)));
+    }
+
+    if let Some(page) = active
+        && let Some(id) = nav.iter().find(|id| nav.data::<Page>(*id) == Some(&page))
+    {
+        nav.activate(id);
+    }
+    nav
+}
+
+/// Icon of `page` in the nav bar, marking fingers by their `status`.
+fn nav_icon(page: Page, status: FingerStatus) -> &'static str {
+    if page == Page::Overview {
+        return NAV_OVERVIEW_ICON;
+    }
+    if page == Page::Test {
+        return NAV_TEST_ICON;
+    }
+
+    match status {
+        FingerStatus::Enrolled => NAV_ENROLLED_ICON,
+        FingerStatus::NotEnrolled => NAV_ICON,
+        FingerStatus::Attention => NAV_ATTENTION_ICON,
+    }
+}
+
+/// Icon shown next to the status while enrolling.
+fn scan_icon(scan_type: ScanType) -> &'static str {
+    match scan_type {
+        ScanType::Press => "input-touchpad-symbolic",
+        ScanType::Swipe => "go-down-symbolic",
+    }
+}
+
+/// Serves the enrollment progress signal on the session bus.
+fn progress_bus_task() -> Task<cosmic::Action<Message>> {
+    Task::perform(progress_signal::serve(), |connection| match connection {
+        Ok(connection) => cosmic::Action::App(Message::ProgressBusReady(connection)),
+        Err(e) => {
+            tracing::debug!(%e, "Failed to serve enrollment progress on the session bus");
+            cosmic::Action::None
+        }
+    })
+}
+
+/// Reads the PAM configuration off the UI thread.
+fn pam_status_task() -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async move {
+            let status =
+                tokio::task::spawn_blocking(|| (read_pam_status(), enable_command().is_some()))
+                    .await;
+            match status {
+                Ok((status, can_enable)) => Message::PamStatusLoaded(Some(status), can_enable),
+                Err(e) => {
+                    tracing::error!("PAM status task join error: {}", e);
+                    Message::PamStatusLoaded(None, false)
+                }
+            }
+        },
+        cosmic::Action::App,
+    )
+}
+
+/// How often the active finger pulses, slower when the window is in the
+/// background or battery saver is on.
+fn pulse_interval(window_focused: bool, power_saver: bool) -> Duration {
+    if window_focused && !power_saver {
+        PULSE_INTERVAL
+    } else {
+        PULSE_INTERVAL_LOW_POWER
     }
 }
 
+/// A user's picture, or a generic one, next to their name.
+fn user_row(user: &UserOption) -> Element<'_, Message> {
+    let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+    let avatar = match &user.avatar {
+        Some(path) => icon::from_path(path.to_path_buf()),
+        None => icon::from_name("avatar-default-symbolic").handle(),
+    };
+    widget::row()
+        .push(icon::icon(avatar).size(AVATAR_SIZE))
+        .push(text::body(user.to_string()).width(Length::Fill))
+        .spacing(space_xs)
+        .align_y(Vertical::Center)
+        .into()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MenuAction {
     About,
+    DeviceInfo,
+    Settings,
+    ActivityLog,
+    OperatorLog,
+    ExportDebugLog,
+}
+
+/// Actions in the context menu of a finger's nav item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NavMenuAction {
+    Enroll(Page),
+    Test(Page),
+    Delete(Page),
+    Rename(Page),
+    /// Leaves the finger out of the nav bar until it is enrolled.
+    Hide(Page),
+}
+
+impl NavMenuAction {
+    pub fn page(self) -> Page {
+        match self {
+            Self::Enroll(page)
+            | Self::Test(page)
+            | Self::Delete(page)
+            | Self::Rename(page)
+            | Self::Hide(page) => page,
+        }
+    }
+}
+
+impl menu::action::MenuAction for NavMenuAction {
+    type Message = cosmic::Action<Message>;
+
+    fn message(&self) -> Self::Message {
+        cosmic::Action::App(Message::NavMenu(*self))
+    }
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -1002,6 +7338,11 @@ impl menu::action::MenuAction for MenuAction {
     fn message(&self) -> Self::Message {
         match self {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
+            MenuAction::DeviceInfo => Message::ToggleContextPage(ContextPage::DeviceInfo),
+            MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            MenuAction::ActivityLog => Message::ToggleContextPage(ContextPage::ActivityLog),
+            MenuAction::OperatorLog => Message::ToggleContextPage(ContextPage::OperatorLog),
+            MenuAction::ExportDebugLog => Message::ExportDebugLog,
         }
     }
 }
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Desktop notifications, sent when an enrollment ends while the window is in
+//! the background.
+
+use fprint_client::notifications_dbus::NotificationsProxy;
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+
+/// Lets the server leave the notification up as long as it usually does.
+const DEFAULT_EXPIRE: i32 = -1;
+
+/// What the notification says.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    pub summary: String,
+    pub body: String,
+}
+
+/// Shows `notification` for the app `app_id`, logging when it couldn't be.
+pub async fn notify(
+    connection: zbus::Connection,
+    app_name: String,
+    app_id: &'static str,
+    notification: Notification,
+) {
+    let sent = async {
+        let proxy = NotificationsProxy::new(&connection).await?;
+        // Lets the server group it with the app and apply its settings
+        let hints = HashMap::from([("desktop-entry", Value::from(app_id))]);
+        proxy
+            .notify(
+                &app_name,
+                0,
+                app_id,
+                &notification.summary,
+                &notification.body,
+                &[],
+                hints,
+                DEFAULT_EXPIRE,
+            )
+            .await
+    };
+    if let Err(e) = sent.await {
+        tracing::debug!(%e, "Failed to send a notification");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use zbus::zvariant::OwnedValue;
+
+    type Sent = Arc<Mutex<Vec<(String, String, String, String)>>>;
+
+    struct Server(Sent);
+
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl Server {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            app_name: String,
+            _replaces_id: u32,
+            _app_icon: String,
+            summary: String,
+            body: String,
+            _actions: Vec<String>,
+            hints: HashMap<String, OwnedValue>,
+            _expire_timeout: i32,
+        ) -> u32 {
+            let entry = hints
+                .get("desktop-entry")
+                .and_then(|value| String::try_from(value.clone()).ok())
+                .unwrap_or_default();
+            self.0.lock().unwrap().push((app_name, entry, summary, body));
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let sent = Sent::default();
+        let (server_stream, client_stream) = tokio::net::UnixStream::pair().unwrap();
+        let server = zbus::connection::Builder::unix_stream(server_stream)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at("/org/freedesktop/Notifications", Server(sent.clone()))
+            .unwrap()
+            .build();
+        let client = zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build();
+        let (_server, client) = futures_util::try_join!(server, client).unwrap();
+
+        let notification = Notification {
+            summary: "Fingerprint enrolled".to_string(),
+            body: "Right Index for alice".to_string(),
+        };
+        notify(
+            client,
+            "Fprint Enroll".to_string(),
+            "fi.joonastuomi.Fprint",
+            notification,
+        )
+        .await;
+
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![(
+                "Fprint Enroll".to_string(),
+                "fi.joonastuomi.Fprint".to_string(),
+                "Fingerprint enrolled".to_string(),
+                "Right Index for alice".to_string(),
+            )]
+        );
+    }
+}
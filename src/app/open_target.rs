@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Opening the window on a finger or a user, from the command line or from
+//! COSMIC Settings and the launcher.
+//!
+//! The target is passed as the action of `org.freedesktop.Application`, which
+//! the running instance serves under its app id, so other components open a
+//! page with e.g.
+//!
+//! ```text
+//! gdbus call --session --dest fi.joonastuomi.Fprint --object-path /fi/joonastuomi/Fprint \
+//!     --method org.freedesktop.Application.ActivateAction \
+//!     'open?user=alice&page=right-index-finger' '[]' '{}'
+//! ```
+//!
+//! Launching the app again with `--page` and `--user` sends the same action.
+
+use crate::app::page::Page;
+use std::fmt;
+
+/// Name of the action, before the `?` of the target.
+pub const ACTION: &str = "open";
+
+/// Names of the overview and self-test pages in a target; fingers go by
+/// their fprintd name.
+const OVERVIEW: &str = "overview";
+const TEST: &str = "test";
+
+/// Page and user to show, each left as it is when `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpenTarget {
+    pub page: Option<Page>,
+    pub user: Option<String>,
+}
+
+impl OpenTarget {
+    /// Reads an action like `open?user=alice&page=right-index-finger`.
+    /// Unknown keys are skipped so newer callers still reach older apps.
+    pub fn parse(action: &str) -> Result<Self, String> {
+        let query = match action.split_once('?') {
+            Some((ACTION, query)) => query,
+            None if action == ACTION => "",
+            _ => return Err(format!("Unknown action {action}")),
+        };
+
+        let mut target = Self::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            match pair.split_once('=') {
+                Some(("page", page)) => target.page = Some(parse_page(page)?),
+                Some(("user", user)) if !user.is_empty() => target.user = Some(user.to_string()),
+                Some(("user", _)) => return Err("Empty user".to_string()),
+                _ => tracing::debug!(pair, "Skipping unknown part of the open action"),
+            }
+        }
+        Ok(target)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.page.is_none() && self.user.is_none()
+    }
+}
+
+/// A finger like `right-index-finger`, `overview` or `test`.
+pub fn parse_page(page: &str) -> Result<Page, String> {
+    match page {
+        OVERVIEW => Ok(Page::Overview),
+        TEST => Ok(Page::Test),
+        _ => Page::from_finger_id(page).ok_or_else(|| format!("Unknown page {page}")),
+    }
+}
+
+impl fmt::Display for OpenTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let page = self.page.and_then(|page| match page {
+            Page::Overview => Some(OVERVIEW),
+            Page::Test => Some(TEST),
+            page => page.as_finger_id(),
+        });
+        let pairs: Vec<String> = [("page", page), ("user", self.user.as_deref())]
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key}={}", value?)))
+            .collect();
+
+        if pairs.is_empty() {
+            f.write_str(ACTION)
+        } else {
+            write!(f, "{ACTION}?{}", pairs.join("&"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let target = OpenTarget::parse("open?user=alice&page=right-index-finger").unwrap();
+        assert_eq!(target.page, Some(Page::RightIndex));
+        assert_eq!(target.user.as_deref(), Some("alice"));
+
+        let target = OpenTarget::parse("open?page=overview&since=2").unwrap();
+        assert_eq!(target.page, Some(Page::Overview));
+        assert_eq!(target.user, None);
+
+        assert!(OpenTarget::parse("open").unwrap().is_empty());
+        assert!(OpenTarget::parse("open?page=tail").is_err());
+        assert!(OpenTarget::parse("open?user=").is_err());
+        assert!(OpenTarget::parse("enroll?page=left-thumb").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for target in [
+            OpenTarget::default(),
+            OpenTarget {
+                page: Some(Page::LeftThumb),
+                user: None,
+            },
+            OpenTarget {
+                page: Some(Page::Test),
+                user: None,
+            },
+            OpenTarget {
+                page: Some(Page::Overview),
+                user: Some("alice".to_string()),
+            },
+        ] {
+            assert_eq!(OpenTarget::parse(&target.to_string()), Ok(target));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The operator log. With operator mode on, every enrollment, deletion and
+//! clear is appended to a local file, so a helpdesk can tell who changed whose
+//! prints on a shared machine.
+
+use crate::app::last_change::unix_now;
+use crate::app::finger_name::finger_id_to_localized;
+use crate::app::report::operator;
+use crate::app::status::clock;
+use crate::fl;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// `result` of an action that went through.
+const OK: &str = "ok";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperatorAction {
+    Enroll,
+    Delete,
+    /// Removing the prints of a user while clearing the reader.
+    Clear,
+}
+
+/// One line of the log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Account logged in and running the app.
+    pub operator: String,
+    /// Account whose prints were changed.
+    pub user: String,
+    pub action: OperatorAction,
+    /// fprintd name of the finger, `None` for every print of the user.
+    pub finger: Option<String>,
+    /// `ok`, or what went wrong.
+    pub result: String,
+}
+
+impl OperatorEntry {
+    /// An action on the prints of `user` that is starting; its result is
+    /// filled in by [`OperatorEntry::finish`].
+    pub fn start(action: OperatorAction, user: &str, finger: Option<String>) -> Self {
+        Self {
+            timestamp: unix_now(),
+            operator: operator(),
+            user: user.to_string(),
+            action,
+            finger,
+            result: String::new(),
+        }
+    }
+
+    /// The action with its outcome, `None` when it went through.
+    pub fn finish(mut self, error: Option<String>) -> Self {
+        self.timestamp = unix_now();
+        self.result = error.unwrap_or_else(|| OK.to_string());
+        self
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.result == OK
+    }
+
+    /// Who did what, e.g. "admin enrolled Left Thumb for alice".
+    pub fn localized_summary(&self) -> String {
+        let finger = self.finger.as_deref().map(finger_id_to_localized);
+        let action = match (self.action, finger) {
+            (OperatorAction::Enroll, Some(finger)) => fl!("operator-log-enroll", finger = finger),
+            (OperatorAction::Enroll, None) => fl!("operator-log-enroll-any"),
+            (OperatorAction::Delete, Some(finger)) => fl!("operator-log-delete", finger = finger),
+            (OperatorAction::Delete, None) => fl!("operator-log-delete-all"),
+            (OperatorAction::Clear, _) => fl!("operator-log-clear"),
+        };
+        let (operator, user) = (self.operator.as_str(), self.user.as_str());
+        fl!(
+            "operator-log-summary",
+            operator = operator,
+            user = user,
+            action = action
+        )
+    }
+
+    /// The outcome as shown on the log page.
+    pub fn localized_result(&self) -> String {
+        if self.succeeded() {
+            fl!("operator-log-succeeded")
+        } else {
+            let error = self.result.as_str();
+            fl!("operator-log-failed", error = error)
+        }
+    }
+}
+
+/// The date and time of `timestamp` in UTC, e.g. "2023-11-14 22:13:20".
+pub fn date_time(timestamp: u64) -> String {
+    // Civil date from days since the epoch, after Howard Hinnant's algorithm
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02} {}", clock(timestamp))
+}
+
+/// Folder the app keeps its logs in: `$XDG_STATE_HOME/cosmic-fprint`.
+pub fn state_dir() -> Option<PathBuf> {
+    let state = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".local/state")))?;
+    Some(state.join("cosmic-fprint"))
+}
+
+/// Where the log is kept: `$XDG_STATE_HOME/cosmic-fprint/operator.log`.
+pub fn log_path() -> Option<PathBuf> {
+    Some(state_dir()?.join("operator.log"))
+}
+
+/// Appends `entries` to the log at `path` as lines of JSON. Lines are only
+/// ever added, never rewritten.
+pub async fn append(path: &Path, entries: Vec<OperatorEntry>) -> Result<(), String> {
+    let mut lines = String::new();
+    for entry in &entries {
+        lines.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+
+    let write = async {
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .mode(0o600)
+            .open(path)
+            .await?;
+        file.write_all(lines.as_bytes()).await
+    };
+    write.await.map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Reads the log at `path`, oldest first. Lines that can't be read are
+/// skipped, and a log that doesn't exist yet is empty.
+pub async fn read(path: &Path) -> Result<Vec<OperatorEntry>, String> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_time() {
+        assert_eq!(date_time(0), "1970-01-01 00:00:00");
+        assert_eq!(date_time(1_700_000_000), "2023-11-14 22:13:20");
+        assert_eq!(date_time(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(date_time(4_107_542_399), "2100-02-28 23:59:59");
+    }
+
+    #[tokio::test]
+    async fn test_append_and_read() {
+        let dir = std::env::temp_dir().join(format!("fprint-operator-{}", std::process::id()));
+        let path = dir.join("nested").join("operator.log");
+
+        let enroll = OperatorEntry::start(
+            OperatorAction::Enroll,
+            "alice",
+            Some("left-thumb".to_string()),
+        );
+        let delete = OperatorEntry::start(OperatorAction::Delete, "bob", None);
+        append(&path, vec![enroll.finish(None)]).await.unwrap();
+        append(&path, vec![delete.finish(Some("Device Busy".to_string()))])
+            .await
+            .unwrap();
+        // Damaged lines don't hide the rest
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap()
+            .write_all(b"{not json\n")
+            .await
+            .unwrap();
+
+        let entries = read(&path).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].user, "alice");
+        assert!(entries[0].succeeded());
+        assert_eq!(entries[1].action, OperatorAction::Delete);
+        assert_eq!(entries[1].result, "Device Busy");
+        assert!(!entries[1].succeeded());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(read(&path).await, Ok(Vec::new()));
+    }
+}
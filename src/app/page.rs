@@ -17,6 +17,10 @@ pub enum Page {
     LeftRing,
     LeftPinky,
     DeleteAllUsersPrints,
+    /// Which fingers every user has enrolled.
+    Overview,
+    /// Scanning a finger over and over to see how reliably it is recognized.
+    Test,
 }
 
 impl Page {
@@ -33,6 +37,8 @@ impl Page {
             Self::LeftRing,
             Self::LeftPinky,
             Self::DeleteAllUsersPrints,
+            Self::Overview,
+            Self::Test,
         ]
     }
 
@@ -49,6 +55,8 @@ impl Page {
             Self::LeftRing => fl!("page-left-ring-finger"),
             Self::LeftPinky => fl!("page-left-little-finger"),
             Self::DeleteAllUsersPrints => fl!("page-delete-all-users-prints"),
+            Self::Overview => fl!("page-overview"),
+            Self::Test => fl!("page-test"),
         }
     }
 
@@ -64,7 +72,54 @@ impl Page {
             Page::LeftMiddle => Some("left-middle-finger"),
             Page::LeftRing => Some("left-ring-finger"),
             Page::LeftPinky => Some("left-little-finger"),
-            Page::DeleteAllUsersPrints => None,
+            Page::DeleteAllUsersPrints | Page::Overview | Page::Test => None,
+        }
+    }
+
+    pub fn from_finger_id(finger_id: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|page| page.as_finger_id() == Some(finger_id))
+    }
+}
+
+/// A group of pages in the nav bar, listed under its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavSection {
+    RightHand,
+    LeftHand,
+    /// Pages about every finger, like the overview.
+    Manage,
+}
+
+impl NavSection {
+    pub fn all() -> &'static [Self] {
+        &[Self::RightHand, Self::LeftHand, Self::Manage]
+    }
+
+    /// The section `page` is listed in.
+    pub fn of(page: Page) -> Self {
+        match page {
+            Page::RightThumb
+            | Page::RightIndex
+            | Page::RightMiddle
+            | Page::RightRing
+            | Page::RightPinky => Self::RightHand,
+            Page::LeftThumb
+            | Page::LeftIndex
+            | Page::LeftMiddle
+            | Page::LeftRing
+            | Page::LeftPinky => Self::LeftHand,
+            Page::DeleteAllUsersPrints | Page::Overview | Page::Test => Self::Manage,
+        }
+    }
+
+    pub fn localized_name(&self) -> String {
+        match self {
+            Self::RightHand => fl!("nav-right-hand"),
+            Self::LeftHand => fl!("nav-left-hand"),
+            Self::Manage => fl!("nav-manage"),
         }
     }
 }
@@ -74,6 +129,12 @@ impl Page {
 pub enum ContextPage {
     #[default]
     About,
+    DeviceInfo,
+    Settings,
+    /// The status messages shown so far.
+    ActivityLog,
+    /// The actions recorded in operator mode.
+    OperatorLog,
 }
 
 #[cfg(test)]
@@ -83,7 +144,7 @@ mod tests {
     #[test]
     fn test_page_all() {
         let pages = Page::all();
-        assert_eq!(pages.len(), 11);
+        assert_eq!(pages.len(), 13);
         assert_eq!(pages[0], Page::RightThumb);
         assert_eq!(pages[1], Page::RightIndex);
         assert_eq!(pages[2], Page::RightMiddle);
@@ -95,6 +156,8 @@ mod tests {
         assert_eq!(pages[8], Page::LeftRing);
         assert_eq!(pages[9], Page::LeftPinky);
         assert_eq!(pages[10], Page::DeleteAllUsersPrints);
+        assert_eq!(pages[11], Page::Overview);
+        assert_eq!(pages[12], Page::Test);
     }
 
     #[test]
@@ -112,6 +175,8 @@ mod tests {
         assert!(!Page::LeftRing.localized_name().is_empty());
         assert!(!Page::LeftPinky.localized_name().is_empty());
         assert!(!Page::DeleteAllUsersPrints.localized_name().is_empty());
+        assert!(!Page::Overview.localized_name().is_empty());
+        assert!(!Page::Test.localized_name().is_empty());
     }
 
     #[test]
@@ -127,5 +192,120 @@ mod tests {
         assert_eq!(Page::LeftRing.as_finger_id(), Some("left-ring-finger"));
         assert_eq!(Page::LeftPinky.as_finger_id(), Some("left-little-finger"));
         assert_eq!(Page::DeleteAllUsersPrints.as_finger_id(), None);
+        assert_eq!(Page::Overview.as_finger_id(), None);
+        assert_eq!(Page::Test.as_finger_id(), None);
+    }
+
+    #[test]
+    fn test_page_from_finger_id() {
+        for page in Page::all() {
+            if let Some(finger_id) = page.as_finger_id() {
+                assert_eq!(Page::from_finger_id(finger_id), Some(*page));
+            }
+        }
+        assert_eq!(Page::from_finger_id("right-toe"), None);
+        assert_eq!(Page::from_finger_id(""), None);
+    }
+
+    #[test]
+    fn test_nav_sections_in_page_order() {
+        // Each section's pages follow each other, so one header can lead them
+        let sections: Vec<NavSection> = Page::all().iter().map(|p| NavSection::of(*p)).collect();
+        let mut order = sections.clone();
+        order.dedup();
+        assert_eq!(order, NavSection::all());
+        assert!(
+            Page::all()
+                .iter()
+                .filter(|page| page.as_finger_id().is_some())
+                .all(|page| NavSection::of(*page) != NavSection::Manage)
+        );
+    }
+
+    /// The finger names fprintd accepts, besides "any".
+    const FPRINTD_FINGERS: [&str; 10] = [
+        "left-thumb",
+        "left-index-finger",
+        "left-middle-finger",
+        "left-ring-finger",
+        "left-little-finger",
+        "right-thumb",
+        "right-index-finger",
+        "right-middle-finger",
+        "right-ring-finger",
+        "right-little-finger",
+    ];
+
+    #[test]
+    fn test_finger_ids_round_trip() {
+        let fingers: Vec<_> = Page::all()
+            .iter()
+            .filter_map(|page| page.as_finger_id().map(|id| (*page, id)))
+            .collect();
+
+        // Each fprintd finger has exactly one page
+        assert_eq!(fingers.len(), FPRINTD_FINGERS.len());
+        for finger in FPRINTD_FINGERS {
+            let page = Page::from_finger_id(finger).expect(finger);
+            assert_eq!(page.as_finger_id(), Some(finger));
+        }
+        for (page, id) in fingers {
+            assert!(FPRINTD_FINGERS.contains(&id), "{page:?} has unknown finger {id}");
+        }
+    }
+
+    /// The message each page is named with, so a new page can't be added
+    /// without a name.
+    fn message_id(page: Page) -> &'static str {
+        match page {
+            Page::RightThumb => "page-right-thumb",
+            Page::RightIndex => "page-right-index-finger",
+            Page::RightMiddle => "page-right-middle-finger",
+            Page::RightRing => "page-right-ring-finger",
+            Page::RightPinky => "page-right-little-finger",
+            Page::LeftThumb => "page-left-thumb",
+            Page::LeftIndex => "page-left-index-finger",
+            Page::LeftMiddle => "page-left-middle-finger",
+            Page::LeftRing => "page-left-ring-finger",
+            Page::LeftPinky => "page-left-little-finger",
+            Page::DeleteAllUsersPrints => "page-delete-all-users-prints",
+            Page::Overview => "page-overview",
+            Page::Test => "page-test",
+        }
+    }
+
+    fn section_message_id(section: NavSection) -> &'static str {
+        match section {
+            NavSection::RightHand => "nav-right-hand",
+            NavSection::LeftHand => "nav-left-hand",
+            NavSection::Manage => "nav-manage",
+        }
+    }
+
+    #[test]
+    fn test_page_names_in_every_locale() {
+        for page in Page::all() {
+            let id = message_id(*page);
+            assert_eq!(page.localized_name(), crate::i18n::LANGUAGE_LOADER.get(id));
+        }
+
+        let shipped = crate::i18n::shipped_messages();
+        assert!(shipped.len() > 1);
+        for (language, messages) in shipped {
+            for page in Page::all() {
+                let id = message_id(*page);
+                assert!(
+                    messages.iter().any(|message| message == id),
+                    "{language} has no name for {page:?}"
+                );
+            }
+            for section in NavSection::all() {
+                let id = section_message_id(*section);
+                assert!(
+                    messages.iter().any(|message| message == id),
+                    "{language} has no name for {section:?}"
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Whether PAM consults fprintd for logging in and sudo.
+
+use std::path::Path;
+
+pub const PAM_DIR: &str = "/etc/pam.d";
+
+const FPRINTD_MODULE: &str = "pam_fprintd.so";
+
+/// Services used for logging in, in order of preference.
+const LOGIN_SERVICES: &[&str] = &["cosmic-greeter", "login"];
+const SUDO_SERVICE: &str = "sudo";
+
+/// Limits how deep includes are followed, guarding against include loops.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Tools that enable fingerprint authentication, with the arguments to do so.
+const ENABLE_COMMANDS: &[(&str, &[&str])] = &[
+    // Debian and Ubuntu
+    ("/usr/sbin/pam-auth-update", &["--enable", "fprintd"]),
+    // Fedora
+    ("/usr/bin/authselect", &["enable-feature", "with-fingerprint"]),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PamStatus {
+    pub login: bool,
+    pub sudo: bool,
+}
+
+impl PamStatus {
+    pub fn fully_enabled(&self) -> bool {
+        self.login && self.sudo
+    }
+}
+
+/// Whether the PAM stack of `service` uses fprintd, following includes.
+///
+/// `read` returns the contents of a file in the PAM directory.
+pub fn uses_fprintd(service: &str, read: &impl Fn(&str) -> Option<String>) -> bool {
+    uses_fprintd_at_depth(service, read, 0)
+}
+
+fn uses_fprintd_at_depth(
+    service: &str,
+    read: &impl Fn(&str) -> Option<String>,
+    depth: usize,
+) -> bool {
+    if depth > MAX_INCLUDE_DEPTH {
+        return false;
+    }
+
+    let Some(contents) = read(service) else {
+        return false;
+    };
+
+    contents.lines().map(str::trim).any(|line| {
+        if line.is_empty() || line.starts_with('#') {
+            return false;
+        }
+
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            // Debian style "@include common-auth"
+            (Some("@include"), Some(included)) => {
+                uses_fprintd_at_depth(included, read, depth + 1)
+            }
+            // Only the auth stack matters, "-auth" is the optional form
+            (Some("auth" | "-auth"), Some("include" | "substack")) => words
+                .next()
+                .is_some_and(|included| uses_fprintd_at_depth(included, read, depth + 1)),
+            (Some("auth" | "-auth"), Some(control)) => skip_control(control, &mut words)
+                .is_some_and(|module| module.ends_with(FPRINTD_MODULE)),
+            _ => false,
+        }
+    })
+}
+
+/// Returns the module path following `control`, skipping bracketed controls
+/// like `[success=2 default=ignore]` that span several words.
+fn skip_control<'a>(
+    control: &str,
+    words: &mut impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    if control.starts_with('[') && !control.ends_with(']') {
+        words.find(|word| word.ends_with(']'))?;
+    }
+    words.next()
+}
+
+/// Reads the PAM configuration of the login and sudo services.
+pub fn read_pam_status() -> PamStatus {
+    let read = |service: &str| std::fs::read_to_string(Path::new(PAM_DIR).join(service)).ok();
+
+    // The first login service that exists decides
+    let login = LOGIN_SERVICES
+        .iter()
+        .find(|service| Path::new(PAM_DIR).join(service).exists())
+        .is_some_and(|service| uses_fprintd(service, &read));
+
+    PamStatus {
+        login,
+        sudo: uses_fprintd(SUDO_SERVICE, &read),
+    }
+}
+
+/// The distribution tool that enables fingerprint authentication, if installed.
+pub fn enable_command() -> Option<(&'static str, &'static [&'static str])> {
+    ENABLE_COMMANDS
+        .iter()
+        .copied()
+        .find(|(program, _)| Path::new(program).exists())
+}
+
+/// Enables fingerprint authentication through `pkexec`, which asks for an
+/// administrator password.
+pub async fn enable_fingerprint_auth() -> Result<(), String> {
+    let (program, args) = enable_command().ok_or("No tool to enable fingerprint login found")?;
+
+    let status = tokio::process::Command::new("pkexec")
+        .arg(program)
+        .args(args)
+        .status()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} exited with {status}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn reader(files: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let files: HashMap<String, String> = files
+            .iter()
+            .map(|(name, contents)| (name.to_string(), contents.to_string()))
+            .collect();
+        move |name| files.get(name).cloned()
+    }
+
+    #[test]
+    fn test_debian_include() {
+        let read = reader(&[
+            ("sudo", "#%PAM-1.0\n@include common-auth\n@include common-account\n"),
+            (
+                "common-auth",
+                "auth\t[success=2 default=ignore]\tpam_fprintd.so max-tries=1 timeout=10\n\
+                 auth\t[success=1 default=ignore]\tpam_unix.so nullok try_first_pass\n",
+            ),
+        ]);
+        assert!(uses_fprintd("sudo", &read));
+    }
+
+    #[test]
+    fn test_fedora_substack() {
+        let read = reader(&[
+            ("login", "auth       substack     system-auth\n"),
+            (
+                "system-auth",
+                "auth        sufficient                                   pam_fprintd.so\n",
+            ),
+        ]);
+        assert!(uses_fprintd("login", &read));
+    }
+
+    #[test]
+    fn test_not_enabled() {
+        let read = reader(&[
+            ("sudo", "auth include system-auth\n# auth sufficient pam_fprintd.so\n"),
+            ("system-auth", "auth required pam_unix.so\naccount required pam_fprintd.so\n"),
+        ]);
+        assert!(!uses_fprintd("sudo", &read));
+        assert!(!uses_fprintd("missing", &read));
+    }
+
+    #[test]
+    fn test_include_loop() {
+        let read = reader(&[("a", "@include b\n"), ("b", "@include a\n")]);
+        assert!(!uses_fprintd("a", &read));
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The app's own interface on the session bus, which lets other components
+//! such as a settings page or an applet follow an enrollment as it runs.
+//!
+//! The connection doesn't own a name, since the single instance name is held
+//! by libcosmic, so listeners match on the interface instead of the sender.
+
+use zbus::object_server::SignalEmitter;
+
+pub const OBJECT_PATH: &str = "/fi/joonastuomi/Fprint/Enrollment";
+
+pub struct Enrollment;
+
+#[zbus::interface(name = "fi.joonastuomi.Fprint.Enrollment")]
+impl Enrollment {
+    /// Sent for each status of a running enrollment. `stage` is the number of
+    /// scans that passed, `total` is 0 when the reader doesn't tell, and
+    /// `status` is the result fprintd reported, like `enroll-stage-passed`.
+    #[zbus(signal)]
+    pub async fn progress(
+        emitter: &SignalEmitter<'_>,
+        stage: u32,
+        total: u32,
+        status: &str,
+    ) -> zbus::Result<()>;
+}
+
+/// Connects to the session bus with the interface served.
+pub async fn serve() -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .serve_at(OBJECT_PATH, Enrollment)?
+        .build()
+        .await
+}
+
+/// Sends the `Progress` signal, logging when nobody could be told.
+pub async fn emit(connection: zbus::Connection, stage: u32, total: u32, status: String) {
+    let sent = async {
+        let emitter = SignalEmitter::new(&connection, OBJECT_PATH)?;
+        Enrollment::progress(&emitter, stage, total, &status).await
+    };
+    if let Err(e) = sent.await {
+        tracing::debug!(%e, "Failed to send enrollment progress");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn test_progress_reaches_listeners() {
+        // Not every build environment has a session bus
+        let Ok(connection) = serve().await else {
+            return;
+        };
+        let listener = zbus::Connection::session().await.unwrap();
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface("fi.joonastuomi.Fprint.Enrollment")
+            .unwrap()
+            .member("Progress")
+            .unwrap()
+            .build();
+        let mut signals = zbus::MessageStream::for_match_rule(rule, &listener, None)
+            .await
+            .unwrap();
+
+        emit(connection, 2, 5, "enroll-stage-passed".to_string()).await;
+
+        let message = signals.next().await.unwrap().unwrap();
+        let (stage, total, status): (u32, u32, String) = message.body().deserialize().unwrap();
+        assert_eq!(
+            (stage, total, status.as_str()),
+            (2, 5, "enroll-stage-passed")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Getting back to fprintd after the bus connection dropped or the service went
+//! away, backing off between attempts.
+
+use std::time::{Duration, Instant};
+
+/// Delay after the first failed attempt, doubled after every further one.
+const INITIAL_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct Reconnect {
+    attempts: u32,
+    next_attempt: Instant,
+    /// Whether an attempt is running.
+    attempting: bool,
+}
+
+impl Reconnect {
+    /// Starts reconnecting, with the first attempt due right away.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            attempts: 0,
+            next_attempt: now,
+            attempting: false,
+        }
+    }
+
+    fn delay(attempts: u32) -> Duration {
+        INITIAL_DELAY
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+            .min(MAX_DELAY)
+    }
+
+    /// Starts an attempt if one is due, returning whether it was started.
+    pub fn start_attempt(&mut self, now: Instant) -> bool {
+        if self.attempting || now < self.next_attempt {
+            return false;
+        }
+        self.attempting = true;
+        true
+    }
+
+    /// Records a failed attempt and schedules the next one.
+    pub fn failed(&mut self, now: Instant) {
+        self.attempting = false;
+        self.attempts = self.attempts.saturating_add(1);
+        self.next_attempt = now + Self::delay(self.attempts);
+    }
+
+    /// Failed attempts so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let start = Instant::now();
+        let mut reconnect = Reconnect::new(start);
+
+        assert!(reconnect.start_attempt(start));
+        // Only one attempt runs at a time
+        assert!(!reconnect.start_attempt(start));
+
+        reconnect.failed(start);
+        assert!(!reconnect.start_attempt(start));
+        assert!(reconnect.start_attempt(start + INITIAL_DELAY));
+
+        reconnect.failed(start);
+        assert!(!reconnect.start_attempt(start + INITIAL_DELAY));
+        assert!(reconnect.start_attempt(start + INITIAL_DELAY * 2));
+
+        for _ in 0..10 {
+            reconnect.failed(start);
+        }
+        assert_eq!(reconnect.attempts(), 12);
+        assert!(!reconnect.start_attempt(start + MAX_DELAY - Duration::from_millis(1)));
+        assert!(reconnect.start_attempt(start + MAX_DELAY));
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Noticing when the app runs in a session away from the reader, where a
+//! mistaken delete can't be noticed by someone sitting at the machine.
+
+use crate::fl;
+
+/// Where the session running the app is, as seen from the reader.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SessionLocation {
+    #[default]
+    Local,
+    /// Logged in over the network, from `host` when it is known.
+    Remote { host: Option<String> },
+    /// Without a seat or a display of its own, like a VNC server's session.
+    Headless,
+}
+
+impl SessionLocation {
+    /// Reads the properties logind reports for the session.
+    pub fn from_logind(remote: bool, remote_host: &str, session_type: &str, seat: &str) -> Self {
+        if remote {
+            Self::Remote {
+                host: (!remote_host.is_empty()).then(|| remote_host.to_string()),
+            }
+        } else if seat.is_empty() || matches!(session_type, "tty" | "unspecified") {
+            Self::Headless
+        } else {
+            Self::Local
+        }
+    }
+
+    /// Falls back to the variables ssh sets, for when logind isn't reachable.
+    pub fn from_env() -> Self {
+        match std::env::var("SSH_CONNECTION") {
+            Ok(connection) => Self::Remote {
+                host: connection.split_whitespace().next().map(str::to_string),
+            },
+            Err(_) if std::env::var_os("SSH_CLIENT").is_some() => Self::Remote { host: None },
+            Err(_) => Self::Local,
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        *self == Self::Local
+    }
+
+    /// Banner text saying the reader is managed from afar, `None` when local.
+    pub fn localized_notice(&self) -> Option<String> {
+        match self {
+            Self::Local => None,
+            Self::Remote { host: Some(host) } => {
+                Some(fl!("remote-session-notice-host", host = host.as_str()))
+            }
+            Self::Remote { host: None } => Some(fl!("remote-session-notice")),
+            Self::Headless => Some(fl!("headless-session-notice")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_logind() {
+        assert_eq!(
+            SessionLocation::from_logind(false, "", "wayland", "seat0"),
+            SessionLocation::Local
+        );
+        assert_eq!(
+            SessionLocation::from_logind(true, "10.0.0.2", "tty", ""),
+            SessionLocation::Remote {
+                host: Some("10.0.0.2".to_string())
+            }
+        );
+        assert_eq!(
+            SessionLocation::from_logind(true, "", "x11", ""),
+            SessionLocation::Remote { host: None }
+        );
+        // A display server started without a seat, as VNC servers do
+        assert_eq!(
+            SessionLocation::from_logind(false, "", "x11", ""),
+            SessionLocation::Headless
+        );
+    }
+}
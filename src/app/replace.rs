@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::hand_map::FingerMark;
+use crate::app::page::Page;
+
+/// Steps of the guided "replace finger" flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaceStep {
+    /// Choosing which finger takes over from the old one.
+    ChooseFinger,
+    /// Enrolling the replacement finger.
+    Enroll,
+    /// Checking that the replacement finger matches.
+    Verify,
+    /// Asking whether the old finger should be deleted.
+    ConfirmDelete,
+}
+
+/// State of the wizard that enrolls a replacement finger, verifies it and
+/// optionally deletes the finger it replaces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplaceWizard {
+    pub old: Page,
+    pub new: Option<Page>,
+    pub step: ReplaceStep,
+    pub candidates: Vec<Page>,
+    pub candidate_names: Vec<String>,
+}
+
+impl ReplaceWizard {
+    pub fn new(old: Page, enrolled_fingers: &[String]) -> Self {
+        let candidates = candidates(old, enrolled_fingers);
+        let candidate_names = candidates.iter().map(Page::localized_name).collect();
+        Self {
+            old,
+            new: None,
+            step: ReplaceStep::ChooseFinger,
+            candidates,
+            candidate_names,
+        }
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if let Some(page) = self.candidates.get(index) {
+            self.new = Some(*page);
+        }
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        let new = self.new?;
+        self.candidates.iter().position(|p| *p == new)
+    }
+
+    /// How `page` is shown on the hand map at the current step.
+    pub fn finger_mark(&self, page: Page) -> FingerMark {
+        if page == self.old {
+            FingerMark::Done
+        } else if Some(page) == self.new {
+            match self.step {
+                ReplaceStep::ChooseFinger => FingerMark::Pending,
+                ReplaceStep::Enroll | ReplaceStep::Verify => FingerMark::Active,
+                ReplaceStep::ConfirmDelete => FingerMark::Done,
+            }
+        } else {
+            FingerMark::Unused
+        }
+    }
+}
+
+/// Fingers that can replace `old`: every other finger without an enrolled print.
+fn candidates(old: Page, enrolled_fingers: &[String]) -> Vec<Page> {
+    Page::all()
+        .iter()
+        .copied()
+        .filter(|page| *page != old)
+        .filter(|page| {
+            page.as_finger_id()
+                .is_some_and(|id| !enrolled_fingers.iter().any(|f| f == id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_exclude_old_and_enrolled() {
+        let enrolled = vec![
+            "right-index-finger".to_string(),
+            "left-thumb".to_string(),
+        ];
+        let wizard = ReplaceWizard::new(Page::RightIndex, &enrolled);
+
+        assert_eq!(wizard.step, ReplaceStep::ChooseFinger);
+        assert_eq!(wizard.candidates.len(), 8);
+        assert!(!wizard.candidates.contains(&Page::RightIndex));
+        assert!(!wizard.candidates.contains(&Page::LeftThumb));
+        assert!(!wizard.candidates.contains(&Page::DeleteAllUsersPrints));
+        assert_eq!(wizard.candidates.len(), wizard.candidate_names.len());
+    }
+
+    #[test]
+    fn test_select() {
+        let mut wizard = ReplaceWizard::new(Page::RightIndex, &[]);
+        assert_eq!(wizard.selected_index(), None);
+
+        wizard.select(1);
+        assert_eq!(wizard.new, Some(Page::RightMiddle));
+        assert_eq!(wizard.selected_index(), Some(1));
+
+        // Out of range selections are ignored
+        wizard.select(100);
+        assert_eq!(wizard.new, Some(Page::RightMiddle));
+    }
+
+    #[test]
+    fn test_finger_mark() {
+        let mut wizard = ReplaceWizard::new(Page::RightIndex, &[]);
+        wizard.select(1);
+
+        assert_eq!(wizard.finger_mark(Page::RightIndex), FingerMark::Done);
+        assert_eq!(wizard.finger_mark(Page::RightMiddle), FingerMark::Pending);
+        assert_eq!(wizard.finger_mark(Page::LeftThumb), FingerMark::Unused);
+
+        wizard.step = ReplaceStep::Enroll;
+        assert_eq!(wizard.finger_mark(Page::RightMiddle), FingerMark::Active);
+
+        wizard.step = ReplaceStep::ConfirmDelete;
+        assert_eq!(wizard.finger_mark(Page::RightMiddle), FingerMark::Done);
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Reports of completed changes, handed to an administrator's hook so
+//! enrollments can be tracked by an asset system.
+
+use crate::config::LastChange;
+use nix::unistd::{Uid, User};
+use serde::Serialize;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+/// One successful change to the prints of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperationReport {
+    /// `enrolled`, `deleted` or `verified`.
+    pub operation: String,
+    pub username: String,
+    pub finger: Option<String>,
+    /// Account that ran the app and made the change.
+    pub operator: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub device: Option<String>,
+}
+
+impl OperationReport {
+    pub fn new(username: &str, change: &LastChange, device: Option<String>) -> Self {
+        Self {
+            operation: format!("{:?}", change.kind).to_lowercase(),
+            username: username.to_string(),
+            finger: change.finger.clone(),
+            operator: operator(),
+            timestamp: change.timestamp,
+            device,
+        }
+    }
+
+    /// Variables set for the report command, next to the JSON on its stdin.
+    fn env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("FPRINT_OPERATION", self.operation.clone()),
+            ("FPRINT_USER", self.username.clone()),
+            ("FPRINT_FINGER", self.finger.clone().unwrap_or_default()),
+            ("FPRINT_OPERATOR", self.operator.clone()),
+            ("FPRINT_TIME", self.timestamp.to_string()),
+        ]
+    }
+}
+
+/// Where reports go, as configured by the administrator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportHook {
+    /// File each report is appended to as a line of JSON.
+    pub path: String,
+    /// Shell command run for each report.
+    pub command: String,
+}
+
+impl ReportHook {
+    pub fn is_enabled(&self) -> bool {
+        !self.path.is_empty() || !self.command.is_empty()
+    }
+
+    /// Hands `reports` to the configured file and command, in order.
+    pub async fn send(self, reports: Vec<OperationReport>) -> Result<(), String> {
+        for report in reports {
+            let mut line = serde_json::to_string(&report).map_err(|e| e.to_string())?;
+            line.push('\n');
+
+            if !self.path.is_empty() {
+                append(&self.path, &line)
+                    .await
+                    .map_err(|e| format!("{}: {e}", self.path))?;
+            }
+            if !self.command.is_empty() {
+                run(&self.command, &report, &line)
+                    .await
+                    .map_err(|e| format!("{}: {e}", self.command))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn append(path: &str, line: &str) -> std::io::Result<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await
+}
+
+async fn run(command: &str, report: &OperationReport, line: &str) -> std::io::Result<()> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(report.env())
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(line.as_bytes()).await?;
+    }
+
+    let status = child.wait().await?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("exited with {status}")))
+    }
+}
+
+/// Name of the account running the app.
+pub fn operator() -> String {
+    User::from_uid(Uid::current())
+        .ok()
+        .flatten()
+        .map_or_else(|| Uid::current().to_string(), |user| user.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChangeKind;
+
+    fn report() -> OperationReport {
+        let change = LastChange {
+            kind: ChangeKind::Enrolled,
+            finger: Some("left-thumb".to_string()),
+            timestamp: 1_700_000_000,
+        };
+        OperationReport::new("alice", &change, Some("Mock Reader".to_string()))
+    }
+
+    #[test]
+    fn test_report_env() {
+        let env = report().env();
+        assert!(env.contains(&("FPRINT_OPERATION", "enrolled".to_string())));
+        assert!(env.contains(&("FPRINT_FINGER", "left-thumb".to_string())));
+        assert!(env.contains(&("FPRINT_TIME", "1700000000".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_send_appends_and_runs_command() {
+        let dir = std::env::temp_dir().join(format!("fprint-report-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("reports.jsonl");
+        let out = dir.join("command.out");
+
+        let hook = ReportHook {
+            path: path.display().to_string(),
+            command: format!("cat > {0} && echo \"$FPRINT_USER\" >> {0}", out.display()),
+        };
+        assert!(hook.is_enabled());
+        hook.send(vec![report(), report()]).await.unwrap();
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(lines.lines().count(), 2);
+        let first: serde_json::Value =
+            serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+        assert_eq!(first["username"], "alice");
+        assert_eq!(first["operation"], "enrolled");
+
+        // The command ran for each report, the last one overwriting the first
+        let output = std::fs::read_to_string(&out).unwrap();
+        assert!(output.ends_with("alice\n"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The self-test page: scanning a finger over and over to see how reliably
+//! the reader recognizes it, before relying on it to log in or for sudo.
+
+/// How a scan of the self-test went.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanResult {
+    Match,
+    NoMatch,
+    /// The scan was too poor to compare, e.g. too short or off-center.
+    Retry,
+}
+
+impl ScanResult {
+    /// The result fprintd reported as `status`, `None` for statuses that say
+    /// nothing about the scan, like a cancelled verification.
+    pub fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "verify-match" => Some(Self::Match),
+            "verify-no-match" => Some(Self::NoMatch),
+            "verify-retry-scan"
+            | "verify-swipe-too-short"
+            | "verify-finger-not-centered"
+            | "verify-remove-and-retry" => Some(Self::Retry),
+            _ => None,
+        }
+    }
+}
+
+/// Tally of the scans since the self-test was started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelfTest {
+    /// Whether scans are being taken, as opposed to stopped or not started.
+    pub running: bool,
+    /// Whether the next scan waits for the pause after a result.
+    pub waiting: bool,
+    pub matches: u32,
+    pub no_matches: u32,
+    pub retries: u32,
+    pub last: Option<ScanResult>,
+}
+
+impl SelfTest {
+    /// Starts over with no scans counted.
+    pub fn start(&mut self) {
+        *self = Self {
+            running: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn stop(&mut self) {
+        self.running = false;
+        self.waiting = false;
+    }
+
+    /// Counts the scan fprintd reported with `status`. A verification that
+    /// ended with a result is followed by another one after a pause, any
+    /// other end stops the test.
+    pub fn record(&mut self, status: &str, done: bool) {
+        if !self.running {
+            return;
+        }
+        let result = ScanResult::from_status(status);
+        match result {
+            Some(ScanResult::Match) => self.matches += 1,
+            Some(ScanResult::NoMatch) => self.no_matches += 1,
+            Some(ScanResult::Retry) => self.retries += 1,
+            None => {}
+        }
+        if result.is_some() {
+            self.last = result;
+        }
+
+        if done {
+            match result {
+                Some(ScanResult::Match | ScanResult::NoMatch) => self.waiting = true,
+                _ => self.stop(),
+            }
+        }
+    }
+
+    /// Whether the pause is over and the next scan should start.
+    pub fn next_scan(&mut self) -> bool {
+        let next = self.running && self.waiting;
+        self.waiting = false;
+        next
+    }
+
+    /// Scans that were compared against the prints.
+    pub fn attempts(&self) -> u32 {
+        self.matches + self.no_matches
+    }
+
+    /// Share of the compared scans that matched, in whole percent.
+    pub fn match_rate(&self) -> Option<u32> {
+        let attempts = self.attempts();
+        (attempts > 0).then(|| (self.matches * 100 + attempts / 2) / attempts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_scans() {
+        let mut test = SelfTest::default();
+        // Nothing is counted before the test is started
+        test.record("verify-match", true);
+        assert_eq!(test.attempts(), 0);
+        assert_eq!(test.match_rate(), None);
+
+        test.start();
+        test.record("verify-match", true);
+        assert!(test.waiting);
+        assert!(test.next_scan());
+        assert!(!test.next_scan());
+
+        test.record("verify-swipe-too-short", false);
+        test.record("verify-no-match", true);
+        assert!(test.next_scan());
+        test.record("verify-match", true);
+
+        assert_eq!((test.matches, test.no_matches, test.retries), (2, 1, 1));
+        assert_eq!(test.last, Some(ScanResult::Match));
+        assert_eq!(test.match_rate(), Some(67));
+    }
+
+    #[test]
+    fn test_stops_on_failure() {
+        let mut test = SelfTest::default();
+        test.start();
+        test.record("verify-match", true);
+        test.next_scan();
+        test.record("verify-disconnected", true);
+        assert!(!test.running);
+        assert!(!test.next_scan());
+        // The tally stays for reading
+        assert_eq!(test.matches, 1);
+
+        test.start();
+        assert_eq!(test.attempts(), 0);
+        test.stop();
+        test.record("verify-cancelled", true);
+        assert!(!test.running);
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A stepped indicator of the scans an enrollment takes, marking the scan
+//! that has to be tried again and why.
+
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Background, Border, Color, Length};
+use cosmic::prelude::*;
+use cosmic::widget::{self, container, text};
+use cosmic::{cosmic_theme, theme};
+
+const STEP_SIZE: f32 = 16.0;
+
+/// Why fprintd asked for another try at the current scan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryReason {
+    /// The scan could not be read.
+    Unreadable,
+    TooShort,
+    TooFast,
+    NotCentered,
+    RemoveFinger,
+}
+
+impl RetryReason {
+    /// The reason behind an `EnrollStatus` result, `None` if it doesn't ask
+    /// for another try.
+    pub fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "enroll-retry-scan" => Some(Self::Unreadable),
+            "enroll-swipe-too-short" => Some(Self::TooShort),
+            "enroll-too-fast" => Some(Self::TooFast),
+            "enroll-finger-not-centered" => Some(Self::NotCentered),
+            "enroll-remove-and-retry" => Some(Self::RemoveFinger),
+            _ => None,
+        }
+    }
+
+    /// A few words shown under the indicator.
+    pub fn localized_label(self) -> String {
+        match self {
+            Self::Unreadable => fl!("stage-retry-unreadable"),
+            Self::TooShort => fl!("stage-retry-too-short"),
+            Self::TooFast => fl!("stage-retry-too-fast"),
+            Self::NotCentered => fl!("stage-retry-not-centered"),
+            Self::RemoveFinger => fl!("stage-retry-remove-finger"),
+        }
+    }
+}
+
+/// How a scan is drawn on the indicator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepMark {
+    /// Passed, drawn filled with a check mark.
+    Done,
+    /// Waiting for the finger, drawn filled.
+    Current,
+    /// Waiting for another try, drawn in the warning color with an
+    /// exclamation mark.
+    Retry,
+    /// Still to come, drawn outlined.
+    Pending,
+}
+
+/// Marks for each of the `total` scans when `done` of them passed.
+pub fn marks(done: u32, total: u32, retry: Option<RetryReason>) -> Vec<StepMark> {
+    (0..total)
+        .map(|step| match step.cmp(&done) {
+            std::cmp::Ordering::Less => StepMark::Done,
+            std::cmp::Ordering::Equal if retry.is_some() => StepMark::Retry,
+            std::cmp::Ordering::Equal => StepMark::Current,
+            std::cmp::Ordering::Greater => StepMark::Pending,
+        })
+        .collect()
+}
+
+/// Draws a step for each scan with the count below, and the reason when the
+/// current scan has to be tried again.
+pub fn view<'a, M: 'a>(done: u32, total: u32, retry: Option<RetryReason>) -> Element<'a, M> {
+    let cosmic_theme::Spacing {
+        space_xxs,
+        space_xs,
+        ..
+    } = theme::active().cosmic().spacing;
+
+    let steps = marks(done, total, retry)
+        .into_iter()
+        .fold(widget::row().spacing(space_xs), |row, mark| {
+            row.push(step(mark))
+        })
+        .align_y(Vertical::Center);
+
+    let passed = done.min(total);
+    let count = fl!("stage-count", done = passed, total = total);
+    let caption = match retry {
+        Some(reason) => fl!("stage-count-retry", count = count, reason = reason.localized_label()),
+        None => count,
+    };
+
+    widget::column()
+        .push(steps)
+        .push(text::caption(caption))
+        .spacing(space_xxs)
+        .into()
+}
+
+fn step<'a, M: 'a>(mark: StepMark) -> Element<'a, M> {
+    let glyph = match mark {
+        StepMark::Done => "✓",
+        StepMark::Retry => "!",
+        StepMark::Current | StepMark::Pending => "",
+    };
+    let glyph_color: Color = {
+        let theme = theme::active();
+        let cosmic = theme.cosmic();
+        match mark {
+            StepMark::Retry => cosmic.warning.on,
+            _ => cosmic.accent.on,
+        }
+        .into()
+    };
+
+    text::caption(glyph)
+        .class(theme::Text::Color(glyph_color))
+        .apply(widget::container)
+        .center(Length::Fixed(STEP_SIZE))
+        .class(theme::Container::custom(move |theme| {
+            let cosmic = theme.cosmic();
+            let color: Color = match mark {
+                StepMark::Retry => cosmic.warning.base.into(),
+                _ => cosmic.accent_color().into(),
+            };
+
+            container::Style {
+                background: (mark != StepMark::Pending).then_some(Background::Color(color)),
+                border: Border {
+                    color,
+                    width: 1.5,
+                    radius: (STEP_SIZE / 2.0).into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks() {
+        use StepMark::*;
+
+        assert_eq!(marks(0, 3, None), [Current, Pending, Pending]);
+        assert_eq!(
+            marks(1, 3, Some(RetryReason::TooShort)),
+            [Done, Retry, Pending]
+        );
+        // Nothing is current once every scan passed
+        assert_eq!(marks(3, 3, None), [Done, Done, Done]);
+    }
+
+    #[test]
+    fn test_retry_reason() {
+        assert_eq!(
+            RetryReason::from_status("enroll-finger-not-centered"),
+            Some(RetryReason::NotCentered)
+        );
+        assert_eq!(RetryReason::from_status("enroll-stage-passed"), None);
+        assert_eq!(RetryReason::from_status("enroll-completed"), None);
+    }
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Recent status messages, so hints that flashed by can be read again and
+//! included when reporting a problem.
+
+use crate::app::last_change::unix_now;
+use std::collections::VecDeque;
+
+/// Number of status messages kept.
+const CAPACITY: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct StatusHistory {
+    /// Oldest message first, with when it was shown in seconds since the Unix
+    /// epoch; the last one is the live status.
+    entries: VecDeque<(u64, String)>,
+    /// Index of the message being browsed, `None` while showing the live status.
+    cursor: Option<usize>,
+}
+
+impl StatusHistory {
+    /// Records `status` if it differs from the latest message.
+    ///
+    /// A new message returns to the live status.
+    pub fn push(&mut self, status: &str) {
+        self.push_at(status, unix_now());
+    }
+
+    fn push_at(&mut self, status: &str, timestamp: u64) {
+        if status.is_empty() || self.entries.back().is_some_and(|(_, last)| last == status) {
+            return;
+        }
+
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((timestamp, status.to_string()));
+        self.cursor = None;
+    }
+
+    /// Every message kept with when it was shown, oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = (u64, &str)> {
+        self.entries
+            .iter()
+            .map(|(timestamp, status)| (*timestamp, status.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The messages as plain text, one per line and oldest first, for pasting
+    /// into a bug report.
+    pub fn to_text(&self) -> String {
+        self.entries()
+            .map(|(timestamp, status)| format!("{} {status}\n", clock(timestamp)))
+            .collect()
+    }
+
+    /// Steps back to an earlier message.
+    pub fn older(&mut self) {
+        let live = self.entries.len().saturating_sub(1);
+        self.cursor = match self.cursor {
+            None if live > 0 => Some(live - 1),
+            Some(i) if i > 0 => Some(i - 1),
+            cursor => cursor,
+        };
+    }
+
+    /// Steps forward towards the live status.
+    pub fn newer(&mut self) {
+        let live = self.entries.len().saturating_sub(1);
+        self.cursor = self.cursor.map(|i| i + 1).filter(|i| *i < live);
+    }
+
+    /// Stops browsing and shows the live status again.
+    pub fn reset(&mut self) {
+        self.cursor = None;
+    }
+
+    /// The message being browsed with its position counted back from the live
+    /// status, or `None` while showing the live status.
+    pub fn browsed(&self) -> Option<(&str, usize)> {
+        let i = self.cursor?;
+        let (_, entry) = self.entries.get(i)?;
+        Some((entry, self.entries.len() - 1 - i))
+    }
+}
+
+/// The time of day of `timestamp` in UTC, e.g. "14:05:09".
+pub fn clock(timestamp: u64) -> String {
+    let secs = timestamp % (24 * 60 * 60);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_skips_repeats() {
+        let mut history = StatusHistory::default();
+        history.push("a");
+        history.push("a");
+        history.push("");
+        history.push("b");
+        let statuses: Vec<_> = history.entries().map(|(_, status)| status).collect();
+        assert_eq!(statuses, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_browse() {
+        let mut history = StatusHistory::default();
+        for status in ["a", "b", "c"] {
+            history.push(status);
+        }
+        assert_eq!(history.browsed(), None);
+
+        history.older();
+        assert_eq!(history.browsed(), Some(("b", 1)));
+        history.older();
+        history.older();
+        assert_eq!(history.browsed(), Some(("a", 2)));
+
+        history.newer();
+        assert_eq!(history.browsed(), Some(("b", 1)));
+        history.newer();
+        assert_eq!(history.browsed(), None);
+
+        // New messages return to the live status
+        history.older();
+        history.push("d");
+        assert_eq!(history.browsed(), None);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut history = StatusHistory::default();
+        for i in 0..=CAPACITY {
+            history.push(&i.to_string());
+        }
+        assert_eq!(history.entries.len(), CAPACITY);
+        assert_eq!(history.entries().next().map(|(_, status)| status), Some("1"));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let mut history = StatusHistory::default();
+        assert!(history.is_empty());
+        history.push_at("Place your finger on the reader.", 1_700_000_000);
+        history.push_at("Device Busy", 1_700_000_065);
+        assert_eq!(
+            history.to_text(),
+            "22:13:20 Place your finger on the reader.\n22:14:25 Device Busy\n"
+        );
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(0), "00:00:00");
+        assert_eq!(clock(86_399), "23:59:59");
+        assert_eq!(clock(86_400 + 3_723), "01:02:03");
+    }
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Wording of the status codes fprintd reports during enrollment and verification.
+//!
+//! Integrators reusing the app can change the wording without touching the
+//! app logic by installing their own [`StatusFormatter`] with [`set_formatter`]
+//! before the app starts. Codes it doesn't handle keep the default wording,
+//! and codes nobody knows get a generic message naming them.
+
+use crate::fl;
+use fprint_client::fprint::ScanType;
+use std::sync::OnceLock;
+
+/// What a formatter knows about the operation besides the status code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatusContext {
+    pub scan_type: ScanType,
+    /// Whole seconds since the operation started.
+    pub secs: u64,
+}
+
+/// Turns status codes such as `enroll-stage-passed` into messages.
+pub trait StatusFormatter: Send + Sync {
+    /// The message for an enrollment status, `None` to leave it to the default wording.
+    fn enroll(&self, status: &str, context: StatusContext) -> Option<String>;
+
+    /// The message for a verification status, `None` to leave it to the default wording.
+    fn verify(&self, status: &str, context: StatusContext) -> Option<String>;
+}
+
+/// The app's own wording, from the translation files.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultFormatter;
+
+impl StatusFormatter for DefaultFormatter {
+    fn enroll(&self, status: &str, context: StatusContext) -> Option<String> {
+        Some(match status {
+            "enroll-stage-passed" => match context.scan_type {
+                ScanType::Press => fl!("enroll-stage-passed"),
+                ScanType::Swipe => fl!("enroll-stage-passed-swipe"),
+            },
+            "enroll-retry-scan" => fl!("enroll-retry-scan"),
+            "enroll-swipe-too-short" => fl!("enroll-swipe-too-short"),
+            "enroll-finger-not-centered" => fl!("enroll-finger-not-centered"),
+            "enroll-remove-and-retry" => fl!("enroll-remove-and-retry"),
+            "enroll-unknown-error" => fl!("enroll-unknown-error"),
+            "enroll-completed" => fl!("enroll-completed-in", secs = context.secs),
+            "enroll-failed" => fl!("enroll-failed"),
+            "enroll-disconnected" => fl!("enroll-disconnected"),
+            "enroll-data-full" => fl!("enroll-data-full"),
+            "enroll-too-fast" => fl!("enroll-too-fast"),
+            "enroll-duplicate" => fl!("enroll-duplicate"),
+            "enroll-cancelled" => fl!("enroll-cancelled"),
+            _ => return None,
+        })
+    }
+
+    fn verify(&self, status: &str, _context: StatusContext) -> Option<String> {
+        Some(match status {
+            "verify-match" => fl!("verify-match"),
+            "verify-no-match" => fl!("verify-no-match"),
+            "verify-retry-scan" => fl!("enroll-retry-scan"),
+            "verify-swipe-too-short" => fl!("enroll-swipe-too-short"),
+            "verify-finger-not-centered" => fl!("enroll-finger-not-centered"),
+            "verify-remove-and-retry" => fl!("enroll-remove-and-retry"),
+            "verify-disconnected" => fl!("enroll-disconnected"),
+            "verify-unknown-error" => fl!("enroll-unknown-error"),
+            "verify-cancelled" => fl!("verify-cancelled"),
+            _ => return None,
+        })
+    }
+}
+
+static FORMATTER: OnceLock<Box<dyn StatusFormatter>> = OnceLock::new();
+
+/// Puts `formatter` in front of the default wording.
+///
+/// Only the first call has an effect; returns whether it was this one.
+pub fn set_formatter(formatter: Box<dyn StatusFormatter>) -> bool {
+    FORMATTER.set(formatter).is_ok()
+}
+
+/// Longest part of an unknown status code that is shown.
+const UNKNOWN_STATUS_CHARS: usize = 40;
+
+/// The message for an enrollment status, falling back to [`unknown_message`].
+pub fn enroll_message(status: &str, context: StatusContext) -> String {
+    FORMATTER
+        .get()
+        .and_then(|formatter| formatter.enroll(status, context))
+        .or_else(|| DefaultFormatter.enroll(status, context))
+        .unwrap_or_else(|| unknown_message(status))
+}
+
+/// The message for a verification status, falling back to [`unknown_message`].
+pub fn verify_message(status: &str, context: StatusContext) -> String {
+    FORMATTER
+        .get()
+        .and_then(|formatter| formatter.verify(status, context))
+        .or_else(|| DefaultFormatter.verify(status, context))
+        .unwrap_or_else(|| unknown_message(status))
+}
+
+/// The message for a status code nobody knows. The code comes straight from
+/// the bus, so only a short, printable part of it is shown.
+pub fn unknown_message(status: &str) -> String {
+    let code: String = status
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(UNKNOWN_STATUS_CHARS)
+        .collect();
+    fl!("status-unknown", code = code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rewords a single made-up code, so other tests keep the default wording.
+    struct Shouting;
+
+    impl StatusFormatter for Shouting {
+        fn enroll(&self, status: &str, _context: StatusContext) -> Option<String> {
+            (status == "enroll-test-override").then(|| "OVERRIDDEN".to_string())
+        }
+
+        fn verify(&self, _status: &str, _context: StatusContext) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_default_wording() {
+        let swipe = StatusContext {
+            scan_type: ScanType::Swipe,
+            secs: 0,
+        };
+        assert_eq!(enroll_message("enroll-stage-passed", swipe), fl!("enroll-stage-passed-swipe"));
+        assert_eq!(verify_message("verify-match", swipe), fl!("verify-match"));
+        assert_eq!(
+            enroll_message("enroll-something-new", swipe),
+            fl!("status-unknown", code = "enroll-something-new")
+        );
+    }
+
+    #[test]
+    fn test_unknown_message() {
+        let context = StatusContext::default();
+        assert_eq!(verify_message("", context), fl!("status-unknown", code = ""));
+
+        let garbled = format!("\u{1b}[2J{}\n\u{0}", "x".repeat(500));
+        let message = verify_message(&garbled, context);
+        assert_eq!(message, enroll_message(&garbled, context));
+        assert!(!message.chars().any(char::is_control));
+        assert!(message.len() < 200);
+    }
+
+    #[test]
+    fn test_override_falls_back() {
+        set_formatter(Box::new(Shouting));
+        let context = StatusContext::default();
+        assert_eq!(enroll_message("enroll-test-override", context), "OVERRIDDEN");
+        assert_eq!(enroll_message("enroll-failed", context), fl!("enroll-failed"));
+    }
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::app::message::UserOption;
+use fprint_client::accounts_dbus::{AccountsProxy, UserProxy};
+use futures_util::stream::{self, Stream, StreamExt};
+use nix::unistd::{Uid, User};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const USER_FETCH_CONCURRENCY: usize = 10;
+
+/// `AccountType` values AccountsService uses for accounts of people.
+const ACCOUNT_TYPE_STANDARD: i32 = 0;
+const ACCOUNT_TYPE_ADMINISTRATOR: i32 = 1;
+
+/// Lists the accounts known to AccountsService, falling back to the current
+/// user if none can be read.
+pub async fn fetch_users(connection: &zbus::Connection) -> Vec<UserOption> {
+    let mut users = Vec::new();
+    if let Ok(accounts) = AccountsProxy::new(connection).await
+        && let Ok(user_paths) = accounts.list_cached_users().await
+    {
+        let fetched_users: Vec<_> = stream::iter(user_paths)
+            .map(|path| async move {
+                let builder = match UserProxy::builder(connection).path(&path) {
+                    Ok(builder) => builder,
+                    Err(e) => {
+                        tracing::error!(%e, "Failed to create UserProxy for path {path}");
+                        return Err(e);
+                    }
+                };
+
+                if let Ok(user_proxy) = builder.build().await
+                    && let (Ok(name), Ok(real_name)) =
+                        (user_proxy.user_name().await, user_proxy.real_name().await)
+                {
+                    // Older AccountsService versions lack these, so count
+                    // the account as a person's when they can't be read
+                    let system = is_system_account(
+                        user_proxy.system_account().await.unwrap_or(false),
+                        user_proxy
+                            .account_type()
+                            .await
+                            .unwrap_or(ACCOUNT_TYPE_STANDARD),
+                    );
+                    let avatar = user_proxy
+                        .icon_file()
+                        .await
+                        .ok()
+                        .map(PathBuf::from)
+                        .filter(|path| path.is_file())
+                        .map(Arc::new);
+                    Ok::<_, zbus::Error>(UserOption {
+                        username: Arc::new(name),
+                        realname: Arc::new(real_name),
+                        system,
+                        avatar,
+                    })
+                } else {
+                    Err(zbus::Error::Failure(
+                        "Failed to fetch user name or real name".to_string(),
+                    ))
+                }
+            })
+            .buffered(USER_FETCH_CONCURRENCY)
+            .filter_map(|res| async { res.ok() })
+            .collect()
+            .await;
+        users.extend(fetched_users);
+    }
+
+    // Fallback to current user if list is empty
+    if users.is_empty()
+        && let Ok(Some(user)) = User::from_uid(Uid::current())
+    {
+        users.push(UserOption {
+            username: Arc::new(user.name),
+            realname: Arc::new(user.gecos.to_string_lossy().into_owned()),
+            system: false,
+            avatar: None,
+        });
+    }
+    users
+}
+
+/// Yields each time AccountsService creates or deletes an account.
+///
+/// The stream ends when the connection to the bus is lost.
+pub async fn watch_users(
+    connection: &zbus::Connection,
+) -> zbus::Result<impl Stream<Item = ()> + use<>> {
+    let accounts = AccountsProxy::new(connection).await?;
+    let added = accounts.receive_user_added().await?;
+    let deleted = accounts.receive_user_deleted().await?;
+    Ok(stream::select(added.map(|_| ()), deleted.map(|_| ())))
+}
+
+/// Whether an account belongs to the system or a service rather than a person,
+/// from its `SystemAccount` and `AccountType` properties.
+fn is_system_account(system_account: bool, account_type: i32) -> bool {
+    system_account
+        || !matches!(
+            account_type,
+            ACCOUNT_TYPE_STANDARD | ACCOUNT_TYPE_ADMINISTRATOR
+        )
+}
+
+/// Reads the username out of text dropped onto the window.
+///
+/// Accepts a plain username or a URI, where the last path segment is taken, so a
+/// dragged home directory also works. Only the first line is looked at.
+pub fn parse_dropped_user(data: &str) -> Option<String> {
+    let line = data.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    let name = match line.split_once("://") {
+        Some((_, rest)) => rest.trim_end_matches('/').rsplit('/').next()?,
+        None => line.strip_prefix("user:").unwrap_or(line),
+    };
+
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    valid.then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fake_accounts::{FakeAccounts, FakeUser};
+    use std::time::Duration;
+
+    fn names(users: &[UserOption]) -> Vec<&str> {
+        users.iter().map(|user| user.username.as_str()).collect()
+    }
+
+    #[test]
+    fn test_is_system_account() {
+        assert!(!is_system_account(false, ACCOUNT_TYPE_STANDARD));
+        assert!(!is_system_account(false, ACCOUNT_TYPE_ADMINISTRATOR));
+        assert!(is_system_account(true, ACCOUNT_TYPE_STANDARD));
+        assert!(is_system_account(false, 7));
+    }
+
+    #[test]
+    fn test_parse_dropped_user() {
+        assert_eq!(parse_dropped_user("alice\n").as_deref(), Some("alice"));
+        assert_eq!(parse_dropped_user("user:bob").as_deref(), Some("bob"));
+        assert_eq!(
+            parse_dropped_user("file:///home/carol/\r\nfile:///home/dave").as_deref(),
+            Some("carol")
+        );
+        assert_eq!(parse_dropped_user("  "), None);
+        assert_eq!(parse_dropped_user("not a user"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_users_keeps_order() {
+        // Earlier accounts answer slower, which must not reorder the list
+        let users = ["alice", "bob", "carol"]
+            .iter()
+            .zip(1000..)
+            .map(|(name, uid)| FakeUser {
+                delay: Duration::from_millis(u64::from(1030 - uid) * 10),
+                ..FakeUser::new(uid, name)
+            })
+            .collect();
+        let (_accounts, connection) = FakeAccounts::serve(users).await;
+
+        let users = fetch_users(&connection).await;
+        assert_eq!(names(&users), ["alice", "bob", "carol"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_users_missing_properties() {
+        let legacy = FakeUser {
+            system_account: None,
+            account_type: None,
+            icon_file: None,
+            ..FakeUser::new(1000, "alice")
+        };
+        let service = FakeUser {
+            account_type: Some(2),
+            ..FakeUser::new(990, "printer")
+        };
+        let nameless = FakeUser {
+            real_name: None,
+            ..FakeUser::new(1001, "bob")
+        };
+        let (_accounts, connection) = FakeAccounts::serve(vec![legacy, service, nameless]).await;
+
+        let users = fetch_users(&connection).await;
+        // Without a real name the account can't be shown
+        assert_eq!(names(&users), ["alice", "printer"]);
+        assert!(!users[0].system);
+        assert_eq!(users[0].avatar, None);
+        assert!(users[1].system);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_users_falls_back_to_current_user() {
+        let current = User::from_uid(Uid::current()).unwrap().unwrap();
+
+        let (_accounts, connection) = FakeAccounts::broken().await;
+        assert_eq!(
+            names(&fetch_users(&connection).await),
+            [current.name.as_str()]
+        );
+
+        let (_accounts, connection) = FakeAccounts::serve(Vec::new()).await;
+        assert_eq!(
+            names(&fetch_users(&connection).await),
+            [current.name.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_users() {
+        let (accounts, connection) = FakeAccounts::serve(vec![FakeUser::new(1000, "alice")]).await;
+        let mut changes = watch_users(&connection).await.unwrap();
+
+        accounts.add_user(FakeUser::new(1001, "bob")).await;
+        changes.next().await.unwrap();
+        assert_eq!(names(&fetch_users(&connection).await), ["alice", "bob"]);
+
+        accounts.delete_user(1000).await;
+        changes.next().await.unwrap();
+        assert_eq!(names(&fetch_users(&connection).await), ["bob"]);
+    }
+}
@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The panel applet: which fingers of the current user are enrolled, and a
+//! shortcut to enroll another one without going through the full window.
+//!
+//! It talks to fprintd through the same backend as the window, and opens the
+//! window by launching the app again, which reaches the running instance.
+
+use crate::app::backend::{FprintBackend, ZbusBackend};
+use crate::app::error::AppError;
+use crate::app::finger_name::finger_id_to_localized;
+use crate::app::page::Page;
+use crate::{fl, i18n};
+use cosmic::iced::platform_specific::shell::commands::popup::{destroy_popup, get_popup};
+use cosmic::iced::window::Id;
+use cosmic::iced::{Length, Limits};
+use cosmic::prelude::*;
+use cosmic::widget::{self, text};
+use cosmic::{cosmic_theme, theme};
+use nix::unistd::{Uid, User};
+
+/// Icon shown in the panel, the one of the app.
+const ICON: &str = "fi.joonastuomi.Fprint";
+
+pub struct Applet {
+    core: cosmic::Core,
+    popup: Option<Id>,
+    /// Fingers of the current user, `None` while listing them.
+    fingers: Option<Result<Vec<String>, AppError>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TogglePopup,
+    PopupClosed(Id),
+    EnrolledFingers(Result<Vec<String>, AppError>),
+    /// Opens the window on the next finger to enroll.
+    EnrollNew,
+    OpenWindow,
+}
+
+impl cosmic::Application for Applet {
+    type Executor = cosmic::executor::Default;
+
+    type Flags = ();
+
+    type Message = Message;
+
+    const APP_ID: &'static str = "fi.joonastuomi.Fprint.Applet";
+
+    fn core(&self) -> &cosmic::Core {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut cosmic::Core {
+        &mut self.core
+    }
+
+    fn init(core: cosmic::Core, _flags: ()) -> (Self, Task<cosmic::Action<Message>>) {
+        let applet = Self {
+            core,
+            popup: None,
+            fingers: None,
+        };
+        (applet, Task::none())
+    }
+
+    fn on_close_requested(&self, id: Id) -> Option<Message> {
+        Some(Message::PopupClosed(id))
+    }
+
+    fn update(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::TogglePopup => {
+                if let Some(popup) = self.popup.take() {
+                    return destroy_popup(popup);
+                }
+                let Some(parent) = self.core.main_window_id() else {
+                    return Task::none();
+                };
+                let popup = Id::unique();
+                self.popup = Some(popup);
+                let mut settings = self
+                    .core
+                    .applet
+                    .get_popup_settings(parent, popup, None, None, None);
+                settings.positioner.size_limits = Limits::NONE.min_width(280.0).max_width(360.0);
+
+                // Listed again each time, as they may have changed in the window
+                self.fingers = None;
+                Task::batch(vec![get_popup(settings), list_fingers()])
+            }
+
+            Message::PopupClosed(id) => {
+                if self.popup == Some(id) {
+                    self.popup = None;
+                }
+                Task::none()
+            }
+
+            Message::EnrolledFingers(fingers) => {
+                self.fingers = Some(fingers);
+                Task::none()
+            }
+
+            Message::EnrollNew => {
+                let enrolled = self.enrolled();
+                let Some(finger) = next_to_enroll(enrolled).and_then(|page| page.as_finger_id())
+                else {
+                    return Task::none();
+                };
+                Task::batch(vec![self.close_popup(), open_window(&["--page", finger])])
+            }
+
+            Message::OpenWindow => Task::batch(vec![self.close_popup(), open_window(&[])]),
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        self.core
+            .applet
+            .icon_button(ICON)
+            .on_press_down(Message::TogglePopup)
+            .into()
+    }
+
+    fn view_window(&self, _id: Id) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let status: Element<'_, Message> = match &self.fingers {
+            None => text::body(fl!("applet-loading")).into(),
+            Some(Err(e)) => text::body(e.localized_message()).into(),
+            Some(Ok(fingers)) if fingers.is_empty() => {
+                text::body(fl!("applet-none-enrolled")).into()
+            }
+            Some(Ok(fingers)) => {
+                let names: Vec<String> =
+                    fingers.iter().map(|f| finger_id_to_localized(f)).collect();
+                widget::column()
+                    .push(text::heading(fl!("applet-enrolled", count = fingers.len())))
+                    .push(text::body(i18n::join_list(names)))
+                    .into()
+            }
+        };
+
+        let mut enroll_btn = widget::button::suggested(fl!("applet-enroll-new"));
+        if matches!(&self.fingers, Some(Ok(_))) && next_to_enroll(self.enrolled()).is_some() {
+            enroll_btn = enroll_btn.on_press(Message::EnrollNew);
+        }
+        let open_btn = widget::button::text(fl!("applet-open")).on_press(Message::OpenWindow);
+
+        let content = widget::column()
+            .push(status)
+            .push(
+                widget::row()
+                    .push(open_btn)
+                    .push(widget::Space::with_width(Length::Fill))
+                    .push(enroll_btn)
+                    .spacing(space_xs),
+            )
+            .spacing(space_s)
+            .padding(space_s)
+            .width(Length::Fill);
+
+        self.core.applet.popup_container(content).into()
+    }
+
+    fn style(&self) -> Option<cosmic::iced_runtime::Appearance> {
+        Some(cosmic::applet::style())
+    }
+}
+
+impl Applet {
+    fn enrolled(&self) -> &[String] {
+        match &self.fingers {
+            Some(Ok(fingers)) => fingers,
+            _ => &[],
+        }
+    }
+
+    fn close_popup(&mut self) -> Task<cosmic::Action<Message>> {
+        self.popup.take().map_or_else(Task::none, destroy_popup)
+    }
+}
+
+/// Lists the fingers again for the popup.
+fn list_fingers() -> Task<cosmic::Action<Message>> {
+    Task::perform(current_user_fingers(), |fingers| {
+        cosmic::Action::App(Message::EnrolledFingers(fingers))
+    })
+}
+
+/// The fingers of the current user on the default reader.
+async fn current_user_fingers() -> Result<Vec<String>, AppError> {
+    let username = User::from_uid(Uid::current())
+        .ok()
+        .flatten()
+        .map(|user| user.name)
+        .ok_or(AppError::Internal)?;
+    let backend = ZbusBackend::new(zbus::Connection::system().await?);
+    let device = backend
+        .find_devices()
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(AppError::DeviceNotFound)?;
+
+    match backend
+        .list_enrolled_fingers((*device.path).clone(), username)
+        .await
+    {
+        Ok(fingers) => Ok(fingers),
+        Err(e) => match AppError::from(e) {
+            e if *e.kind() == AppError::NoEnrolledPrints => Ok(Vec::new()),
+            e => Err(e),
+        },
+    }
+}
+
+/// Launches the app with `args`, which hands them to the running window or
+/// opens a new one.
+fn open_window(args: &[&str]) -> Task<cosmic::Action<Message>> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    Task::perform(
+        async move {
+            match std::env::current_exe() {
+                Ok(exe) => tokio::process::Command::new(exe).args(args).status().await,
+                Err(e) => Err(e),
+            }
+        },
+        |status| {
+            if let Err(e) = status {
+                tracing::warn!(%e, "Failed to open the window");
+            }
+            cosmic::Action::None
+        },
+    )
+}
+
+/// The finger to enroll next: the right index finger, which most readers are
+/// placed for, then the others in the order of the nav bar.
+fn next_to_enroll(enrolled: &[String]) -> Option<Page> {
+    std::iter::once(Page::default())
+        .chain(Page::all().iter().copied())
+        .filter_map(|page| Some((page, page.as_finger_id()?)))
+        .find(|(_, finger)| !enrolled.iter().any(|e| e == finger))
+        .map(|(page, _)| page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_to_enroll() {
+        assert_eq!(next_to_enroll(&[]), Some(Page::RightIndex));
+
+        let mut enrolled = vec!["right-index-finger".to_string()];
+        assert_eq!(next_to_enroll(&enrolled), Some(Page::RightThumb));
+
+        enrolled = Page::all()
+            .iter()
+            .filter_map(|page| page.as_finger_id())
+            .map(str::to_string)
+            .collect();
+        assert_eq!(next_to_enroll(&enrolled), None);
+    }
+}
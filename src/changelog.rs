@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Release notes bundled from the `CHANGES` file.
+
+const CHANGES: &str = include_str!("../CHANGES");
+
+/// Notes of a single release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release<'a> {
+    pub version: &'a str,
+    pub notes: Vec<&'a str>,
+}
+
+/// Returns the bundled release notes for `version`, if any.
+pub fn release(version: &str) -> Option<Release<'static>> {
+    parse(CHANGES).into_iter().find(|r| r.version == version)
+}
+
+/// Parses releases written either as `version: note` or as `version:`
+/// followed by indented `- note` lines.
+fn parse(changes: &str) -> Vec<Release<'_>> {
+    let mut releases: Vec<Release<'_>> = Vec::new();
+
+    for line in changes.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some(note) = line.trim().strip_prefix("- ")
+                && let Some(release) = releases.last_mut()
+            {
+                release.notes.push(note.trim());
+            }
+        } else if let Some((version, note)) = line.split_once(':') {
+            let note = note.trim();
+            releases.push(Release {
+                version: version.trim(),
+                notes: if note.is_empty() { Vec::new() } else { vec![note] },
+            });
+        }
+    }
+
+    releases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_line_and_list() {
+        let changes = "0.1.0: First release\n0.2.0:\n  - One: with colon\n  - Two\n\n0.3.0:\n";
+        let releases = parse(changes);
+
+        assert_eq!(
+            releases,
+            vec![
+                Release { version: "0.1.0", notes: vec!["First release"] },
+                Release { version: "0.2.0", notes: vec!["One: with colon", "Two"] },
+                Release { version: "0.3.0", notes: vec![] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_current_version_has_notes() {
+        let release = release(env!("CARGO_PKG_VERSION"));
+        assert!(release.is_some_and(|r| !r.notes.is_empty()));
+    }
+}
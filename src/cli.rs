@@ -0,0 +1,564 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Headless command line mode for scripting enrollment.
+
+use crate::app::error::AppError;
+use crate::app::open_target::{OpenTarget, parse_page};
+use crate::app::page::Page;
+use crate::app::users::fetch_users;
+use crate::fl;
+use fprint_client::fprint::{
+    DeviceInfo, Event, delete_fingerprint_dbus, enroll_fingerprint_process, find_devices,
+    list_enrolled_fingers_dbus, verify_fingerprint_process,
+};
+use fprint_client::fprint_dbus::DeviceProxy;
+use clap::{ArgGroup, Parser};
+use futures_util::sink;
+use nix::unistd::{Uid, User};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// Manage fingerprints with fprintd. Without an action, the window is opened.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+#[command(group(
+    ArgGroup::new("action").args(["enroll", "delete", "list", "verify", "bench_startup"])
+))]
+pub struct Cli {
+    /// Enroll a finger, e.g. right-index-finger
+    #[arg(long, value_name = "FINGER", value_parser = parse_finger)]
+    enroll: Option<String>,
+    /// Delete the print of a finger
+    #[arg(long, value_name = "FINGER", value_parser = parse_finger)]
+    delete: Option<String>,
+    /// List enrolled fingers
+    #[arg(long)]
+    list: bool,
+    /// Verify a finger, or any enrolled finger when none is given
+    #[arg(
+        long,
+        value_name = "FINGER",
+        value_parser = parse_verify_finger,
+        num_args = 0..=1,
+        default_missing_value = "any"
+    )]
+    verify: Option<String>,
+    /// User whose prints are managed, defaults to the current user. Without
+    /// an action, the user the window opens on
+    #[arg(long, short)]
+    user: Option<String>,
+    /// Time each step of the startup path and print a breakdown
+    #[arg(long, hide = true)]
+    bench_startup: bool,
+    /// Open the window with a simulated reader instead of fprintd
+    #[arg(long, conflicts_with = "action")]
+    demo: bool,
+    /// Open the window on a finger, e.g. right-index-finger, or on the overview
+    /// or test page
+    #[arg(
+        long,
+        visible_alias = "finger",
+        value_name = "PAGE",
+        value_parser = parse_page,
+        conflicts_with = "action"
+    )]
+    page: Option<Page>,
+    /// Start in the background and open the window once activated over DBus
+    #[arg(long, conflicts_with = "action")]
+    hidden: bool,
+    /// Run as an applet in the COSMIC panel
+    #[cfg(feature = "applet")]
+    #[arg(long, conflicts_with_all = ["action", "demo", "hidden"])]
+    applet: bool,
+    /// Write the debug log for this run, whatever the settings say
+    #[arg(long, short)]
+    verbose: bool,
+    /// Print the outcome of the action as one JSON object instead of text
+    #[arg(long, requires = "action", conflicts_with = "bench_startup")]
+    json: bool,
+}
+
+/// Exit status of an action that ran but didn't succeed, like a verification
+/// that didn't match, or of an error without a status of its own.
+const EXIT_FAILED: i32 = 1;
+
+/// The exit status and the name scripts see for `error`. 2 is left to usage
+/// errors, which clap exits with.
+fn error_code(error: &AppError) -> (i32, &'static str) {
+    match error.kind() {
+        AppError::DeviceNotFound => (3, "device-not-found"),
+        AppError::PermissionDenied => (4, "permission-denied"),
+        AppError::AlreadyInUse => (5, "already-in-use"),
+        AppError::ClaimDevice => (6, "claim-device"),
+        AppError::Timeout => (7, "timeout"),
+        AppError::NoEnrolledPrints => (8, "no-enrolled-prints"),
+        AppError::PrintsNotDeleted => (9, "prints-not-deleted"),
+        AppError::ServiceUnavailable => (10, "service-unavailable"),
+        AppError::ServiceNotInstalled => (11, "service-not-installed"),
+        AppError::ConnectDbus(_) => (12, "connect-dbus"),
+        AppError::Internal => (13, "internal"),
+        AppError::Unknown(_) | AppError::Dbus(..) => (EXIT_FAILED, "unknown"),
+    }
+}
+
+/// The outcome of an action, printed with `--json`.
+#[derive(Debug, Default, Serialize)]
+struct Report {
+    action: &'static str,
+    user: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finger: Option<String>,
+    success: bool,
+    exit_code: i32,
+    /// Enrolled fingers, when listing them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fingers: Vec<String>,
+    /// Each result fprintd reported while enrolling or verifying.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    statuses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorReport {
+    kind: &'static str,
+    message: String,
+    /// The DBus error behind it, if fprintd or the bus replied with one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dbus: Option<String>,
+}
+
+impl ErrorReport {
+    fn new(error: &AppError) -> Self {
+        Self {
+            kind: error_code(error).1,
+            message: error.localized_message(),
+            dbus: error.dbus_error().map(ToString::to_string),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Action {
+    Enroll(String),
+    Delete(String),
+    List,
+    Verify(String),
+}
+
+impl Action {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Enroll(_) => "enroll",
+            Self::Delete(_) => "delete",
+            Self::List => "list",
+            Self::Verify(_) => "verify",
+        }
+    }
+
+    fn finger(&self) -> Option<&str> {
+        match self {
+            Self::Enroll(finger) | Self::Delete(finger) | Self::Verify(finger) => Some(finger),
+            Self::List => None,
+        }
+    }
+}
+
+impl Cli {
+    fn action(&self) -> Option<Action> {
+        if let Some(finger) = &self.enroll {
+            Some(Action::Enroll(finger.clone()))
+        } else if let Some(finger) = &self.delete {
+            Some(Action::Delete(finger.clone()))
+        } else if self.list {
+            Some(Action::List)
+        } else {
+            self.verify.clone().map(Action::Verify)
+        }
+    }
+
+    /// Whether the window should use a simulated reader.
+    pub fn is_demo(&self) -> bool {
+        self.demo
+    }
+
+    /// Whether the debug log is written for this run.
+    pub fn is_verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// Page and user the window should open on, if any were given.
+    pub fn open_target(&self) -> Option<OpenTarget> {
+        let target = OpenTarget {
+            page: self.page,
+            user: self.user.clone(),
+        };
+        (!target.is_empty()).then_some(target)
+    }
+
+    /// Whether the app should start without its window.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Whether the app should run in the panel instead of a window.
+    #[cfg(feature = "applet")]
+    pub fn is_applet(&self) -> bool {
+        self.applet
+    }
+
+    /// Whether an action was requested instead of the window.
+    pub fn is_headless(&self) -> bool {
+        self.bench_startup || self.action().is_some()
+    }
+
+    /// Runs the requested action and returns the exit status for it, 0 when
+    /// it succeeded.
+    pub fn run(self) -> i32 {
+        let action = self.action();
+        if action.is_none() && !self.bench_startup {
+            return 0;
+        }
+        let json = self.json;
+
+        let Some(username) = self.user.or_else(|| {
+            User::from_uid(Uid::current())
+                .ok()
+                .flatten()
+                .map(|user| user.name)
+        }) else {
+            eprintln!("Could not determine the current user, pass --user");
+            return EXIT_FAILED;
+        };
+
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start the async runtime: {e}");
+                return EXIT_FAILED;
+            }
+        };
+
+        let mut report = Report {
+            action: action.as_ref().map_or("bench-startup", Action::name),
+            user: username.clone(),
+            finger: action.as_ref().and_then(Action::finger).map(str::to_string),
+            ..Report::default()
+        };
+        let outcome = runtime.block_on(async {
+            match action {
+                Some(action) => run_action(action, username, &mut report, json).await,
+                None => bench_startup(username).await,
+            }
+        });
+
+        let exit_code = match outcome {
+            Ok(true) => 0,
+            Ok(false) => EXIT_FAILED,
+            Err(e) => {
+                if !json {
+                    eprintln!("{}: {}", e.localized_title(), e.localized_detail());
+                    if let Some(dbus_error) = e.dbus_error() {
+                        eprintln!("{dbus_error}");
+                    }
+                }
+                report.error = Some(ErrorReport::new(&e));
+                error_code(&e).0
+            }
+        };
+
+        if json {
+            report.success = exit_code == 0;
+            report.exit_code = exit_code;
+            match serde_json::to_string(&report) {
+                Ok(report) => println!("{report}"),
+                Err(e) => eprintln!("Failed to write the report: {e}"),
+            }
+        }
+        exit_code
+    }
+}
+
+/// Returns whether the action succeeded, printing its progress to stdout
+/// unless it is collected into `report` for `--json`.
+async fn run_action(
+    action: Action,
+    username: String,
+    report: &mut Report,
+    json: bool,
+) -> Result<bool, AppError> {
+    let connection = zbus::Connection::system()
+        .await
+        .map_err(|e| AppError::ConnectDbus(e.to_string()))?;
+    let device = find_devices(&connection)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(AppError::DeviceNotFound)?;
+
+    match action {
+        Action::List => {
+            let proxy = device_proxy(&connection, &device).await?;
+            report.fingers = enrolled_or_none(list_enrolled_fingers_dbus(&proxy, username).await)?;
+            if !json {
+                for finger in &report.fingers {
+                    println!("{finger}");
+                }
+            }
+            Ok(true)
+        }
+        Action::Delete(finger) => {
+            delete_fingerprint_dbus(&connection, device.path.clone(), finger, username)
+                .await?;
+            Ok(true)
+        }
+        Action::Enroll(finger) => {
+            let statuses = json.then_some(&mut report.statuses);
+            report_progress(connection, &device, &finger, &username, false, statuses).await
+        }
+        Action::Verify(finger) => {
+            let statuses = json.then_some(&mut report.statuses);
+            report_progress(connection, &device, &finger, &username, true, statuses).await
+        }
+    }
+}
+
+/// Times the steps the app takes before it is ready, one after another.
+///
+/// The app runs device discovery and user enumeration concurrently, so the
+/// total here is an upper bound.
+async fn bench_startup(username: String) -> Result<bool, AppError> {
+    let mut steps: Vec<(&str, Duration)> = Vec::new();
+
+    let start = Instant::now();
+    let connection = zbus::Connection::system()
+        .await
+        .map_err(|e| AppError::ConnectDbus(e.to_string()))?;
+    steps.push(("connection", start.elapsed()));
+
+    let step = Instant::now();
+    let devices = find_devices(&connection).await?;
+    steps.push(("device discovery", step.elapsed()));
+
+    let step = Instant::now();
+    let users = fetch_users(&connection).await;
+    steps.push(("user enumeration", step.elapsed()));
+
+    if let Some(device) = devices.first() {
+        let step = Instant::now();
+        let proxy = device_proxy(&connection, device).await?;
+        list_enrolled_fingers_dbus(&proxy, username).await?;
+        steps.push(("first finger listing", step.elapsed()));
+    }
+
+    for (name, elapsed) in &steps {
+        println!("{name:<22}{:>10.1} ms", elapsed.as_secs_f64() * 1000.0);
+    }
+    println!("{:<22}{:>10.1} ms", "total", start.elapsed().as_secs_f64() * 1000.0);
+    println!("{} device(s), {} user(s)", devices.len(), users.len());
+
+    Ok(true)
+}
+
+/// Runs an enrollment or verification, printing each result reported by
+/// fprintd, or collecting them into `statuses` when given.
+async fn report_progress(
+    connection: zbus::Connection,
+    device: &DeviceInfo,
+    finger: &str,
+    username: &str,
+    verify: bool,
+    mut statuses: Option<&mut Vec<String>>,
+) -> Result<bool, AppError> {
+    let mut last_result = None;
+    let mut error = None;
+
+    {
+        let output = sink::unfold((), |(), event: Event| {
+            match event {
+                Event::EnrollStatus(result, _) | Event::VerifyStatus(result, _) => {
+                    match statuses.as_deref_mut() {
+                        Some(statuses) => statuses.push(result.clone()),
+                        None => println!("{result}"),
+                    }
+                    last_result = Some(result);
+                }
+                Event::InvalidSignal => {
+                    error = Some(AppError::Unknown(fl!("error-invalid-signal")))
+                }
+                _ => {}
+            }
+            async { Ok::<_, Infallible>(()) }
+        });
+        let mut output = std::pin::pin!(output);
+
+        if verify {
+            verify_fingerprint_process(connection, &device.path, finger, username, &mut output)
+                .await?;
+        } else {
+            enroll_fingerprint_process(connection, &device.path, finger, username, &mut output)
+                .await?;
+        }
+    }
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    let success = if verify { "verify-match" } else { "enroll-completed" };
+    Ok(last_result.as_deref() == Some(success))
+}
+
+async fn device_proxy(
+    connection: &zbus::Connection,
+    device: &DeviceInfo,
+) -> zbus::Result<DeviceProxy<'static>> {
+    DeviceProxy::builder(connection)
+        .path(device.path.clone())?
+        .build()
+        .await
+}
+
+/// fprintd reports a user without prints as an error, which is an empty list here.
+fn enrolled_or_none(fingers: zbus::Result<Vec<String>>) -> Result<Vec<String>, AppError> {
+    match fingers.map_err(AppError::from) {
+        Err(e) if *e.kind() == AppError::NoEnrolledPrints => Ok(Vec::new()),
+        fingers => fingers,
+    }
+}
+
+/// A finger like `right-index-finger`, refusing others as a usage error.
+fn parse_finger(finger: &str) -> Result<String, String> {
+    if Page::from_finger_id(finger).is_some() {
+        return Ok(finger.to_string());
+    }
+
+    let fingers: Vec<&str> = Page::all().iter().filter_map(Page::as_finger_id).collect();
+    Err(format!("Unknown finger {finger}, expected one of: {}", fingers.join(", ")))
+}
+
+/// A finger to verify, or `any` for any enrolled finger.
+fn parse_verify_finger(finger: &str) -> Result<String, String> {
+    match finger {
+        "any" => Ok(finger.to_string()),
+        _ => parse_finger(finger),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actions() {
+        let cli = Cli::parse_from(["cosmic-ext-fprint"]);
+        assert!(!cli.is_headless());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--enroll", "left-thumb", "-u", "alice"]);
+        assert!(matches!(cli.action(), Some(Action::Enroll(f)) if f == "left-thumb"));
+        assert_eq!(cli.user.as_deref(), Some("alice"));
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--bench-startup"]);
+        assert!(cli.is_headless());
+        assert!(cli.action().is_none());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--verify"]);
+        assert!(matches!(cli.action(), Some(Action::Verify(f)) if f == "any"));
+
+        assert!(Cli::try_parse_from(["cosmic-ext-fprint", "--list", "--delete", "left-thumb"]).is_err());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--demo"]);
+        assert!(cli.is_demo() && !cli.is_headless());
+        assert!(Cli::try_parse_from(["cosmic-ext-fprint", "--demo", "--list"]).is_err());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--hidden"]);
+        assert!(cli.is_hidden() && !cli.is_headless());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--verbose", "--list"]);
+        assert!(cli.is_verbose() && cli.is_headless());
+
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--page", "left-thumb", "-u", "alice"]);
+        let target = cli.open_target().unwrap();
+        assert_eq!(target.page, Some(Page::LeftThumb));
+        assert_eq!(target.user.as_deref(), Some("alice"));
+        assert!(!cli.is_headless());
+        assert!(Cli::try_parse_from(["cosmic-ext-fprint", "--page", "tail"]).is_err());
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--finger", "right-index-finger"]);
+        assert_eq!(cli.open_target().and_then(|t| t.page), Some(Page::RightIndex));
+        let page_and_action = ["cosmic-ext-fprint", "--page", "overview", "--list"];
+        assert!(Cli::try_parse_from(page_and_action).is_err());
+        assert_eq!(Cli::parse_from(["cosmic-ext-fprint"]).open_target(), None);
+    }
+
+    #[test]
+    fn test_json_report() {
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--list", "--json"]);
+        assert!(cli.json && cli.is_headless());
+        // Only actions have an outcome to report
+        assert!(Cli::try_parse_from(["cosmic-ext-fprint", "--json"]).is_err());
+        assert!(Cli::try_parse_from(["cosmic-ext-fprint", "--bench-startup", "--json"]).is_err());
+
+        let error = AppError::PermissionDenied;
+        let report = Report {
+            action: "delete",
+            user: "alice".to_string(),
+            finger: Some("left-thumb".to_string()),
+            exit_code: error_code(&error).0,
+            error: Some(ErrorReport::new(&error)),
+            ..Report::default()
+        };
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["exit_code"], 4);
+        assert_eq!(json["success"], false);
+        assert_eq!(json["error"]["kind"], "permission-denied");
+        assert!(json.get("fingers").is_none());
+    }
+
+    #[test]
+    fn test_error_codes_distinct() {
+        let errors = [
+            AppError::DeviceNotFound,
+            AppError::PermissionDenied,
+            AppError::AlreadyInUse,
+            AppError::ClaimDevice,
+            AppError::Timeout,
+            AppError::NoEnrolledPrints,
+            AppError::PrintsNotDeleted,
+            AppError::ServiceUnavailable,
+            AppError::ServiceNotInstalled,
+            AppError::ConnectDbus(String::new()),
+            AppError::Internal,
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(|e| error_code(e).0).collect();
+        assert!(codes.iter().all(|code| ![0, EXIT_FAILED, 2].contains(code)));
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+    }
+
+    #[test]
+    fn test_unknown_finger_is_usage_error() {
+        for action in ["--enroll", "--delete", "--verify"] {
+            let err = Cli::try_parse_from(["cosmic-ext-fprint", action, "right-toe"]).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+            assert_eq!(err.exit_code(), 2);
+        }
+        let cli = Cli::parse_from(["cosmic-ext-fprint", "--delete", "right-index-finger"]);
+        assert!(matches!(cli.action(), Some(Action::Delete(f)) if f == "right-index-finger"));
+    }
+
+    #[test]
+    fn test_list_without_prints() {
+        let msg = zbus::message::Message::method_call("/", "ListEnrolledFingers")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        let name = "net.reactivated.Fprint.Error.NoEnrolledPrints";
+        let name = zbus::names::ErrorName::try_from(name).unwrap();
+        let err = zbus::Error::MethodError(name.into(), None, msg);
+        assert_eq!(enrolled_or_none(Err(err)), Ok(Vec::new()));
+        assert!(enrolled_or_none(Err(zbus::Error::InvalidReply)).is_err());
+    }
+}
@@ -1,7 +1,175 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
+use cosmic::theme;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
+#[version = 1]
+pub struct Config {
+    /// Hide the dual-boot warning when clearing a match-on-chip reader.
+    pub suppress_dual_boot_warning: bool,
+    /// Version of the app that was last launched, used to show "What's new".
+    pub last_seen_version: String,
+    /// Seconds without enrollment or verification progress before the claimed
+    /// reader is released automatically. Zero disables the timeout.
+    pub idle_release_secs: u64,
+    /// Seconds an operation waits for a reader held by another program, such
+    /// as a login prompt, before failing. Zero fails right away.
+    pub busy_wait_secs: u64,
+    /// Require the selected user to verify a fingerprint before their prints are deleted.
+    pub require_verify_before_delete: bool,
+    /// Require administrator authentication through polkit before the device
+    /// is cleared.
+    pub require_auth_before_clear: bool,
+    /// Account selected at startup. Empty selects the account used last.
+    pub default_user: String,
+    /// Account last picked in the user picker, empty for the user running the app.
+    pub last_user: String,
+    /// Object path of the reader last picked, empty for fprintd's default one.
+    pub last_device: String,
+    /// Ask for confirmation before deleting prints.
+    pub confirm_before_delete: bool,
+    /// List every account in the user picker instead of only the current user.
+    pub show_all_users: bool,
+    /// Also list system and service accounts in the user picker.
+    pub show_system_users: bool,
+    /// Color scheme of the app.
+    pub app_theme: AppTheme,
+    /// Lock the reader in `XDG_RUNTIME_DIR` while claimed, so other instances
+    /// of the app can't use it at the same time.
+    pub lock_device: bool,
+    /// Last successful change to the prints of each user, keyed by username.
+    pub last_changes: HashMap<String, LastChange>,
+    /// Names given to fingers, keyed by username and then finger id.
+    pub finger_names: HashMap<String, HashMap<String, String>>,
+    /// Fingers left out of the nav bar while the selected user has no print
+    /// of them, by finger id.
+    pub hidden_fingers: Vec<String>,
+    /// File a JSON line is appended to for each enrollment, deletion and
+    /// verification. Empty disables the report.
+    pub report_path: String,
+    /// Shell command run for each enrollment, deletion and verification, with
+    /// the report on stdin. Empty disables it.
+    pub report_command: String,
+    /// Append every enrollment, deletion and clear to the operator log, for
+    /// machines shared by a helpdesk.
+    pub operator_mode: bool,
+    /// Write what the app does to the debug log, for bug reports.
+    pub debug_log: bool,
+    /// Send a desktop notification when an enrollment ends while the window
+    /// is in the background.
+    pub notify_in_background: bool,
+    /// Size the window was last left at, in logical pixels. Zero opens it at
+    /// the default size.
+    pub window_width: u32,
+    pub window_height: u32,
+    /// Whether the nav bar was hidden with its toggle in the header.
+    pub nav_bar_collapsed: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            suppress_dual_boot_warning: false,
+            last_seen_version: String::new(),
+            idle_release_secs: 120,
+            busy_wait_secs: 60,
+            require_verify_before_delete: false,
+            require_auth_before_clear: false,
+            default_user: String::new(),
+            last_user: String::new(),
+            last_device: String::new(),
+            confirm_before_delete: false,
+            show_all_users: true,
+            show_system_users: false,
+            app_theme: AppTheme::default(),
+            lock_device: true,
+            last_changes: HashMap::new(),
+            finger_names: HashMap::new(),
+            hidden_fingers: Vec::new(),
+            report_path: String::new(),
+            report_command: String::new(),
+            operator_mode: false,
+            debug_log: false,
+            notify_in_background: true,
+            window_width: 0,
+            window_height: 0,
+            nav_bar_collapsed: false,
+        }
+    }
+}
+
+impl Config {
+    /// Size to open the window at, `None` before it was ever resized.
+    pub fn window_size(&self) -> Option<cosmic::iced::Size> {
+        (self.window_width > 0 && self.window_height > 0)
+            .then(|| cosmic::iced::Size::new(self.window_width as f32, self.window_height as f32))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AppTheme {
+    Dark,
+    Light,
+    /// Follow the system color scheme.
+    #[default]
+    System,
+}
+
+impl AppTheme {
+    pub fn all() -> &'static [Self] {
+        &[Self::System, Self::Dark, Self::Light]
+    }
+
+    pub fn theme(&self) -> theme::Theme {
+        match self {
+            Self::Dark => {
+                let mut t = theme::system_dark();
+                t.theme_type.prefer_dark(Some(true));
+                t
+            }
+            Self::Light => {
+                let mut t = theme::system_light();
+                t.theme_type.prefer_dark(Some(false));
+                t
+            }
+            Self::System => theme::system_preference(),
+        }
+    }
+}
+
+/// A successful enrollment, deletion or verification, kept as a small audit trail.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LastChange {
+    pub kind: ChangeKind,
+    /// fprintd name of the finger, None for every print or any finger.
+    pub finger: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Enrolled,
+    Deleted,
+    Verified,
+}
+
+/// State persisted between runs, used to recover from interrupted operations.
 #[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
 #[version = 1]
-pub struct Config {}
+pub struct State {
+    /// Enrollment that was in progress when the app last exited.
+    pub interrupted_enrollment: Option<EnrollmentMarker>,
+    /// Users whose prints a device clear had not removed yet when it stopped.
+    pub unfinished_clear: Vec<String>,
+}
+
+/// Marks an enrollment as started until it completes, fails or is cancelled.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EnrollmentMarker {
+    pub username: String,
+    pub finger: String,
+}
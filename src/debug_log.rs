@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The debug log: what the app and fprint-client trace, with timestamps,
+//! written to `$XDG_STATE_HOME/cosmic-fprint/log` to attach to bug reports.
+//!
+//! It is written while turned on in the settings, or for the whole run when
+//! started with `--verbose`.
+
+use crate::app::operator_log::state_dir;
+use std::fs::File;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use tracing::{Level, Metadata};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+
+/// Size the log may reach before it is moved aside to `log.old` at startup.
+const MAX_LOG_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Most of the log copied for a bug report, taken from its end.
+const EXPORT_BYTES: usize = 256 * 1024;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Where the log is written.
+pub fn log_path() -> Option<PathBuf> {
+    Some(state_dir()?.join("log"))
+}
+
+/// Installs the subscriber writing the log, always on for `verbose`.
+pub fn init(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+    let Some(path) = log_path() else {
+        return;
+    };
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(LogFile::new(path))
+        .with_ansi(false)
+        .with_filter(filter_fn(wanted));
+    if let Err(why) = tracing_subscriber::registry().with(layer).try_init() {
+        eprintln!("failed to set up the debug log: {why}");
+    }
+}
+
+/// Turns the log on or off as set in the settings.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the log is being written.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) || VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Everything this app and fprint-client trace down to debug messages, and
+/// warnings from the libraries below them.
+fn wanted(metadata: &Metadata<'_>) -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    let target = metadata.target();
+    let ours = target.starts_with(env!("CARGO_CRATE_NAME")) || target.starts_with("fprint_client");
+    let level = if ours { Level::DEBUG } else { Level::WARN };
+    *metadata.level() <= level
+}
+
+/// The log file, opened on the first message so nothing is created while the
+/// log is off.
+struct LogFile {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+}
+
+impl LogFile {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: Mutex::new(None),
+        }
+    }
+
+    fn write(&self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        if file.is_none() {
+            *file = Some(open(&self.path)?);
+        }
+        file.as_mut().map_or(Ok(0), |file| file.write(buf))
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogFile {
+    type Writer = LogWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogWriter(self)
+    }
+}
+
+struct LogWriter<'a>(&'a LogFile);
+
+impl Write for LogWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Opens the log at `path` for appending, first moving a log that grew too
+/// large out of the way.
+fn open(path: &Path) -> std::io::Result<File> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    if std::fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_LOG_BYTES) {
+        std::fs::rename(path, path.with_extension("old"))?;
+    }
+
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(0o600)
+        .open(path)
+}
+
+/// The end of the log with where it is kept, to paste into a bug report.
+pub async fn export() -> Result<String, String> {
+    let path = log_path().ok_or("Neither XDG_STATE_HOME nor HOME is set")?;
+    let log = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(format!(
+        "{}\n\n{}",
+        path.display(),
+        tail(&log, EXPORT_BYTES)
+    ))
+}
+
+/// The last `max` bytes of `log`, starting at a whole line.
+fn tail(log: &[u8], max: usize) -> String {
+    let mut start = log.len().saturating_sub(max);
+    if start > 0 {
+        start = log[start..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(log.len(), |newline| start + newline + 1);
+    }
+    String::from_utf8_lossy(&log[start..]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail() {
+        let log = b"first line\nsecond line\nthird line\n";
+        assert_eq!(tail(log, 100), "first line\nsecond line\nthird line\n");
+        assert_eq!(tail(log, 15), "third line\n");
+        assert_eq!(tail(log, 3), "");
+    }
+
+    #[test]
+    fn test_log_file_moves_large_log_aside() {
+        let dir = std::env::temp_dir().join(format!("fprint-debug-log-{}", std::process::id()));
+        let path = dir.join("log");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, vec![b'x'; MAX_LOG_BYTES as usize + 1]).unwrap();
+
+        let log = LogFile::new(path.clone());
+        log.write(b"started\n").unwrap();
+        log.write(b"claimed the reader\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "started\nclaimed the reader\n"
+        );
+        assert!(path.with_extension("old").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
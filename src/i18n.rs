@@ -9,6 +9,7 @@ use i18n_embed::{
     fluent::{FluentLanguageLoader, fluent_language_loader},
     unic_langid::LanguageIdentifier,
 };
+use pure_rust_locales::{Locale, locale_match};
 use rust_embed::RustEmbed;
 
 /// Applies the requested language(s) to requested translations from the `fl!()` macro.
@@ -38,6 +39,66 @@ pub static LANGUAGE_LOADER: LazyLock<FluentLanguageLoader> = LazyLock::new(|| {
     loader
 });
 
+/// Every language shipped in i18n/, with the ids of the messages it translates.
+#[cfg(test)]
+pub fn shipped_messages() -> Vec<(LanguageIdentifier, Vec<String>)> {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    let languages = loader
+        .available_languages(&Localizations)
+        .expect("Error while listing languages");
+    loader
+        .load_languages(&Localizations, &languages)
+        .expect("Error while loading languages");
+
+    languages
+        .into_iter()
+        .map(|language| {
+            let ids = loader.with_message_iter(&language, |messages| {
+                messages.map(|message| message.id.name.to_string()).collect()
+            });
+            (language, ids)
+        })
+        .collect()
+}
+
+/// The locale of the language the app is shown in, used for formatting
+/// numbers the way its readers expect.
+pub fn active_locale() -> Locale {
+    locale_for(&LANGUAGE_LOADER.current_language())
+}
+
+/// Finds the locale for `language`, trying `fi_FI` for a bare `fi` and
+/// falling back to POSIX.
+fn locale_for(language: &LanguageIdentifier) -> Locale {
+    let lang = language.language.as_str();
+    let region = language
+        .region
+        .map_or_else(|| lang.to_uppercase(), |region| region.as_str().to_string());
+
+    [format!("{lang}_{region}"), lang.to_string()]
+        .iter()
+        .find_map(|name| Locale::try_from(name.as_str()).ok())
+        .unwrap_or(Locale::POSIX)
+}
+
+/// Formats `value` with `digits` decimals, using the decimal separator of the
+/// active locale.
+pub fn format_decimal(value: f64, digits: usize) -> String {
+    let separator = locale_match!(active_locale() => LC_NUMERIC::DECIMAL_POINT);
+    format!("{value:.digits$}").replacen('.', separator, 1)
+}
+
+/// Lists `items` with the separator of the shown language. Each item is
+/// isolated the way Fluent isolates the values it fills in, so names written
+/// right to left keep their place in the list.
+pub fn join_list<S: AsRef<str>>(items: impl IntoIterator<Item = S>) -> String {
+    let items: Vec<String> = items
+        .into_iter()
+        .map(|item| format!("\u{2068}{}\u{2069}", item.as_ref()))
+        .collect();
+    items.join(&crate::fl!("list-separator"))
+}
+
 /// Request a localized string by ID from the i18n/ directory.
 #[macro_export]
 macro_rules! fl {
@@ -49,3 +110,33 @@ macro_rules! fl {
         i18n_embed_fl::fl!($crate::i18n::LANGUAGE_LOADER, $message_id, $($args), *)
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_for() {
+        let locale = |id: &str| locale_for(&id.parse().unwrap());
+        assert_eq!(locale("fi-FI"), Locale::fi_FI);
+        assert_eq!(locale("fi"), Locale::fi_FI);
+        assert_eq!(locale("pt-BR"), Locale::pt_BR);
+        assert_eq!(locale("zz"), Locale::POSIX);
+    }
+
+    #[test]
+    fn test_format_decimal() {
+        // Tests run with the English fallback
+        assert_eq!(format_decimal(2.26, 1), "2.3");
+        assert_eq!(format_decimal(12.0, 1), "12.0");
+    }
+
+    #[test]
+    fn test_join_list() {
+        assert_eq!(
+            join_list(["alice", "bob"]),
+            "\u{2068}alice\u{2069}, \u{2068}bob\u{2069}"
+        );
+        assert_eq!(join_list(Vec::<String>::new()), "");
+    }
+}
@@ -1,16 +1,20 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod app;
+#[cfg(feature = "applet")]
+mod applet;
+mod changelog;
+mod cli;
 mod config;
-mod fprint_dbus;
-mod accounts_dbus;
+mod debug_log;
 mod i18n;
 
 extern crate tracing;
 extern crate zbus;
 
-const WINDOW_MIN_WIDTH: f32 = 400.0;
-const WINDOW_MIN_HEIGHT: f32 = 380.0;
+use clap::Parser;
+use cosmic::Application;
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
 
 fn main() -> cosmic::iced::Result {
     // Get the system's preferred languages.
@@ -19,13 +23,45 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Run a command line action instead of opening the window if one was given.
+    let cli = cli::Cli::parse();
+    debug_log::init(cli.is_verbose());
+    if cli.is_headless() {
+        std::process::exit(cli.run());
+    }
+
+    // Run in the panel instead of opening the window
+    #[cfg(feature = "applet")]
+    if cli.is_applet() {
+        return cosmic::applet::run::<applet::Applet>(());
+    }
+
     // Settings for configuring the application window and iced runtime.
-    let settings = cosmic::app::Settings::default().size_limits(
-        cosmic::iced::Limits::NONE
-            .min_width(WINDOW_MIN_WIDTH)
-            .min_height(WINDOW_MIN_HEIGHT),
-    );
-
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::app::run::<app::AppModel>(settings, ())
+    let mut settings = cosmic::app::Settings::default()
+        .size_limits(
+            cosmic::iced::Limits::NONE
+                .min_width(app::WINDOW_MIN_WIDTH)
+                .min_height(app::WINDOW_MIN_HEIGHT),
+        )
+        // The window is opened once the app is activated over DBus
+        .no_main_window(cli.is_hidden());
+
+    // Reopen the window at the size it was left at
+    let saved = cosmic_config::Config::new(app::AppModel::APP_ID, config::Config::VERSION)
+        .map(|context| config::Config::get_entry(&context).unwrap_or_else(|(_, config)| config))
+        .unwrap_or_default();
+    if let Some(size) = saved.window_size() {
+        settings = settings.size(cosmic::iced::Size::new(
+            size.width.max(app::WINDOW_MIN_WIDTH),
+            size.height.max(app::WINDOW_MIN_HEIGHT),
+        ));
+    }
+
+    let flags = app::Flags {
+        demo: cli.is_demo(),
+        open: cli.open_target(),
+    };
+
+    // Starts the application's event loop, or activates the instance already running.
+    cosmic::app::run_single_instance::<app::AppModel>(settings, flags)
 }